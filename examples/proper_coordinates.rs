@@ -2,8 +2,7 @@
 
 use solarviewer::stellar_forge::{
     coordinates::{
-        GalacticCoordinates, EquatorialCoordinates,
-        CoordinateTransform, CoordinateFormatter, ReferencePositions,
+        CoordinateTransform, CoordinateFormatter, ReferencePositions, HeliocentricVelocity,
     },
     builders::SystemBuilder,
 };
@@ -128,7 +127,7 @@ fn main() {
         CoordinateFormatter::format_cartesian(heliocentric_vel / 1000.0, "km/s")
     );
 
-    let lsr_vel = CoordinateTransform::apply_lsr_correction(heliocentric_vel);
+    let lsr_vel = CoordinateTransform::apply_lsr_correction(HeliocentricVelocity(heliocentric_vel)).0;
     println!("   LSR-corrected velocity: {:.1} km/s",
         CoordinateFormatter::format_cartesian(lsr_vel / 1000.0, "km/s")
     );
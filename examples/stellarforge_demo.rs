@@ -2,10 +2,10 @@
 
 use solarviewer::stellar_forge::{
     builders::{SystemBuilder, PlanetBuilder, MoonBuilder, GalaxyBuilder, create_sol_like_system},
-    containers::Galaxy,
     storage::{StellarForgeDataset, FileStorage},
-    associations::{Tag, TagCategories},
+    associations::TagCategories,
     services::StellarForgeService,
+    core::Container,
 };
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -39,7 +39,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("   Created: {} with {} planets", sol.name, sol.planets.len());
     for planet in &sol.planets {
-        println!("     - {} with {} moons", planet.name(), planet.child_count());
+        println!("     - {} with {} moons", planet.name, planet.child_count());
     }
 
     // Example 3: Create a custom system with multiple planet types
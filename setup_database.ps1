@@ -1,8 +1,9 @@
 # PowerShell script to setup PostgreSQL with PostGIS for StellarForge
-# Password: Beta5357
+# Pass the postgres password with -PostgresPassword; it is never stored in the repository
 
 param(
-    [string]$PostgresPassword = "Beta5357",
+    [Parameter(Mandatory = $true)]
+    [string]$PostgresPassword,
     [string]$DatabaseName = "stellarforge",
     [string]$PostgresUser = "postgres",
     [string]$Host = "localhost",
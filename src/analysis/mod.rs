@@ -0,0 +1,5 @@
+pub mod network;
+
+pub use network::{
+    betweenness_centrality, connection_centrality, reachable_components, shortest_jump_path, JumpPath,
+};
@@ -0,0 +1,281 @@
+/// Jump-network analysis over stars and their `find_connections` links
+use std::cmp::Reverse;
+use std::collections::{HashSet, VecDeque};
+
+use crate::extraction::Star;
+use crate::visualization::enhanced_renderer::StarConnection;
+
+/// Neighbor lists for each star index
+fn adjacency(star_count: usize, connections: &[StarConnection]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); star_count];
+    for c in connections {
+        neighbors[c.from_idx].push(c.to_idx);
+        neighbors[c.to_idx].push(c.from_idx);
+    }
+    neighbors
+}
+
+/// Number of connections per star as (star index, degree), busiest hubs first
+/// Ties keep star order. `connections` must index into `stars`.
+pub fn connection_centrality(stars: &[Star], connections: &[StarConnection]) -> Vec<(usize, usize)> {
+    let mut degrees: Vec<(usize, usize)> = adjacency(stars.len(), connections)
+        .iter()
+        .map(Vec::len)
+        .enumerate()
+        .collect();
+    degrees.sort_by_key(|&(_, degree)| Reverse(degree));
+    degrees
+}
+
+/// Betweenness centrality as (star index, score), biggest chokepoints first
+/// The score is how many shortest jump paths (counted in jumps, not light-years) between
+/// other pairs of stars pass through a star, split evenly among equally short paths.
+/// This is the in-memory counterpart of the database's route-based `find_chokepoints`.
+pub fn betweenness_centrality(stars: &[Star], connections: &[StarConnection]) -> Vec<(usize, f64)> {
+    let neighbors = adjacency(stars.len(), connections);
+    let n = stars.len();
+    let mut scores = vec![0.0; n];
+
+    // Brandes' algorithm: one breadth-first search per source, then accumulate backwards
+    for source in 0..n {
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut path_counts = vec![0.0; n];
+        let mut depth: Vec<Option<usize>> = vec![None; n];
+        path_counts[source] = 1.0;
+        depth[source] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let next_depth = depth[v].map(|d| d + 1);
+            for &w in &neighbors[v] {
+                if depth[w].is_none() {
+                    depth[w] = next_depth;
+                    queue.push_back(w);
+                }
+                if depth[w] == next_depth {
+                    path_counts[w] += path_counts[v];
+                    predecessors[w].push(v);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &predecessors[w] {
+                dependency[v] += path_counts[v] / path_counts[w] * (1.0 + dependency[w]);
+            }
+            if w != source {
+                scores[w] += dependency[w];
+            }
+        }
+    }
+
+    // Each undirected path was counted once from each end
+    let mut ranked: Vec<(usize, f64)> = scores.into_iter().map(|s| s / 2.0).enumerate().collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+    ranked
+}
+
+/// Groups of star indices that can reach each other in jumps of at most `max_jump_ly`
+/// Each group is sorted, and groups are ordered by their first star, so isolated stars
+/// show up as single-star groups.
+pub fn reachable_components(stars: &[Star], max_jump_ly: f64) -> Vec<Vec<usize>> {
+    let mut component: Vec<Option<usize>> = vec![None; stars.len()];
+    let mut components: Vec<Vec<usize>> = Vec::new();
+
+    for start in 0..stars.len() {
+        if component[start].is_some() {
+            continue;
+        }
+        let id = components.len();
+        component[start] = Some(id);
+        let mut members = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            for w in 0..stars.len() {
+                if component[w].is_none() && stars[v].distance_to(&stars[w]) <= max_jump_ly {
+                    component[w] = Some(id);
+                    members.push(w);
+                    queue.push_back(w);
+                }
+            }
+        }
+        members.sort_unstable();
+        components.push(members);
+    }
+
+    components
+}
+
+/// A route through the jump network
+#[derive(Debug, Clone, PartialEq)]
+pub struct JumpPath {
+    /// Star indices from origin to destination, both included
+    pub stars: Vec<usize>,
+    /// Sum of the jump lengths in light-years
+    pub distance_ly: f64,
+}
+
+/// Shortest route by total distance from star `from` to star `to`, with no single jump
+/// longer than `max_jump_ly` and never passing through a star in `avoid`, or None when
+/// no such route exists
+/// This is the file-based counterpart of the database's `find_safe_route`; an avoided
+/// origin or destination leaves no route at all.
+pub fn shortest_jump_path(
+    stars: &[Star],
+    from: usize,
+    to: usize,
+    max_jump_ly: f64,
+    avoid: &HashSet<usize>,
+) -> Option<JumpPath> {
+    let n = stars.len();
+    if from >= n || to >= n || avoid.contains(&from) || avoid.contains(&to) {
+        return None;
+    }
+
+    // Dijkstra with a linear scan for the next star; jumps are computed on the fly, since
+    // the jump graph can be nearly complete for generous limits
+    let mut distance = vec![f64::INFINITY; n];
+    let mut previous: Vec<Option<usize>> = vec![None; n];
+    // Avoided stars start out settled so they are never entered
+    let mut settled: Vec<bool> = (0..n).map(|i| avoid.contains(&i)).collect();
+    distance[from] = 0.0;
+
+    while let Some(v) = (0..n)
+        .filter(|&i| !settled[i] && distance[i].is_finite())
+        .min_by(|&a, &b| distance[a].total_cmp(&distance[b]))
+    {
+        if v == to {
+            break;
+        }
+        settled[v] = true;
+        for w in (0..n).filter(|&w| !settled[w]) {
+            let jump = stars[v].distance_to(&stars[w]);
+            if jump <= max_jump_ly && distance[v] + jump < distance[w] {
+                distance[w] = distance[v] + jump;
+                previous[w] = Some(v);
+            }
+        }
+    }
+
+    if !distance[to].is_finite() {
+        return None;
+    }
+    let mut path = vec![to];
+    while let Some(prev) = previous[*path.last().unwrap()] {
+        path.push(prev);
+    }
+    path.reverse();
+    Some(JumpPath { stars: path, distance_ly: distance[to] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(id: i32, x: f64, y: f64) -> Star {
+        Star {
+            id,
+            name: format!("Star {}", id),
+            spectral_type: "K2V".to_string(),
+            radius_solar: 0.8,
+            mass_solar: 0.8,
+            luminosity_solar: 0.3,
+            temperature_k: 5000.0,
+            x,
+            y,
+            system_x: x,
+            system_y: y,
+            ..Default::default()
+        }
+    }
+
+    fn link(from_idx: usize, to_idx: usize) -> StarConnection {
+        StarConnection { from_idx, to_idx, distance_ly: 1.0 }
+    }
+
+    #[test]
+    fn test_bridge_star_has_highest_betweenness() {
+        // Two triangles, 0-1-2 and 4-5-6, joined only through star 3
+        let stars: Vec<Star> = (0..7).map(|i| star(i, i as f64, 0.0)).collect();
+        let connections = vec![
+            link(0, 1), link(1, 2), link(0, 2),
+            link(2, 3), link(3, 4),
+            link(4, 5), link(5, 6), link(4, 6),
+        ];
+
+        let betweenness = betweenness_centrality(&stars, &connections);
+        assert_eq!(betweenness[0].0, 3);
+        // All 3 x 3 cross-cluster paths pass through the bridge
+        assert!((betweenness[0].1 - 9.0).abs() < 1e-9);
+        assert!(betweenness[0].1 > betweenness[1].1);
+
+        let degrees = connection_centrality(&stars, &connections);
+        assert_eq!(degrees[0], (2, 3));
+        assert_eq!(degrees.iter().find(|(i, _)| *i == 3), Some(&(3, 2)));
+    }
+
+    #[test]
+    fn test_clusters_split_beyond_jump_range() {
+        // Two chains of stars 3 ly apart, with a 20 ly gap between the chains
+        let stars: Vec<Star> = [0.0, 3.0, 6.0, 26.0, 29.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| star(i as i32, x, 0.0))
+            .collect();
+
+        let components = reachable_components(&stars, 5.0);
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3, 4]]);
+
+        let components = reachable_components(&stars, 20.0);
+        assert_eq!(components, vec![vec![0, 1, 2, 3, 4]]);
+
+        assert_eq!(reachable_components(&stars, 1.0).len(), stars.len());
+    }
+
+    #[test]
+    fn test_shortest_jump_path_takes_shortest_route() {
+        // From 0 to 2 either via 1 (5 + 5 ly) or via 3 (5.66 + 5.66 ly); the direct
+        // jump is 8 ly
+        let stars = vec![
+            star(0, 0.0, 0.0),
+            star(1, 4.0, 3.0),
+            star(2, 8.0, 0.0),
+            star(3, 4.0, -4.0),
+            star(4, 30.0, 0.0),
+        ];
+        let none = HashSet::new();
+
+        let path = shortest_jump_path(&stars, 0, 2, 6.0, &none).unwrap();
+        assert_eq!(path.stars, vec![0, 1, 2]);
+        assert!((path.distance_ly - 10.0).abs() < 1e-9);
+
+        let path = shortest_jump_path(&stars, 0, 2, 9.0, &none).unwrap();
+        assert_eq!(path.stars, vec![0, 2]);
+
+        assert_eq!(shortest_jump_path(&stars, 0, 2, 4.0, &none), None);
+        assert_eq!(shortest_jump_path(&stars, 0, 4, 9.0, &none), None);
+        assert_eq!(shortest_jump_path(&stars, 3, 3, 1.0, &none).unwrap().stars, vec![3]);
+    }
+
+    #[test]
+    fn test_avoided_star_forces_a_detour() {
+        let stars = vec![
+            star(0, 0.0, 0.0),
+            star(1, 4.0, 3.0),
+            star(2, 8.0, 0.0),
+            star(3, 4.0, -4.0),
+        ];
+
+        // Star 1 is hostile, so the longer southern route via 3 is the only one left
+        let avoid = HashSet::from([1]);
+        let path = shortest_jump_path(&stars, 0, 2, 6.0, &avoid).unwrap();
+        assert_eq!(path.stars, vec![0, 3, 2]);
+        assert!((path.distance_ly - 2.0 * 32f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(shortest_jump_path(&stars, 0, 2, 6.0, &HashSet::from([1, 3])), None);
+        assert_eq!(shortest_jump_path(&stars, 0, 2, 9.0, &HashSet::from([2])), None);
+    }
+}
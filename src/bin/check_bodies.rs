@@ -53,11 +53,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         ))
     })?;
 
-    for sample in samples {
-        if let Ok((id, name, body_type, parent_id, spectral)) = sample {
-            println!("  ID: {}, Name: '{}', Type: '{:?}', Parent: {}, Spectral: '{:?}'",
-                     id, name, body_type, parent_id, spectral);
-        }
+    for (id, name, body_type, parent_id, spectral) in samples.flatten() {
+        println!("  ID: {}, Name: '{}', Type: '{:?}', Parent: {}, Spectral: '{:?}'",
+                 id, name, body_type, parent_id, spectral);
     }
 
     Ok(())
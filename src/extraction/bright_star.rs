@@ -0,0 +1,131 @@
+use std::fs::File;
+use std::io::Write;
+use anyhow::Result;
+
+use super::reader::sol_position;
+use super::Star;
+use crate::stellar_forge::coordinates::CoordinateTransform;
+
+/// Export a Bright Star style catalog as seen from Sol
+/// Sol is found the same way as for `apply_observables`.
+pub fn export_bright_star_catalog(stars: &[Star], max_magnitude: f64, output_path: &str) -> Result<usize> {
+    export_bright_star_catalog_from(stars, sol_position(stars), max_magnitude, output_path)
+}
+
+/// Export every star brighter than `max_magnitude` as seen from `vantage` (light-years),
+/// brightest first, with RA/Dec and distance measured from the vantage point
+/// Returns the number of catalog entries written.
+pub fn export_bright_star_catalog_from(
+    stars: &[Star],
+    vantage: (f64, f64, f64),
+    max_magnitude: f64,
+    output_path: &str,
+) -> Result<usize> {
+    let mut entries: Vec<(Star, f64)> = stars
+        .iter()
+        .filter_map(|star| {
+            let mut observed = star.clone();
+            observed.compute_observables(vantage);
+            let magnitude = observed.apparent_magnitude?;
+            (magnitude <= max_magnitude).then_some((observed, magnitude))
+        })
+        .collect();
+    entries.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut file = File::create(output_path)?;
+    writeln!(file, "Rank,Name,Spectral Type,Apparent Magnitude,RA (hours),Dec (deg),Distance (ly)")?;
+
+    for (rank, (star, magnitude)) in entries.iter().enumerate() {
+        let (dx, dy, dz) = (star.x - vantage.0, star.y - vantage.1, star.z - vantage.2);
+        let equatorial = CoordinateTransform::galactic_to_equatorial(
+            CoordinateTransform::astrosynthesis_to_galactic(dx, dy, dz),
+        );
+        writeln!(
+            file,
+            "{},\"{}\",\"{}\",{:.2},{:.6},{:.6},{:.3}",
+            rank + 1,
+            // Quotes inside quoted fields are doubled (CSV standard)
+            star.display_name().replace("\"", "\"\""),
+            star.spectral_type.replace("\"", "\"\""),
+            magnitude,
+            equatorial.ra_hours(),
+            equatorial.dec_degrees(),
+            (dx * dx + dy * dy + dz * dz).sqrt()
+        )?;
+    }
+
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(name: &str, x: f64, luminosity_solar: f64) -> Star {
+        Star {
+            id: 0,
+            name: name.to_string(),
+            spectral_type: "G2V".to_string(),
+            radius_solar: 1.0,
+            mass_solar: 1.0,
+            luminosity_solar,
+            temperature_k: 5778.0,
+            x,
+            y: 0.0,
+            z: 0.0,
+            system_name: None,
+            system_x: x,
+            system_y: 0.0,
+            system_z: 0.0,
+            catalog_id: None,
+            hip: None,
+            hd: None,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        }
+    }
+
+    #[test]
+    fn test_bright_star_catalog_sorted_and_limited() {
+        let stars = vec![
+            star("Sol", 0.0, 1.0),
+            star("Dim", 30.0, 0.01),       // about +9.7
+            star("Sirius-ish", 8.6, 25.0), // about -1.6
+            star("Alpha", 4.4, 1.5),       // about 0.0
+            star("Faraway", 500.0, 1.0),   // about +10.8
+        ];
+
+        let path = std::env::temp_dir().join("solarviewer_bright_star_test.csv");
+        let written = export_bright_star_catalog(&stars, 6.0, path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let names: Vec<&str> = csv.lines().skip(1).map(|line| line.split(',').nth(1).unwrap()).collect();
+        assert_eq!(written, 2);
+        assert_eq!(names, vec!["\"Sirius-ish\"", "\"Alpha\""]);
+
+        let magnitudes: Vec<f64> = csv
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(3).unwrap().parse().unwrap())
+            .collect();
+        assert!(magnitudes.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(magnitudes.iter().all(|m| *m <= 6.0));
+    }
+
+    #[test]
+    fn test_bright_star_catalog_escapes_quotes() {
+        let mut flare = star("Flare \"Star\"", 4.4, 1.5);
+        flare.spectral_type = "M4.5Ve \"flare\", var".to_string();
+        let stars = vec![star("Sol", 0.0, 1.0), flare];
+
+        let path = std::env::temp_dir().join("solarviewer_bright_star_quotes.csv");
+        export_bright_star_catalog(&stars, 6.0, path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("1,\"Flare \"\"Star\"\"\",\"M4.5Ve \"\"flare\"\", var\","));
+    }
+}
@@ -2,15 +2,35 @@ use std::fs::File;
 use std::io::Write;
 use anyhow::Result;
 
+use super::reader::sol_position;
 use super::Star;
 
+/// Optional column groups for CSV export
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvOptions {
+    /// Append J2000 right ascension (hours) and declination (degrees)
+    pub include_equatorial: bool,
+}
+
 /// Export stars to a CSV file with comprehensive stellar data
 /// Includes multi-star system information when applicable
 pub fn export_stars_to_csv(stars: &[Star], output_path: &str) -> Result<()> {
+    export_stars_to_csv_with(stars, output_path, CsvOptions::default())
+}
+
+/// Export stars to CSV, adding the optional columns selected in `options`
+pub fn export_stars_to_csv_with(stars: &[Star], output_path: &str, options: CsvOptions) -> Result<()> {
     let mut file = File::create(output_path)?;
 
     // Write header row
-    writeln!(file, "Name,Spectral Type,Radius (Solar),Mass (Solar),Luminosity (Solar),Temperature (K),Star X,Star Y,Star Z,System Name,System X,System Y,System Z")?;
+    write!(file, "Name,Spectral Type,Radius (Solar),Mass (Solar),Luminosity (Solar),Temperature (K),Star X,Star Y,Star Z,System Name,System X,System Y,System Z,Catalog ID,HIP,HD,Parallax (mas),Apparent Magnitude")?;
+    if options.include_equatorial {
+        write!(file, ",RA (hours),Dec (deg)")?;
+    }
+    writeln!(file)?;
+
+    // RA/Dec are as seen from Sol, like the catalogs they are compared against
+    let sol = sol_position(stars);
 
     // Write data rows
     for star in stars {
@@ -19,9 +39,15 @@ pub fn export_stars_to_csv(stars: &[Star], output_path: &str) -> Result<()> {
             None => "",
         };
 
-        writeln!(
+        let catalog_id = star.catalog_id.as_deref().unwrap_or("");
+        let hip = star.hip.map(|n| n.to_string()).unwrap_or_default();
+        let hd = star.hd.map(|n| n.to_string()).unwrap_or_default();
+        let parallax = star.parallax_mas.map(|p| format!("{:.4}", p)).unwrap_or_default();
+        let magnitude = star.apparent_magnitude.map(|m| format!("{:.3}", m)).unwrap_or_default();
+
+        write!(
             file,
-            "\"{}\",\"{}\",{},{},{},{},{},{},\"{}\",{},{},{}",
+            "\"{}\",\"{}\",{},{},{},{},{},{},{},\"{}\",{},{},{},\"{}\",{},{},{},{}",
             // Escape quotes in names by doubling them (CSV standard)
             star.name.replace("\"", "\"\""),
             star.spectral_type,
@@ -34,8 +60,20 @@ pub fn export_stars_to_csv(stars: &[Star], output_path: &str) -> Result<()> {
             star.z,
             system_name.replace("\"", "\"\""),
             star.system_x,
-            star.system_y
+            star.system_y,
+            star.system_z,
+            catalog_id.replace("\"", "\"\""),
+            hip,
+            hd,
+            parallax,
+            magnitude
         )?;
+
+        if options.include_equatorial {
+            let equatorial = star.equatorial_coordinates_from(sol);
+            write!(file, ",{:.6},{:.6}", equatorial.ra_hours(), equatorial.dec_degrees())?;
+        }
+        writeln!(file)?;
     }
 
     Ok(())
@@ -0,0 +1,163 @@
+/// Catalog-based deduplication for stars loaded from overlapping catalogs
+use std::collections::HashMap;
+
+use super::Star;
+
+/// Catalog designations that identify the same physical star across files
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum CatalogKey {
+    Designation(String),
+    Hip(u32),
+    Hd(u32),
+}
+
+fn catalog_keys(star: &Star) -> Vec<CatalogKey> {
+    let designation = star
+        .catalog_id
+        .as_deref()
+        .map(|id| id.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase())
+        .filter(|id| !id.is_empty())
+        .map(CatalogKey::Designation);
+
+    designation
+        .into_iter()
+        .chain(star.hip.map(CatalogKey::Hip))
+        .chain(star.hd.map(CatalogKey::Hd))
+        .collect()
+}
+
+/// How many of the optional or measured fields a record actually fills in
+fn completeness(star: &Star) -> usize {
+    let measured = [star.radius_solar, star.mass_solar, star.luminosity_solar, star.temperature_k];
+    measured.iter().filter(|v| **v > 0.0).count()
+        + usize::from(!star.spectral_type.trim().is_empty())
+        + usize::from(!star.name.trim().is_empty())
+        + usize::from(star.system_name.is_some())
+        + usize::from(star.catalog_id.as_ref().is_some_and(|c| !c.trim().is_empty()))
+        + usize::from(star.hip.is_some())
+        + usize::from(star.hd.is_some())
+        + usize::from(star.parallax_mas.is_some())
+        + usize::from(star.apparent_magnitude.is_some())
+        + usize::from(star.velocity_kms.is_some())
+}
+
+/// Fill the gaps in `kept` from `other`, which describes the same star
+fn absorb(kept: &mut Star, other: Star) {
+    if kept.name.trim().is_empty() {
+        kept.name = other.name;
+    }
+    if kept.spectral_type.trim().is_empty() {
+        kept.spectral_type = other.spectral_type;
+    }
+    for (value, fallback) in [
+        (&mut kept.radius_solar, other.radius_solar),
+        (&mut kept.mass_solar, other.mass_solar),
+        (&mut kept.luminosity_solar, other.luminosity_solar),
+        (&mut kept.temperature_k, other.temperature_k),
+    ] {
+        if *value <= 0.0 {
+            *value = fallback;
+        }
+    }
+    if kept.catalog_id.as_ref().is_none_or(|c| c.trim().is_empty()) {
+        kept.catalog_id = other.catalog_id;
+    }
+    kept.system_name = kept.system_name.take().or(other.system_name);
+    kept.hip = kept.hip.or(other.hip);
+    kept.hd = kept.hd.or(other.hd);
+    kept.parallax_mas = kept.parallax_mas.or(other.parallax_mas);
+    kept.apparent_magnitude = kept.apparent_magnitude.or(other.apparent_magnitude);
+    kept.velocity_kms = kept.velocity_kms.or(other.velocity_kms);
+}
+
+/// Merge stars that share a catalog designation, HIP number or HD number
+/// The most complete record of each group is kept, with its missing fields filled in from
+/// the others. Stars without any catalog identifier are never merged. Order follows the first
+/// appearance of each star.
+pub fn merge_duplicate_stars(stars: Vec<Star>) -> Vec<Star> {
+    let mut merged: Vec<Star> = Vec::with_capacity(stars.len());
+    let mut index_by_key: HashMap<CatalogKey, usize> = HashMap::new();
+
+    for star in stars {
+        let keys = catalog_keys(&star);
+        let index = match keys.iter().find_map(|key| index_by_key.get(key).copied()) {
+            Some(index) => {
+                let existing = &mut merged[index];
+                if completeness(&star) > completeness(existing) {
+                    let previous = std::mem::replace(existing, star);
+                    absorb(existing, previous);
+                } else {
+                    absorb(existing, star);
+                }
+                index
+            }
+            None => {
+                merged.push(star);
+                merged.len() - 1
+            }
+        };
+
+        for key in catalog_keys(&merged[index]).into_iter().chain(keys) {
+            index_by_key.entry(key).or_insert(index);
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(id: i32, name: &str, hd: Option<u32>) -> Star {
+        Star {
+            id,
+            name: name.to_string(),
+            spectral_type: "A1V".to_string(),
+            radius_solar: 1.7,
+            mass_solar: 2.1,
+            luminosity_solar: 25.0,
+            temperature_k: 9940.0,
+            x: -1.6,
+            y: 8.1,
+            z: -2.5,
+            system_name: None,
+            system_x: -1.6,
+            system_y: 8.1,
+            system_z: -2.5,
+            catalog_id: None,
+            hip: None,
+            hd,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        }
+    }
+
+    #[test]
+    fn test_shared_hd_number_collapses() {
+        let mut sirius = star(1, "Sirius", Some(48915));
+        sirius.hip = Some(32349);
+        let mut alpha_cma = star(2, "Alpha Canis Majoris", Some(48915));
+        alpha_cma.temperature_k = 0.0;
+        alpha_cma.apparent_magnitude = Some(-1.46);
+
+        let stars = vec![
+            sirius,
+            star(3, "Vega", Some(172167)),
+            alpha_cma,
+            star(4, "Rogue", None),
+            star(5, "Rogue", None), // No catalog number, so never merged
+        ];
+        let merged = merge_duplicate_stars(stars);
+
+        let names: Vec<&str> = merged.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Sirius", "Vega", "Rogue", "Rogue"]);
+
+        // The fuller Sirius record wins and picks up the magnitude it lacked
+        let sirius = &merged[0];
+        assert_eq!((sirius.id, sirius.hip, sirius.hd), (1, Some(32349), Some(48915)));
+        assert_eq!(sirius.temperature_k, 9940.0);
+        assert_eq!(sirius.apparent_magnitude, Some(-1.46));
+    }
+}
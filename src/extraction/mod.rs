@@ -1,7 +1,19 @@
 pub mod reader;
 pub mod csv_export;
+pub mod bright_star;
 pub mod multistar_analysis;
+pub mod dedup;
+pub mod proper_motion;
+pub mod space_engine;
+#[cfg(feature = "parquet")]
+pub mod parquet_export;
 
-pub use reader::{Star, StarReader};
-pub use csv_export::export_stars_to_csv;
+pub use reader::{apply_observables, nearest_star_to, sol_position, ColumnMapping, Star, StarReader};
+pub use csv_export::{export_stars_to_csv, export_stars_to_csv_with, CsvOptions};
+pub use bright_star::{export_bright_star_catalog, export_bright_star_catalog_from};
 pub use multistar_analysis::analyze_multistar_systems;
+pub use dedup::merge_duplicate_stars;
+pub use proper_motion::project_positions;
+pub use space_engine::export_for_space_engine;
+#[cfg(feature = "parquet")]
+pub use parquet_export::{export_stars_to_parquet, read_stars_from_parquet};
@@ -56,7 +56,7 @@ pub fn analyze_multistar_systems(db_path: &str) -> SqliteResult<()> {
         })?;
 
         for comp in components {
-            let (comp_name, spectral, radius, mass, lum, temp) = comp?;
+            let (comp_name, spectral, radius, mass, lum, _temp) = comp?;
             println!("    ├─ {}: {} (R:{}, M:{}, L:{})",
                      comp_name, spectral, radius, mass, lum);
         }
@@ -0,0 +1,224 @@
+use std::fs::File;
+use std::sync::Arc;
+use anyhow::{anyhow, Result};
+use arrow_array::{Array, ArrayRef, Float64Array, Int32Array, RecordBatch, StringArray, UInt32Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+
+use super::Star;
+
+/// Arrow schema of the exported file, one column per star field with velocity split into three
+fn star_schema() -> Schema {
+    let required = |name: &str, data_type: DataType| Field::new(name, data_type, false);
+    let optional = |name: &str, data_type: DataType| Field::new(name, data_type, true);
+
+    Schema::new(vec![
+        required("id", DataType::Int32),
+        required("name", DataType::Utf8),
+        required("spectral_type", DataType::Utf8),
+        required("radius_solar", DataType::Float64),
+        required("mass_solar", DataType::Float64),
+        required("luminosity_solar", DataType::Float64),
+        required("temperature_k", DataType::Float64),
+        required("x", DataType::Float64),
+        required("y", DataType::Float64),
+        required("z", DataType::Float64),
+        optional("system_name", DataType::Utf8),
+        required("system_x", DataType::Float64),
+        required("system_y", DataType::Float64),
+        required("system_z", DataType::Float64),
+        optional("catalog_id", DataType::Utf8),
+        optional("hip", DataType::UInt32),
+        optional("hd", DataType::UInt32),
+        optional("parallax_mas", DataType::Float64),
+        optional("apparent_magnitude", DataType::Float64),
+        optional("velocity_x_kms", DataType::Float64),
+        optional("velocity_y_kms", DataType::Float64),
+        optional("velocity_z_kms", DataType::Float64),
+    ])
+}
+
+/// Export stars to a Parquet file for pandas/Polars workflows
+/// All stars go into one record batch written with the parquet crate's arrow writer.
+pub fn export_stars_to_parquet(stars: &[Star], output_path: &str) -> Result<()> {
+    let float = |field: fn(&Star) -> f64| -> ArrayRef {
+        Arc::new(Float64Array::from_iter_values(stars.iter().map(field)))
+    };
+    let optional_float = |field: fn(&Star) -> Option<f64>| -> ArrayRef {
+        Arc::new(Float64Array::from(stars.iter().map(field).collect::<Vec<_>>()))
+    };
+    let optional_text = |field: fn(&Star) -> Option<&str>| -> ArrayRef {
+        Arc::new(StringArray::from(stars.iter().map(field).collect::<Vec<_>>()))
+    };
+    let optional_number = |field: fn(&Star) -> Option<u32>| -> ArrayRef {
+        Arc::new(UInt32Array::from(stars.iter().map(field).collect::<Vec<_>>()))
+    };
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int32Array::from_iter_values(stars.iter().map(|s| s.id))),
+        Arc::new(StringArray::from_iter_values(stars.iter().map(|s| s.name.as_str()))),
+        Arc::new(StringArray::from_iter_values(stars.iter().map(|s| s.spectral_type.as_str()))),
+        float(|s| s.radius_solar),
+        float(|s| s.mass_solar),
+        float(|s| s.luminosity_solar),
+        float(|s| s.temperature_k),
+        float(|s| s.x),
+        float(|s| s.y),
+        float(|s| s.z),
+        optional_text(|s| s.system_name.as_deref()),
+        float(|s| s.system_x),
+        float(|s| s.system_y),
+        float(|s| s.system_z),
+        optional_text(|s| s.catalog_id.as_deref()),
+        optional_number(|s| s.hip),
+        optional_number(|s| s.hd),
+        optional_float(|s| s.parallax_mas),
+        optional_float(|s| s.apparent_magnitude),
+        optional_float(|s| s.velocity_kms.map(|v| v.0)),
+        optional_float(|s| s.velocity_kms.map(|v| v.1)),
+        optional_float(|s| s.velocity_kms.map(|v| v.2)),
+    ];
+
+    let schema = Arc::new(star_schema());
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+    let mut writer = ArrowWriter::try_new(File::create(output_path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Read stars back from a Parquet file written by `export_stars_to_parquet`
+pub fn read_stars_from_parquet(path: &str) -> Result<Vec<Star>> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?;
+
+    let mut stars = Vec::new();
+    for batch in reader {
+        let batch = batch?;
+        let ids = column::<Int32Array>(&batch, "id")?;
+        let names = column::<StringArray>(&batch, "name")?;
+        let spectral_types = column::<StringArray>(&batch, "spectral_type")?;
+        let system_names = column::<StringArray>(&batch, "system_name")?;
+        let catalog_ids = column::<StringArray>(&batch, "catalog_id")?;
+        let hips = column::<UInt32Array>(&batch, "hip")?;
+        let hds = column::<UInt32Array>(&batch, "hd")?;
+        let float = |name: &str| column::<Float64Array>(&batch, name);
+        let (radius, mass, luminosity, temperature) =
+            (float("radius_solar")?, float("mass_solar")?, float("luminosity_solar")?, float("temperature_k")?);
+        let (x, y, z) = (float("x")?, float("y")?, float("z")?);
+        let (system_x, system_y, system_z) = (float("system_x")?, float("system_y")?, float("system_z")?);
+        let (parallax, magnitude) = (float("parallax_mas")?, float("apparent_magnitude")?);
+        let (vx, vy, vz) = (float("velocity_x_kms")?, float("velocity_y_kms")?, float("velocity_z_kms")?);
+
+        for row in 0..batch.num_rows() {
+            stars.push(Star {
+                id: ids.value(row),
+                name: names.value(row).to_string(),
+                spectral_type: spectral_types.value(row).to_string(),
+                radius_solar: radius.value(row),
+                mass_solar: mass.value(row),
+                luminosity_solar: luminosity.value(row),
+                temperature_k: temperature.value(row),
+                x: x.value(row),
+                y: y.value(row),
+                z: z.value(row),
+                system_name: system_names.is_valid(row).then(|| system_names.value(row).to_string()),
+                system_x: system_x.value(row),
+                system_y: system_y.value(row),
+                system_z: system_z.value(row),
+                catalog_id: catalog_ids.is_valid(row).then(|| catalog_ids.value(row).to_string()),
+                hip: hips.is_valid(row).then(|| hips.value(row)),
+                hd: hds.is_valid(row).then(|| hds.value(row)),
+                parallax_mas: parallax.is_valid(row).then(|| parallax.value(row)),
+                apparent_magnitude: magnitude.is_valid(row).then(|| magnitude.value(row)),
+                velocity_kms: vx.is_valid(row).then(|| (vx.value(row), vy.value(row), vz.value(row))),
+            });
+        }
+    }
+
+    Ok(stars)
+}
+
+/// Column `name` of the batch as a concrete arrow array type
+fn column<'a, T: Array + 'static>(batch: &'a RecordBatch, name: &str) -> Result<&'a T> {
+    batch
+        .column_by_name(name)
+        .and_then(|array| array.as_any().downcast_ref::<T>())
+        .ok_or_else(|| anyhow!("Parquet file has no {} column of the expected type", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(id: i32, name: &str, hip: Option<u32>) -> Star {
+        Star {
+            id,
+            name: name.to_string(),
+            spectral_type: "G2V".to_string(),
+            radius_solar: 1.0,
+            mass_solar: 1.0,
+            luminosity_solar: 1.0,
+            temperature_k: 5778.0,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            system_name: None,
+            system_x: 0.0,
+            system_y: 0.0,
+            system_z: 0.0,
+            catalog_id: None,
+            hip,
+            hd: None,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        }
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let sirius = Star {
+            id: 1,
+            name: "Sirius A".to_string(),
+            spectral_type: "A1V".to_string(),
+            radius_solar: 1.71,
+            mass_solar: 2.06,
+            luminosity_solar: 25.4,
+            temperature_k: 9940.0,
+            x: -1.6,
+            y: 8.1,
+            z: -2.5,
+            system_name: Some("Sirius".to_string()),
+            system_x: -1.6,
+            system_y: 8.1,
+            system_z: -2.5,
+            catalog_id: Some("Gliese 244".to_string()),
+            hip: Some(32349),
+            hd: Some(48915),
+            parallax_mas: Some(379.21),
+            apparent_magnitude: Some(-1.46),
+            velocity_kms: Some((-5.5, -7.6, -13.1)),
+        };
+        let stars = vec![sirius, star(2, "Sol", None)];
+        let many: Vec<Star> = (0..20)
+            .map(|i| star(10 + i, &format!("Star {}", i), (i % 3 == 0).then_some(i as u32)))
+            .collect();
+
+        let path = std::env::temp_dir().join("solarviewer_parquet_round_trip.parquet");
+        let path = path.to_str().unwrap();
+        for set in [&stars, &many, &Vec::new()] {
+            export_stars_to_parquet(set, path).unwrap();
+            let back = read_stars_from_parquet(path).unwrap();
+
+            assert_eq!(back.len(), set.len());
+            for (a, b) in set.iter().zip(&back) {
+                assert_eq!(format!("{:?}", a), format!("{:?}", b));
+            }
+        }
+        std::fs::remove_file(path).ok();
+
+        assert!(read_stars_from_parquet("/nonexistent/stars.parquet").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+/// Linear proper-motion projection of star positions
+use super::Star;
+
+/// Light-years travelled in one year at 1 km/s
+pub const LY_PER_YEAR_PER_KMS: f64 = 3.335_641e-6;
+
+/// Snapshot of `stars` as they will be `years` from now, moving in straight lines
+/// Stars without a velocity stay where they are. System positions move with their stars so
+/// multi-star components stay grouped with their container.
+pub fn project_positions(stars: &[Star], years: f64) -> Vec<Star> {
+    stars
+        .iter()
+        .map(|star| {
+            let mut projected = star.clone();
+            if let Some((vx, vy, vz)) = star.velocity_kms {
+                let scale = years * LY_PER_YEAR_PER_KMS;
+                let (dx, dy, dz) = (vx * scale, vy * scale, vz * scale);
+                projected.x += dx;
+                projected.y += dy;
+                projected.z += dz;
+                projected.system_x += dx;
+                projected.system_y += dy;
+                projected.system_z += dz;
+            }
+            projected
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(name: &str, velocity_kms: Option<(f64, f64, f64)>) -> Star {
+        Star {
+            name: name.to_string(),
+            spectral_type: "M4V".to_string(),
+            radius_solar: 0.2,
+            mass_solar: 0.14,
+            luminosity_solar: 0.0035,
+            temperature_k: 3134.0,
+            x: 5.0,
+            y: 1.0,
+            z: -2.0,
+            system_x: 5.0,
+            system_y: 1.0,
+            system_z: -2.0,
+            velocity_kms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_projection_moves_only_stars_with_velocity() {
+        // A thousandth of light speed covers one light-year in a thousand years
+        let c_kms = 299_792.458;
+        let stars = vec![
+            star("Runner", Some((c_kms / 1000.0, 0.0, -c_kms / 2000.0))),
+            star("Still", None),
+        ];
+        let future = project_positions(&stars, 1000.0);
+
+        let runner = &future[0];
+        assert!((runner.x - 6.0).abs() < 1e-4, "x moved to {}", runner.x);
+        assert!((runner.y - 1.0).abs() < 1e-12);
+        assert!((runner.z + 2.5).abs() < 1e-4, "z moved to {}", runner.z);
+        assert_eq!((runner.system_x, runner.system_z), (runner.x, runner.z));
+
+        let still = &future[1];
+        assert_eq!((still.x, still.y, still.z), (5.0, 1.0, -2.0));
+    }
+}
@@ -1,8 +1,10 @@
-use rusqlite::{Connection, Result as SqliteResult};
+use rusqlite::{types::Value, Connection, Result as SqliteResult};
 use serde::Serialize;
 
+use crate::stellar_forge::coordinates::{CoordinateTransform, EquatorialCoordinates};
+
 /// Represents a star system extracted from Astrosynthesis
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Star {
     pub id: i32,
     pub name: String,
@@ -18,18 +20,278 @@ pub struct Star {
     pub system_x: f64,           // System container position (same for all components)
     pub system_y: f64,
     pub system_z: f64,
+    pub catalog_id: Option<String>,   // Catalog designation (e.g., "Gliese 581"), if the file has one
+    pub hip: Option<u32>,             // Hipparcos catalog number
+    pub hd: Option<u32>,              // Henry Draper catalog number
+    pub parallax_mas: Option<f64>,        // Parallax as seen from Sol, in milliarcseconds
+    pub apparent_magnitude: Option<f64>,  // Apparent magnitude as seen from Sol
+    pub velocity_kms: Option<(f64, f64, f64)>,  // Space velocity along x, y, z in km/s, if the file has one
+}
+
+/// Light-years per parsec
+pub(crate) const LY_PER_PARSEC: f64 = 3.261_563_777;
+/// Absolute bolometric magnitude of the Sun
+const SUN_ABSOLUTE_MAGNITUDE: f64 = 4.83;
+
+impl Star {
+    /// Label to show for this star: its name, or the best catalog designation when the name is blank
+    pub fn display_name(&self) -> String {
+        if !self.name.trim().is_empty() {
+            return self.name.clone();
+        }
+        if let Some(catalog_id) = self.catalog_id.as_ref().filter(|c| !c.trim().is_empty()) {
+            return catalog_id.clone();
+        }
+        if let Some(hip) = self.hip {
+            return format!("HIP {}", hip);
+        }
+        if let Some(hd) = self.hd {
+            return format!("HD {}", hd);
+        }
+        String::new()
+    }
+
+    /// Straight-line distance to another star in light-years
+    pub fn distance_to(&self, other: &Star) -> f64 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        let dz = self.z - other.z;
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// Fill in parallax and apparent magnitude as observed from `sol` (light-year coordinates)
+    /// Both stay None for a star at Sol's position; magnitude also needs a positive luminosity.
+    pub fn compute_observables(&mut self, sol: (f64, f64, f64)) {
+        let distance_ly = ((self.x - sol.0).powi(2) + (self.y - sol.1).powi(2) + (self.z - sol.2).powi(2)).sqrt();
+        let distance_pc = distance_ly / LY_PER_PARSEC;
+        if distance_pc <= 0.0 {
+            self.parallax_mas = None;
+            self.apparent_magnitude = None;
+            return;
+        }
+
+        self.parallax_mas = Some(1000.0 / distance_pc);
+
+        // Distance modulus: m = M + 5 log10(d / 10 pc)
+        self.apparent_magnitude = (self.luminosity_solar > 0.0).then(|| {
+            let absolute = SUN_ABSOLUTE_MAGNITUDE - 2.5 * self.luminosity_solar.log10();
+            absolute + 5.0 * (distance_pc / 10.0).log10()
+        });
+    }
+
+    /// J2000 RA/Dec of the star as seen from `vantage`, a position in light-year coordinates
+    /// such as `sol_position`
+    pub fn equatorial_coordinates_from(&self, vantage: (f64, f64, f64)) -> EquatorialCoordinates {
+        CoordinateTransform::galactic_to_equatorial(CoordinateTransform::astrosynthesis_to_galactic(
+            self.x - vantage.0,
+            self.y - vantage.1,
+            self.z - vantage.2,
+        ))
+    }
+}
+
+/// Candidate column names for catalog identifiers, checked in order
+const CATALOG_ID_COLUMNS: &[&str] = &["catalog_id", "catalog", "designation", "catalog_name"];
+const HIP_COLUMNS: &[&str] = &["hip", "hip_id", "hipparcos", "hip_number"];
+const HD_COLUMNS: &[&str] = &["hd", "hd_id", "hd_number"];
+
+const VX_COLUMNS: &[&str] = &["vx", "vel_x", "velocity_x", "vx_kms"];
+const VY_COLUMNS: &[&str] = &["vy", "vel_y", "velocity_y", "vy_kms"];
+const VZ_COLUMNS: &[&str] = &["vz", "vel_z", "velocity_z", "vz_kms"];
+
+/// Catalog and velocity columns found in a particular file's `bodies` table
+#[derive(Debug, Clone, Default)]
+struct CatalogColumns {
+    catalog_id: Option<String>,
+    hip: Option<String>,
+    hd: Option<String>,
+    vx: Option<String>,
+    vy: Option<String>,
+    vz: Option<String>,
+}
+
+impl CatalogColumns {
+    fn detect(conn: &Connection) -> SqliteResult<Self> {
+        let columns = body_columns(conn)?;
+        Ok(Self {
+            catalog_id: find_column(&columns, CATALOG_ID_COLUMNS),
+            hip: find_column(&columns, HIP_COLUMNS),
+            hd: find_column(&columns, HD_COLUMNS),
+            vx: find_column(&columns, VX_COLUMNS),
+            vy: find_column(&columns, VY_COLUMNS),
+            vz: find_column(&columns, VZ_COLUMNS),
+        })
+    }
+
+    /// SELECT expressions for catalog_id, hip, hd, vx, vy, vz on the given table alias,
+    /// NULL where missing
+    fn select_list(&self, alias: &str) -> String {
+        [&self.catalog_id, &self.hip, &self.hd, &self.vx, &self.vy, &self.vz]
+            .iter()
+            .map(|candidate| match candidate {
+                Some(name) => column(alias, name),
+                None => "NULL".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Read a catalog designation stored as either text or a number
+fn catalog_text(value: Value) -> Option<String> {
+    match value {
+        Value::Text(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Value::Integer(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Read a catalog number stored as an integer or as text like "HIP 70890"
+fn catalog_number(value: Value) -> Option<u32> {
+    match value {
+        Value::Integer(n) => u32::try_from(n).ok().filter(|&n| n > 0),
+        Value::Real(n) if n > 0.0 => Some(n as u32),
+        Value::Text(text) => text
+            .trim_start_matches(|c: char| c.is_ascii_alphabetic() || c.is_whitespace())
+            .trim()
+            .parse()
+            .ok()
+            .filter(|&n| n > 0),
+        _ => None,
+    }
+}
+
+/// Velocity from its three components, only when all of them are numbers
+fn velocity(vx: Value, vy: Value, vz: Value) -> Option<(f64, f64, f64)> {
+    let real = |value: Value| match value {
+        Value::Real(v) => Some(v),
+        Value::Integer(v) => Some(v as f64),
+        _ => None,
+    };
+    Some((real(vx)?, real(vy)?, real(vz)?))
+}
+
+/// Which `bodies` columns hold each star attribute
+/// Astrosynthesis files normally use `name`, `spectral`, `luminosity`, `x`, `y`, `z`, but some
+/// exports rename or swap them. Swapping axes is just a matter of pointing `x` at another column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMapping {
+    pub name: String,
+    pub spectral: String,
+    pub luminosity: String,
+    pub x: String,
+    pub y: String,
+    pub z: String,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            name: "name".to_string(),
+            spectral: "spectral".to_string(),
+            luminosity: "luminosity".to_string(),
+            x: "x".to_string(),
+            y: "y".to_string(),
+            z: "z".to_string(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    /// Pick columns by common names, falling back to the standard Astrosynthesis names
+    pub fn detect(conn: &Connection) -> SqliteResult<Self> {
+        let columns = body_columns(conn)?;
+        let defaults = Self::default();
+        let find = |candidates: &[&str], default: String| {
+            find_column(&columns, candidates).unwrap_or(default)
+        };
+
+        Ok(Self {
+            name: find(&["name", "star_name", "body_name"], defaults.name),
+            spectral: find(&["spectral", "spectral_type", "spectral_class", "spec"], defaults.spectral),
+            luminosity: find(&["luminosity", "lum", "luminosity_solar"], defaults.luminosity),
+            x: find(&["x", "pos_x", "x_ly"], defaults.x),
+            y: find(&["y", "pos_y", "y_ly"], defaults.y),
+            z: find(&["z", "pos_z", "z_ly"], defaults.z),
+        })
+    }
+}
+
+/// Compute parallax and apparent magnitude for every star, observed from Sol
+/// Sol is the star named "Sol" or "Sun"; datasets without one are observed from the origin.
+pub fn apply_observables(stars: &mut [Star]) {
+    let sol = sol_position(stars);
+    for star in stars.iter_mut() {
+        star.compute_observables(sol);
+    }
+}
+
+/// Position of the star named Sol (or Sun), or the origin when there is none
+pub fn sol_position(stars: &[Star]) -> (f64, f64, f64) {
+    stars
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case("sol") || s.name.eq_ignore_ascii_case("sun"))
+        .map(|s| (s.x, s.y, s.z))
+        .unwrap_or((0.0, 0.0, 0.0))
+}
+
+/// Star closest to an arbitrary point in light-year coordinates, or None for an empty slice
+pub fn nearest_star_to(stars: &[Star], point: (f64, f64, f64)) -> Option<&Star> {
+    let distance_sq = |s: &Star| (s.x - point.0).powi(2) + (s.y - point.1).powi(2) + (s.z - point.2).powi(2);
+    stars.iter().min_by(|a, b| distance_sq(a).total_cmp(&distance_sq(b)))
+}
+
+/// Column names of the `bodies` table
+fn body_columns(conn: &Connection) -> SqliteResult<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA table_info(bodies)")?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<SqliteResult<Vec<String>>>()?;
+    Ok(columns)
+}
+
+/// First candidate present in `columns`, compared case-insensitively
+fn find_column(columns: &[String], candidates: &[&str]) -> Option<String> {
+    candidates.iter().find_map(|candidate| {
+        columns.iter().find(|c| c.eq_ignore_ascii_case(candidate)).cloned()
+    })
+}
+
+/// Quoted column reference on a table alias
+fn column(alias: &str, name: &str) -> String {
+    format!("{}.\"{}\"", alias, name.replace('"', "\"\""))
 }
 
 /// Reader for extracting stars from Astrosynthesis .AstroDB files
 pub struct StarReader {
     conn: Connection,
+    mapping: ColumnMapping,
+    catalog_columns: CatalogColumns,
 }
 
 impl StarReader {
     /// Create a new StarReader from an .AstroDB file path
+    /// Column names are auto-detected; see `new_with_mapping` to set them explicitly
     pub fn new(db_path: &str) -> SqliteResult<Self> {
         let conn = Connection::open(db_path)?;
-        Ok(StarReader { conn })
+        let mapping = ColumnMapping::detect(&conn)?;
+        Self::from_connection(conn, mapping)
+    }
+
+    /// Create a StarReader that reads star attributes from the given columns
+    pub fn new_with_mapping(db_path: &str, mapping: ColumnMapping) -> SqliteResult<Self> {
+        let conn = Connection::open(db_path)?;
+        Self::from_connection(conn, mapping)
+    }
+
+    fn from_connection(conn: Connection, mapping: ColumnMapping) -> SqliteResult<Self> {
+        let catalog_columns = CatalogColumns::detect(&conn)?;
+        Ok(StarReader { conn, mapping, catalog_columns })
+    }
+
+    /// Column mapping in use
+    pub fn mapping(&self) -> &ColumnMapping {
+        &self.mapping
     }
 
     /// Extract all stars from the database
@@ -37,19 +299,27 @@ impl StarReader {
     /// For multi-star systems, includes the container name and position
     pub fn read_all_stars(&self) -> SqliteResult<Vec<Star>> {
         let mut result = Vec::new();
+        let m = &self.mapping;
 
         // Get single-star systems (system_id = id with spectral type)
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, spectral, radius, mass, luminosity, temp, x, y, z
-             FROM bodies
-             WHERE system_id = id AND parent_id = 0 AND spectral != '' AND spectral IS NOT NULL
-             ORDER BY name"
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT b.id, {name}, {spectral}, b.radius, b.mass, {lum}, b.temp, {x}, {y}, {z}, {catalog}
+             FROM bodies b
+             WHERE b.system_id = b.id AND b.parent_id = 0 AND {spectral} != '' AND {spectral} IS NOT NULL
+             ORDER BY {name}",
+            name = column("b", &m.name),
+            spectral = column("b", &m.spectral),
+            lum = column("b", &m.luminosity),
+            x = column("b", &m.x),
+            y = column("b", &m.y),
+            z = column("b", &m.z),
+            catalog = self.catalog_columns.select_list("b"),
+        ))?;
 
         let stars = stmt.query_map([], |row| {
             Ok(Star {
                 id: row.get(0)?,
-                name: row.get(1)?,
+                name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
                 spectral_type: row.get::<_, String>(2).unwrap_or_default(),
                 radius_solar: row.get(3)?,
                 mass_solar: row.get(4)?,
@@ -62,6 +332,12 @@ impl StarReader {
                 system_x: row.get(7)?,
                 system_y: row.get(8)?,
                 system_z: row.get(9)?,
+                catalog_id: catalog_text(row.get(10)?),
+                hip: catalog_number(row.get(11)?),
+                hd: catalog_number(row.get(12)?),
+                parallax_mas: None,
+                apparent_magnitude: None,
+                velocity_kms: velocity(row.get(13)?, row.get(14)?, row.get(15)?),
             })
         })?;
 
@@ -72,22 +348,34 @@ impl StarReader {
         // Get component stars from multi-star containers
         // A multi-star container has system_id = id, parent_id = 0, no spectral type,
         // and has child stars (parent_id = container_id, spectral type set)
-        let mut multi_stmt = self.conn.prepare(
-            "SELECT b.id, b.name, b.spectral, b.radius, b.mass, b.luminosity, b.temp,
-                    b.x, b.y, b.z, c.name, c.x, c.y, c.z
+        let mut multi_stmt = self.conn.prepare(&format!(
+            "SELECT b.id, {name}, {spectral}, b.radius, b.mass, {lum}, b.temp,
+                    {x}, {y}, {z}, {c_name}, {c_x}, {c_y}, {c_z}, {catalog}
              FROM bodies b
              JOIN bodies c ON b.parent_id = c.id
              WHERE c.system_id = c.id AND c.parent_id = 0
-             AND (c.spectral = '' OR c.spectral IS NULL)
-             AND b.spectral != '' AND b.spectral IS NOT NULL
+             AND ({c_spectral} = '' OR {c_spectral} IS NULL)
+             AND {spectral} != '' AND {spectral} IS NOT NULL
              AND b.parent_id = c.id
-             ORDER BY c.name, b.name"
-        )?;
+             ORDER BY {c_name}, {name}",
+            name = column("b", &m.name),
+            spectral = column("b", &m.spectral),
+            lum = column("b", &m.luminosity),
+            x = column("b", &m.x),
+            y = column("b", &m.y),
+            z = column("b", &m.z),
+            c_name = column("c", &m.name),
+            c_spectral = column("c", &m.spectral),
+            c_x = column("c", &m.x),
+            c_y = column("c", &m.y),
+            c_z = column("c", &m.z),
+            catalog = self.catalog_columns.select_list("b"),
+        ))?;
 
         let multi_stars = multi_stmt.query_map([], |row| {
             Ok(Star {
                 id: row.get(0)?,
-                name: row.get(1)?,
+                name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
                 spectral_type: row.get::<_, String>(2).unwrap_or_default(),
                 radius_solar: row.get(3)?,
                 mass_solar: row.get(4)?,
@@ -96,10 +384,16 @@ impl StarReader {
                 x: row.get(7)?,
                 y: row.get(8)?,
                 z: row.get(9)?,
-                system_name: Some(row.get::<_, String>(10)?),
+                system_name: Some(row.get::<_, Option<String>>(10)?.unwrap_or_default()),
                 system_x: row.get(11)?,
                 system_y: row.get(12)?,
                 system_z: row.get(13)?,
+                catalog_id: catalog_text(row.get(14)?),
+                hip: catalog_number(row.get(15)?),
+                hd: catalog_number(row.get(16)?),
+                parallax_mas: None,
+                apparent_magnitude: None,
+                velocity_kms: velocity(row.get(17)?, row.get(18)?, row.get(19)?),
             })
         })?;
 
@@ -110,29 +404,250 @@ impl StarReader {
         // Sort by name
         result.sort_by(|a, b| a.name.cmp(&b.name));
 
+        apply_observables(&mut result);
         Ok(result)
     }
 
+    /// Star closest to the galactic point (x, y, z) in light-years, None when the file has no stars
+    pub fn nearest_star(&self, x: f64, y: f64, z: f64) -> SqliteResult<Option<Star>> {
+        let stars = self.read_all_stars()?;
+        Ok(nearest_star_to(&stars, (x, y, z)).cloned())
+    }
+
+    /// Extract all stars, keeping only those matching `predicate`
+    /// Observables are computed before filtering, so Sol need not pass the predicate.
+    pub fn read_stars_filtered<F: Fn(&Star) -> bool>(&self, predicate: F) -> SqliteResult<Vec<Star>> {
+        let mut stars = self.read_all_stars()?;
+        stars.retain(|star| predicate(star));
+        Ok(stars)
+    }
+
     /// Count total number of stars (includes both single-star systems and multi-star components)
     pub fn count_stars(&self) -> SqliteResult<i64> {
+        let spectral = column("b", &self.mapping.spectral);
+        let c_spectral = column("c", &self.mapping.spectral);
+
         // Count single-star systems
-        let mut stmt = self.conn.prepare(
-            "SELECT COUNT(*) FROM bodies
-             WHERE system_id = id AND parent_id = 0 AND spectral != '' AND spectral IS NOT NULL"
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT COUNT(*) FROM bodies b
+             WHERE b.system_id = b.id AND b.parent_id = 0 AND {spectral} != '' AND {spectral} IS NOT NULL"
+        ))?;
         let single_stars: i64 = stmt.query_row([], |row| row.get(0))?;
 
         // Count component stars in multi-star systems
-        let mut multi_stmt = self.conn.prepare(
+        let mut multi_stmt = self.conn.prepare(&format!(
             "SELECT COUNT(DISTINCT b.id) FROM bodies b
              JOIN bodies c ON b.parent_id = c.id
              WHERE c.system_id = c.id AND c.parent_id = 0
-             AND (c.spectral = '' OR c.spectral IS NULL)
-             AND b.spectral != '' AND b.spectral IS NOT NULL
+             AND ({c_spectral} = '' OR {c_spectral} IS NULL)
+             AND {spectral} != '' AND {spectral} IS NOT NULL
              AND b.parent_id = c.id"
-        )?;
+        ))?;
         let multi_stars: i64 = multi_stmt.query_row([], |row| row.get(0))?;
 
         Ok(single_stars + multi_stars)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal Astrosynthesis-style `bodies` table with catalog columns
+    fn catalog_fixture(path: &std::path::Path) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE bodies (
+                id INTEGER PRIMARY KEY, system_id INTEGER, parent_id INTEGER,
+                name TEXT, spectral TEXT, radius REAL, mass REAL, luminosity REAL, temp REAL,
+                x REAL, y REAL, z REAL,
+                Designation TEXT, HIP INTEGER, HD TEXT
+            );
+            INSERT INTO bodies VALUES (1, 1, 0, 'Sol', 'G2V', 1.0, 1.0, 1.0, 5778, 0, 0, 0, NULL, NULL, NULL);
+            INSERT INTO bodies VALUES (2, 2, 0, '', 'M4V', 0.15, 0.12, 0.0017, 3042, 4.2, 0, 0, 'Gliese 551', 70890, 'HD 0');
+            INSERT INTO bodies VALUES (3, 3, 0, 'Sirius', 'A1V', 1.7, 2.0, 25.4, 9940, 8.6, 0, 0, '', 32349, '48915');",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_reads_catalog_columns() {
+        let path = std::env::temp_dir().join("solarviewer_catalog_fixture.AstroDB");
+        std::fs::remove_file(&path).ok();
+        catalog_fixture(&path);
+
+        let reader = StarReader::new(path.to_str().unwrap()).unwrap();
+        let stars = reader.read_all_stars().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(stars.len(), 3);
+        let proxima = stars.iter().find(|s| s.id == 2).unwrap();
+        assert_eq!(proxima.catalog_id.as_deref(), Some("Gliese 551"));
+        assert_eq!(proxima.hip, Some(70890));
+        assert_eq!(proxima.hd, None);
+        assert_eq!(proxima.display_name(), "Gliese 551");
+
+        let sirius = stars.iter().find(|s| s.name == "Sirius").unwrap();
+        assert_eq!(sirius.catalog_id, None);
+        assert_eq!(sirius.hip, Some(32349));
+        assert_eq!(sirius.hd, Some(48915));
+
+        let sol = stars.iter().find(|s| s.name == "Sol").unwrap();
+        assert_eq!((sol.catalog_id.as_ref(), sol.hip, sol.hd), (None, None, None));
+    }
+
+    #[test]
+    fn test_parallax_and_apparent_magnitude() {
+        let mut star = Star {
+            id: 1,
+            name: "Twin".to_string(),
+            spectral_type: "G2V".to_string(),
+            radius_solar: 1.0,
+            mass_solar: 1.0,
+            luminosity_solar: 1.0,
+            temperature_k: 5778.0,
+            x: 10.0 + 10.0 * LY_PER_PARSEC,
+            y: 5.0,
+            z: 0.0,
+            system_name: None,
+            system_x: 0.0,
+            system_y: 0.0,
+            system_z: 0.0,
+            catalog_id: None,
+            hip: None,
+            hd: None,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        };
+
+        // Ten parsecs from a Sol that is not at the origin
+        star.compute_observables((10.0, 5.0, 0.0));
+        assert!((star.parallax_mas.unwrap() - 100.0).abs() < 1e-9);
+        assert!((star.apparent_magnitude.unwrap() - SUN_ABSOLUTE_MAGNITUDE).abs() < 1e-9);
+
+        // Distance modulus for the same star at 100 pc adds five magnitudes
+        star.x = 10.0 + 100.0 * LY_PER_PARSEC;
+        star.compute_observables((10.0, 5.0, 0.0));
+        assert!((star.parallax_mas.unwrap() - 10.0).abs() < 1e-9);
+        assert!((star.apparent_magnitude.unwrap() - (SUN_ABSOLUTE_MAGNITUDE + 5.0)).abs() < 1e-9);
+
+        star.compute_observables((star.x, star.y, star.z));
+        assert_eq!((star.parallax_mas, star.apparent_magnitude), (None, None));
+    }
+
+    #[test]
+    fn test_equatorial_coordinates_are_seen_from_sol() {
+        let path = std::env::temp_dir().join("solarviewer_equatorial_fixture.AstroDB");
+        std::fs::remove_file(&path).ok();
+        catalog_fixture(&path);
+        let mut stars = StarReader::new(path.to_str().unwrap()).unwrap().read_all_stars().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let sirius_radec = |stars: &[Star]| {
+            let sirius = stars.iter().find(|s| s.name == "Sirius").unwrap();
+            let equatorial = sirius.equatorial_coordinates_from(sol_position(stars));
+            (equatorial.ra_hours(), equatorial.dec_degrees())
+        };
+        let centered = sirius_radec(&stars);
+
+        // Moving the whole dataset away from the origin doesn't change the sky seen from Sol
+        for star in &mut stars {
+            star.x += 100.0;
+            star.y += 50.0;
+            star.z -= 20.0;
+        }
+        let shifted = sirius_radec(&stars);
+        assert!((centered.0 - shifted.0).abs() < 1e-9 && (centered.1 - shifted.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_filter_by_spectral_class() {
+        use crate::visualization::spectral::{parse_spectral_classes, SpectralType};
+
+        let path = std::env::temp_dir().join("solarviewer_spectral_filter.AstroDB");
+        std::fs::remove_file(&path).ok();
+        catalog_fixture(&path);
+
+        let classes = parse_spectral_classes("m").unwrap();
+        assert_eq!(classes, vec![SpectralType::M]);
+
+        let reader = StarReader::new(path.to_str().unwrap()).unwrap();
+        let stars = reader
+            .read_stars_filtered(|star| {
+                star.spectral_type.parse().is_ok_and(|class: SpectralType| classes.contains(&class))
+            })
+            .unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(stars.len(), 1);
+        assert_eq!(stars[0].spectral_type, "M4V");
+        // Distances are still measured from Sol even though it was filtered out
+        assert!((stars[0].parallax_mas.unwrap() - 1000.0 * LY_PER_PARSEC / 4.2).abs() < 1e-6);
+        assert!(parse_spectral_classes("G,X").is_err());
+    }
+
+    #[test]
+    fn test_explicit_column_mapping() {
+        let path = std::env::temp_dir().join("solarviewer_renamed_columns.AstroDB");
+        std::fs::remove_file(&path).ok();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE bodies (
+                    id INTEGER PRIMARY KEY, system_id INTEGER, parent_id INTEGER,
+                    label TEXT, class TEXT, radius REAL, mass REAL, brightness REAL, temp REAL,
+                    gx REAL, gy REAL, gz REAL
+                );
+                INSERT INTO bodies VALUES (1, 1, 0, 'Vega', 'A0V', 2.4, 2.1, 40.1, 9602, 1.0, 2.0, 3.0);
+                INSERT INTO bodies VALUES (2, 2, 0, 'Binary', '', 0, 0, 0, 0, 5.0, 6.0, 7.0);
+                INSERT INTO bodies VALUES (3, 2, 2, 'Binary A', 'K1V', 0.9, 0.9, 0.5, 5200, 5.1, 6.0, 7.0);",
+            )
+            .unwrap();
+        }
+
+        // Swap the vertical axis into y while renaming everything else
+        let mapping = ColumnMapping {
+            name: "label".to_string(),
+            spectral: "class".to_string(),
+            luminosity: "brightness".to_string(),
+            x: "gx".to_string(),
+            y: "gz".to_string(),
+            z: "gy".to_string(),
+        };
+        let reader = StarReader::new_with_mapping(path.to_str().unwrap(), mapping.clone()).unwrap();
+        let stars = reader.read_all_stars().unwrap();
+        let count = reader.count_stars().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(reader.mapping(), &mapping);
+        assert_eq!(count, 2);
+        assert_eq!(stars.len(), 2);
+
+        let vega = stars.iter().find(|s| s.name == "Vega").unwrap();
+        assert_eq!(vega.spectral_type, "A0V");
+        assert_eq!(vega.luminosity_solar, 40.1);
+        assert_eq!((vega.x, vega.y, vega.z), (1.0, 3.0, 2.0));
+
+        let component = stars.iter().find(|s| s.name == "Binary A").unwrap();
+        assert_eq!(component.system_name.as_deref(), Some("Binary"));
+        assert_eq!((component.system_x, component.system_y, component.system_z), (5.0, 7.0, 6.0));
+    }
+
+    #[test]
+    fn test_nearest_star_to_point() {
+        let path = std::env::temp_dir().join("solarviewer_nearest_star.AstroDB");
+        std::fs::remove_file(&path).ok();
+        catalog_fixture(&path);
+
+        let reader = StarReader::new(path.to_str().unwrap()).unwrap();
+        let near_proxima = reader.nearest_star(5.0, 1.0, -1.0).unwrap();
+        let stars = reader.read_all_stars().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(near_proxima.map(|s| s.id), Some(2));
+        assert_eq!(nearest_star_to(&stars, (-3.0, 0.0, 0.0)).unwrap().name, "Sol");
+        assert_eq!(nearest_star_to(&stars, (7.0, 0.0, 0.0)).unwrap().name, "Sirius");
+        assert!(nearest_star_to(&[], (0.0, 0.0, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs::File;
+use std::io::Write;
+use anyhow::Result;
+
+use super::reader::{sol_position, LY_PER_PARSEC};
+use super::Star;
+use crate::stellar_forge::coordinates::CoordinateTransform;
+
+/// Export stars as a SpaceEngine-style CSV star catalog, as seen from Sol
+/// Columns are Name, RA and Dec in J2000 degrees, Dist in parsecs, AppMagn and SpecClass,
+/// the layout 3D sandbox tools read for custom catalogs. Sol itself, which has no
+/// direction from its own position, is left out. Returns the number of stars written.
+pub fn export_for_space_engine(stars: &[Star], output_path: &str) -> Result<usize> {
+    let sol = sol_position(stars);
+    let mut file = File::create(output_path)?;
+    writeln!(file, "Name,RA,Dec,Dist,AppMagn,SpecClass")?;
+
+    let mut written = 0;
+    for star in stars {
+        let (dx, dy, dz) = (star.x - sol.0, star.y - sol.1, star.z - sol.2);
+        let distance_ly = (dx * dx + dy * dy + dz * dz).sqrt();
+        if distance_ly <= 0.0 {
+            continue;
+        }
+
+        let equatorial = CoordinateTransform::galactic_to_equatorial(
+            CoordinateTransform::astrosynthesis_to_galactic(dx, dy, dz),
+        );
+        let mut observed = star.clone();
+        observed.compute_observables(sol);
+        let magnitude = observed.apparent_magnitude.map(|m| format!("{:.3}", m)).unwrap_or_default();
+
+        writeln!(
+            file,
+            "\"{}\",{:.6},{:.6},{:.6},{},\"{}\"",
+            star.display_name().replace("\"", "\"\""),
+            equatorial.ra_hours() * 15.0,
+            equatorial.dec_degrees(),
+            distance_ly / LY_PER_PARSEC,
+            magnitude,
+            star.spectral_type
+        )?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(name: &str, x: f64, y: f64, z: f64) -> Star {
+        Star {
+            name: name.to_string(),
+            spectral_type: "K0III".to_string(),
+            radius_solar: 10.0,
+            mass_solar: 1.5,
+            luminosity_solar: 50.0,
+            temperature_k: 4800.0,
+            x,
+            y,
+            z,
+            system_x: x,
+            system_y: y,
+            system_z: z,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_space_engine_export_uses_equatorial_degrees() {
+        // A star toward the galactic center, with Sol away from the origin
+        let stars = vec![
+            star("Sol", 1.0, 2.0, 3.0),
+            star("Core Watcher", 1.0 + 10.0 * LY_PER_PARSEC, 2.0, 3.0),
+        ];
+
+        let path = std::env::temp_dir().join("solarviewer_space_engine_test.csv");
+        let written = export_for_space_engine(&stars, path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(written, 1);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Name,RA,Dec,Dist,AppMagn,SpecClass");
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields[0], "\"Core Watcher\"");
+
+        // Galactic center (Sgr A*) sits at RA 266.40 deg, Dec -28.94 deg
+        let ra: f64 = fields[1].parse().unwrap();
+        let dec: f64 = fields[2].parse().unwrap();
+        let dist: f64 = fields[3].parse().unwrap();
+        assert!((ra - 266.405).abs() < 0.01, "RA {}", ra);
+        assert!((dec + 28.936).abs() < 0.01, "Dec {}", dec);
+        assert!((dist - 10.0).abs() < 1e-6);
+        assert_eq!(fields[5], "\"K0III\"");
+    }
+}
@@ -2,9 +2,23 @@
 //!
 //! This library provides tools for working with Astrosynthesis stellar cartography data
 //! and the modern StellarForge database system.
+//!
+//! ```
+//! use solarviewer::stellar_forge::builders::{PlanetBuilder, SystemBuilder};
+//! use solarviewer::stellar_forge::containers::Galaxy;
+//!
+//! let system = SystemBuilder::new("Sol")
+//!     .with_star("G2V")
+//!     .with_planet(PlanetBuilder::terrestrial("Earth", 1.0))
+//!     .build();
+//!
+//! let mut galaxy = Galaxy::new("Local");
+//! galaxy.add_star_system(system).unwrap();
+//! assert_eq!(galaxy.star_systems[0].planets[0].name, "Earth");
+//! ```
 
 pub mod schema;
 pub mod extraction;
 pub mod visualization;
-// Temporarily disabled until compilation errors are fixed
-// pub mod stellar_forge;
+pub mod stellar_forge;
+pub mod analysis;
@@ -1,11 +1,11 @@
+use std::collections::HashSet;
+
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use tracing::info;
 
-mod schema;
-mod extraction;
-mod visualization;
-mod stellar_forge;
+use solarviewer::{analysis, extraction, schema, stellar_forge, visualization};
+use solarviewer::stellar_forge::{coordinates::format_distance, core::Units};
 
 #[derive(Parser)]
 #[command(name = "solarviewer")]
@@ -15,6 +15,8 @@ struct Cli {
     command: Commands,
 }
 
+// Parsed once per run, so the size of the largest variant doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Explore and document the schema of an Astrosynthesis .AstroDB file
@@ -37,6 +39,34 @@ enum Commands {
         /// Output CSV file path
         #[arg(short, long, default_value = "stars.csv")]
         output: String,
+
+        /// Add RA/Dec (J2000) columns to the CSV
+        #[arg(long)]
+        equatorial: bool,
+
+        /// Only export these spectral classes, comma-separated (e.g. "G,K")
+        #[arg(long)]
+        spectral_filter: Option<String>,
+
+        /// Only export stars near this star (requires --radius)
+        #[arg(long, requires = "radius")]
+        center: Option<String>,
+
+        /// Radius in light-years around --center
+        #[arg(long, requires = "center")]
+        radius: Option<f64>,
+
+        /// Also print a terminal map of the bubble around --center
+        #[arg(long, requires = "center")]
+        ascii: bool,
+
+        /// Merge stars sharing a catalog designation, HIP or HD number
+        #[arg(long)]
+        merge_duplicates: bool,
+
+        /// Export positions this many years in the future, moving stars along their velocity
+        #[arg(long)]
+        years: Option<f64>,
     },
 
     /// Analyze multi-star systems in an Astrosynthesis file
@@ -46,6 +76,29 @@ enum Commands {
         file: String,
     },
 
+    /// Find the shortest jump route between two stars in an Astrosynthesis file
+    Path {
+        /// Path to the .AstroDB file
+        #[arg(short, long)]
+        file: String,
+
+        /// Star to start from
+        #[arg(long)]
+        from: String,
+
+        /// Star to reach
+        #[arg(long)]
+        to: String,
+
+        /// Longest single jump in light-years
+        #[arg(short, long, default_value = "7")]
+        max_jump: f64,
+
+        /// Comma-separated star names the route must not pass through (e.g., "Wolf 359,Ross 128")
+        #[arg(long)]
+        avoid: Option<String>,
+    },
+
     /// Extract data from an Astrosynthesis file and load into PostgreSQL
     Import {
         /// Path to the .AstroDB file
@@ -86,10 +139,26 @@ enum Commands {
         #[arg(short, long)]
         file: String,
 
-        /// Center star name (e.g., "Amateru")
-        #[arg(short, long)]
+        /// Center star name (e.g., "Amateru"); may be blank when --star-id or --x/--y/--z is given
+        #[arg(short, long, default_value = "")]
         star: String,
 
+        /// System/catalog ID to pick between stars sharing a name (database id, system name, catalog designation, "HIP n" or "HD n")
+        #[arg(long)]
+        star_id: Option<String>,
+
+        /// Center on the star nearest this galactic X coordinate in light-years
+        #[arg(long, allow_hyphen_values = true, requires_all = ["y", "z"], conflicts_with_all = ["star", "star_id"])]
+        x: Option<f64>,
+
+        /// Galactic Y coordinate to center near
+        #[arg(long, allow_hyphen_values = true, requires_all = ["x", "z"])]
+        y: Option<f64>,
+
+        /// Galactic Z coordinate to center near
+        #[arg(long, allow_hyphen_values = true, requires_all = ["x", "y"])]
+        z: Option<f64>,
+
         /// Radius around center star in light-years
         #[arg(short, long, default_value = "25")]
         radius: f64,
@@ -109,6 +178,46 @@ enum Commands {
         /// Maximum distance for star connections in light-years
         #[arg(long, default_value = "7")]
         connection_distance: f64,
+
+        /// Render in square tiles of this many pixels instead of one image
+        #[arg(long)]
+        tile_size: Option<u32>,
+
+        /// Directory for tiles when --tile-size is set
+        #[arg(long, default_value = "star_map_tiles")]
+        tile_dir: String,
+
+        /// Also stitch the tiles into --output
+        #[arg(long, requires = "tile_size")]
+        stitch: bool,
+
+        /// Background color as #rrggbb
+        #[arg(long, default_value = "#000000")]
+        background: String,
+
+        /// Draw a coordinate grid with lines this many light-years apart
+        #[arg(long)]
+        grid: Option<f64>,
+
+        /// Title caption across the top of the map
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Axis to look along: z (top-down), y or x
+        #[arg(long, default_value = "z")]
+        view_axis: String,
+
+        /// Turn the map clockwise by this many degrees
+        #[arg(long, default_value = "0", allow_hyphen_values = true)]
+        rotation: f64,
+
+        /// Ring systems holding a body with this tag (e.g. "colonized")
+        #[arg(long, requires = "tags_from")]
+        highlight_tag: Option<String>,
+
+        /// StellarForge dataset JSON to read tags, star colors and markers from
+        #[arg(long)]
+        tags_from: Option<String>,
     },
 
     /// Create a new StellarForge galaxy
@@ -130,6 +239,76 @@ enum Commands {
         seed: u64,
     },
 
+    /// Check a StellarForge dataset JSON file for structural problems
+    Validate {
+        /// Dataset file to check
+        #[arg(short, long)]
+        file: String,
+    },
+
+    /// Print a summary of a StellarForge dataset JSON file
+    Stats {
+        /// Dataset file to summarize
+        #[arg(short, long)]
+        file: String,
+    },
+
+    /// List habitable worlds near a system or point of a StellarForge dataset JSON file
+    Habitable {
+        /// Dataset file to search
+        #[arg(short, long)]
+        file: String,
+
+        /// System name, or galactic "x,y,z" in light-years, to search around
+        #[arg(short, long, allow_hyphen_values = true)]
+        center: String,
+
+        /// Search radius in light-years
+        #[arg(short, long, default_value = "25")]
+        radius: f64,
+    },
+
+    /// Merge several StellarForge dataset JSON files into one
+    Merge {
+        /// Datasets to merge, earlier files take precedence under "keep"
+        #[arg(short, long, num_args = 2.., required = true)]
+        inputs: Vec<String>,
+
+        /// Output file path for the merged dataset
+        #[arg(short, long, default_value = "merged.json")]
+        output: String,
+
+        /// What to do with systems whose id or name already exists: keep, replace or rename
+        #[arg(long, default_value = "keep")]
+        policy: String,
+    },
+
+    /// Write one system of a StellarForge dataset to its own JSON file
+    ExportSystem {
+        /// Dataset file to read
+        #[arg(short, long)]
+        file: String,
+
+        /// Name of the system to export
+        #[arg(short, long)]
+        system_name: String,
+
+        /// Output file path for the system
+        #[arg(short, long, default_value = "system.json")]
+        output: String,
+    },
+
+    /// Insert a system JSON file into a dataset, replacing the system with the same id
+    ImportSystem {
+        /// Dataset file to update in place
+        #[arg(short, long)]
+        file: String,
+
+        /// System file written by export-system
+        #[arg(short, long)]
+        input: String,
+    },
+
     /// Create a Sol-like system using StellarForge
     CreateSol {
         /// Output file path for the system data
@@ -171,7 +350,7 @@ fn main() -> Result<()> {
             println!("  Documentation: {}", output);
         }
 
-        Commands::Extract { file, output } => {
+        Commands::Extract { file, output, equatorial, spectral_filter, center, radius, ascii, merge_duplicates, years } => {
             info!("Extracting stars from: {}", file);
             info!("Output will be written to: {}", output);
 
@@ -183,12 +362,61 @@ fn main() -> Result<()> {
             let count = reader.count_stars()?;
             info!("Found {} stars", count);
 
-            // Read all stars
-            let stars = reader.read_all_stars()?;
+            // Read all stars, or only the requested spectral classes
+            let stars = match &spectral_filter {
+                Some(list) => {
+                    let classes = visualization::spectral::parse_spectral_classes(list)
+                        .map_err(|e| anyhow::anyhow!(e))?;
+                    reader.read_stars_filtered(|star| {
+                        star.spectral_type
+                            .parse::<visualization::spectral::SpectralType>()
+                            .is_ok_and(|class| classes.contains(&class))
+                    })?
+                }
+                None => reader.read_all_stars()?,
+            };
             info!("Read {} stars", stars.len());
 
+            let stars = if merge_duplicates {
+                let before = stars.len();
+                let merged = extraction::merge_duplicate_stars(stars);
+                info!("Merged {} duplicate catalog entries", before - merged.len());
+                merged
+            } else {
+                stars
+            };
+
+            let stars = match years {
+                Some(years) => {
+                    info!("Projecting positions {} years ahead", years);
+                    extraction::project_positions(&stars, years)
+                }
+                None => stars,
+            };
+
+            // Keep a bubble around the center; it is resolved against every star so the
+            // spectral filter doesn't have to admit it
+            let stars = match (&center, radius) {
+                (Some(center_name), Some(radius_ly)) => {
+                    // The center is projected with the same years as the stars around it
+                    let all_stars = match years {
+                        Some(years) => extraction::project_positions(&reader.read_all_stars()?, years),
+                        None => reader.read_all_stars()?,
+                    };
+                    let center_star = visualization::find_center_star(&all_stars, center_name, None)?;
+                    let bubble = visualization::stars_within_radius(&stars, center_star, radius_ly);
+                    info!("{} stars within {} ly of {}", bubble.len(), radius_ly, center_star.display_name());
+                    if ascii {
+                        print!("{}", visualization::render_ascii_map(&bubble, center_star, radius_ly, 79, 39));
+                    }
+                    bubble
+                }
+                _ => stars,
+            };
+
             // Export to CSV
-            extraction::export_stars_to_csv(&stars, &output)?;
+            let options = extraction::CsvOptions { include_equatorial: equatorial };
+            extraction::export_stars_to_csv_with(&stars, &output, options)?;
             info!("Stars exported to CSV: {}", output);
 
             println!("✓ Star extraction complete!");
@@ -201,6 +429,57 @@ fn main() -> Result<()> {
             extraction::analyze_multistar_systems(&file)?;
         }
 
+        Commands::Path { file, from, to, max_jump, avoid } => {
+            info!("Finding a route from {} to {} in: {}", from, to, file);
+
+            let reader = extraction::StarReader::new(&file)?;
+            let stars = reader.read_all_stars()?;
+            let origin = visualization::find_center_star(&stars, &from, None)?;
+            let destination = visualization::find_center_star(&stars, &to, None)?;
+            let index_of = |star: &extraction::Star| stars.iter().position(|s| s.id == star.id).unwrap();
+
+            let avoid = avoid
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| visualization::find_center_star(&stars, name, None).map(index_of))
+                .collect::<Result<HashSet<usize>>>()?;
+            if avoid.contains(&index_of(origin)) || avoid.contains(&index_of(destination)) {
+                anyhow::bail!("The start and end of the route cannot be in --avoid");
+            }
+            if !avoid.is_empty() {
+                info!("Avoiding {} stars", avoid.len());
+            }
+
+            match analysis::shortest_jump_path(&stars, index_of(origin), index_of(destination), max_jump, &avoid) {
+                Some(path) => {
+                    println!(
+                        "Route from {} to {} ({} jumps, {}):",
+                        from, to, path.stars.len() - 1, format_distance(path.distance_ly * Units::LIGHT_YEAR)
+                    );
+                    let mut previous: Option<&extraction::Star> = None;
+                    for &idx in &path.stars {
+                        let star = &stars[idx];
+                        match previous {
+                            Some(prev) => println!(
+                                "  -> {} ({})",
+                                star.display_name(),
+                                format_distance(prev.distance_to(star) * Units::LIGHT_YEAR)
+                            ),
+                            None => println!("  {}", star.display_name()),
+                        }
+                        previous = Some(star);
+                    }
+                }
+                None => {
+                    let avoiding = if avoid.is_empty() { String::new() } else { format!(" avoiding {} stars", avoid.len()) };
+                    println!("No route from {} to {} with jumps of at most {} ly{}", from, to, max_jump, avoiding);
+                }
+            }
+        }
+
         Commands::Import { file, name, database } => {
             info!("Importing {} as '{}'", file, name);
             info!("Target database: {}", database);
@@ -214,7 +493,7 @@ fn main() -> Result<()> {
             println!("  4. Load into PostgreSQL with source tracking");
         }
 
-        Commands::Map { name, output, algorithm, database } => {
+        Commands::Map { name, output, algorithm, database: _ } => {
             info!("Generating map for dataset: {}", name);
             info!("Using {} algorithm", algorithm);
             info!("Output file: {}", output);
@@ -228,21 +507,76 @@ fn main() -> Result<()> {
             println!("  4. Render to {}", output);
         }
 
-        Commands::Render { file, star, radius, output, width, height, connection_distance } => {
+        Commands::Render {
+            file, star, star_id, x, y, z, radius, output, width, height, connection_distance, tile_size, tile_dir, stitch,
+            background, grid, title, view_axis, rotation, highlight_tag, tags_from,
+        } => {
+            // A coordinate stands in for the center star by resolving to the nearest one's id
+            let (star, star_id) = match (x, y, z) {
+                (Some(x), Some(y), Some(z)) => {
+                    let nearest = extraction::StarReader::new(&file)?
+                        .nearest_star(x, y, z)?
+                        .ok_or_else(|| anyhow::anyhow!("No stars found in {}", file))?;
+                    info!("Nearest star to ({}, {}, {}) is {}", x, y, z, nearest.display_name());
+                    (nearest.display_name(), Some(nearest.id.to_string()))
+                }
+                _ => (star, star_id),
+            };
+
+            let tags_from_galaxy = match &tags_from {
+                Some(dataset_path) => Some(stellar_forge::storage::FileStorage::load_json(dataset_path)?.galaxy),
+                None => None,
+            };
+            let style = visualization::MapStyle {
+                background: visualization::Color::from_hex(&background).map_err(anyhow::Error::msg)?,
+                grid_spacing_ly: grid,
+                title,
+                orientation: visualization::Orientation {
+                    view_axis: view_axis.parse().map_err(anyhow::Error::msg)?,
+                    rotation_deg: rotation,
+                },
+                highlight: match (&highlight_tag, &tags_from_galaxy) {
+                    (Some(tag), Some(galaxy)) => Some(visualization::TagHighlight::from_galaxy(galaxy, tag)),
+                    _ => None,
+                },
+                overrides: tags_from_galaxy.as_ref().map(visualization::StarOverrides::from_galaxy),
+            };
+
             info!("Rendering star map for: {}", star);
             info!("File: {}", file);
             info!("Radius: {} ly", radius);
             info!("Output: {}", output);
 
-            visualization::render_star_map(
-                &file,
-                &star,
-                radius,
-                &output,
-                width,
-                height,
-                connection_distance,
-            )?;
+            match tile_size {
+                Some(tile_size) => {
+                    visualization::render_star_map_tiled(
+                        &file,
+                        &star,
+                        star_id.as_deref(),
+                        radius,
+                        &tile_dir,
+                        width,
+                        height,
+                        connection_distance,
+                        tile_size,
+                        stitch.then_some(output.as_str()),
+                        &style,
+                    )?;
+                }
+                None => {
+                    visualization::render_star_map(
+                        &file,
+                        &star,
+                        star_id.as_deref(),
+                        radius,
+                        &output,
+                        width,
+                        height,
+                        connection_distance,
+                        &style,
+                    )?;
+                }
+            }
 
             println!("✓ Star map rendering complete!");
             println!("  Center star: {}", star);
@@ -275,12 +609,103 @@ fn main() -> Result<()> {
             println!("  Output: {}", output);
         }
 
+        Commands::Validate { file } => {
+            use stellar_forge::storage::FileStorage;
+
+            let dataset = FileStorage::load_json(&file)?;
+            let problems = dataset.validate();
+            if !problems.is_empty() {
+                for problem in &problems {
+                    println!("✗ {}", problem);
+                }
+                anyhow::bail!("{} problem(s) found in {}", problems.len(), file);
+            }
+
+            println!("✓ {} is valid", file);
+            println!("  Systems: {}", dataset.metadata.statistics.total_systems);
+            println!("  Stars: {}", dataset.metadata.statistics.total_stars);
+        }
+
+        Commands::Stats { file } => {
+            use stellar_forge::storage::FileStorage;
+
+            let dataset = FileStorage::load_json(&file)?;
+            print!("{}", dataset.summary());
+        }
+
+        Commands::Habitable { file, center, radius } => {
+            use stellar_forge::storage::FileStorage;
+
+            let dataset = FileStorage::load_json(&file)?;
+            let galaxy = &dataset.galaxy;
+            let coordinates: Vec<f64> = center.split(',').filter_map(|c| c.trim().parse().ok()).collect();
+            let origin = match coordinates[..] {
+                [x, y, z] => stellar_forge::core::Vec3::new(x, y, z) * stellar_forge::core::Units::LIGHT_YEAR,
+                _ => galaxy
+                    .find_system_by_name(&center)
+                    .ok_or_else(|| anyhow::anyhow!("No system named '{}' in {}", center, file))?
+                    .galactic_position(),
+            };
+
+            let worlds = galaxy.habitable_worlds_within_ly(origin, radius);
+            println!("{} habitable world(s) within {} ly of {}", worlds.len(), radius, center);
+            for (world, distance_ly) in &worlds {
+                let temperature = world.surface_temperature_k
+                    .map(|k| format!("{:.0} K", k))
+                    .unwrap_or_else(|| "?".to_string());
+                println!(
+                    "  {:>7.2} ly  {:<20} {:<20} habitability {:.2}  {}",
+                    distance_ly, world.name, world.system_name, world.habitability_score, temperature
+                );
+            }
+        }
+
+        Commands::Merge { inputs, output, policy } => {
+            use stellar_forge::{containers::MergePolicy, storage::FileStorage};
+
+            let policy: MergePolicy = policy.parse().map_err(anyhow::Error::msg)?;
+            let (dataset, summary) = FileStorage::merge_json(&inputs, policy)?;
+            FileStorage::save_json(&dataset, &output)?;
+
+            println!("✓ Merged {} datasets", inputs.len());
+            println!("  Systems: {}", dataset.metadata.statistics.total_systems);
+            println!(
+                "  Added: {}, replaced: {}, renamed: {}, skipped: {}",
+                summary.added, summary.replaced, summary.renamed, summary.skipped
+            );
+            for failure in &summary.failed {
+                println!("✗ Could not merge {}", failure);
+            }
+            println!("  Output: {}", output);
+        }
+
+        Commands::ExportSystem { file, system_name, output } => {
+            use stellar_forge::storage::FileStorage;
+
+            let dataset = FileStorage::load_json(&file)?;
+            dataset.export_system_named(&system_name, &output)?;
+
+            println!("✓ Exported {} to {}", system_name, output);
+        }
+
+        Commands::ImportSystem { file, input } => {
+            use stellar_forge::storage::FileStorage;
+
+            let mut dataset = FileStorage::load_json(&file)?;
+            let system = FileStorage::import_system(&input)?;
+            let name = system.name.clone();
+            let replaced = dataset.import_system(system)?;
+            FileStorage::save_json(&dataset, &file)?;
+
+            println!("✓ {} {} in {}", if replaced { "Replaced" } else { "Added" }, name, file);
+            println!("  Systems: {}", dataset.metadata.statistics.total_systems);
+        }
+
         Commands::CreateSol { output } => {
             use stellar_forge::{
                 builders::create_sol_like_system,
                 containers::Galaxy,
                 storage::{StellarForgeDataset, FileStorage},
-                services::StellarForgeService,
             };
 
             info!("Creating Sol-like system");
@@ -58,7 +58,7 @@ pub fn generate_markdown(tables: &[TableInfo], output_path: &str, db_path: &str)
                 if col.is_pk { "✓" } else { "" }
             ));
         }
-        md.push_str("\n");
+        md.push('\n');
 
         // Foreign Keys
         if !table.foreign_keys.is_empty() {
@@ -72,7 +72,7 @@ pub fn generate_markdown(tables: &[TableInfo], output_path: &str, db_path: &str)
                     fk.from, fk.table, fk.to, fk.on_update, fk.on_delete
                 ));
             }
-            md.push_str("\n");
+            md.push('\n');
         }
 
         // Sample Data
@@ -84,22 +84,22 @@ pub fn generate_markdown(tables: &[TableInfo], output_path: &str, db_path: &str)
             let column_names: Vec<&String> = first_row.keys().collect();
 
             // Header
-            md.push_str("|");
+            md.push('|');
             for col_name in &column_names {
                 md.push_str(&format!(" {} |", col_name));
             }
-            md.push_str("\n");
+            md.push('\n');
 
             // Separator
-            md.push_str("|");
+            md.push('|');
             for _ in &column_names {
                 md.push_str("---|");
             }
-            md.push_str("\n");
+            md.push('\n');
 
             // Data rows (limit to 5 for readability)
             for row in table.sample_data.iter().take(5) {
-                md.push_str("|");
+                md.push('|');
                 for col_name in &column_names {
                     let value = row.get(*col_name).map(|s| s.as_str()).unwrap_or("NULL");
                     // Truncate long values for readability
@@ -110,9 +110,9 @@ pub fn generate_markdown(tables: &[TableInfo], output_path: &str, db_path: &str)
                     };
                     md.push_str(&format!(" {} |", display_value));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
-            md.push_str("\n");
+            md.push('\n');
         }
 
         md.push_str("---\n\n");
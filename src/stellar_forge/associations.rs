@@ -61,8 +61,8 @@ impl Association {
     }
 
     pub fn is_active(&self, at: OffsetDateTime) -> bool {
-        let after_start = self.since_epoch.map_or(true, |s| at >= s);
-        let before_end = self.until_epoch.map_or(true, |e| at <= e);
+        let after_start = self.since_epoch.is_none_or(|s| at >= s);
+        let before_end = self.until_epoch.is_none_or(|e| at <= e);
         after_start && before_end
     }
 
@@ -98,6 +98,12 @@ pub struct AssociationManager {
     entities_by_tag: HashMap<Tag, HashSet<Id>>,
 }
 
+impl Default for AssociationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AssociationManager {
     pub fn new() -> Self {
         Self {
@@ -114,12 +120,12 @@ impl AssociationManager {
 
         self.associations_by_entity
             .entry(entity_id)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(association);
 
         self.associations_by_group
             .entry(group)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(entity_id);
     }
 
@@ -170,12 +176,12 @@ impl AssociationManager {
     pub fn add_tag(&mut self, entity_id: Id, tag: Tag) {
         self.tags_by_entity
             .entry(entity_id)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(tag.clone());
 
         self.entities_by_tag
             .entry(tag)
-            .or_insert_with(HashSet::new)
+            .or_default()
             .insert(entity_id);
     }
 
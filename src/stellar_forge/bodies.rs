@@ -4,7 +4,6 @@ use crate::stellar_forge::core::{
     Id, State, Vec3, Identified, Spatial, Container, ContainerError,
     Massive, Orbital, Rotating, Taggable, Associable,
 };
-use crate::stellar_forge::frames::{Frame, FrameKind};
 use crate::stellar_forge::motion::MotionModel;
 use crate::stellar_forge::physical::Physical;
 use crate::stellar_forge::associations::Association;
@@ -257,6 +256,11 @@ impl StellarBody {
         result
     }
 
+    // Associations valid at `epoch` per their since/until bounds
+    pub fn active_associations_at(&self, epoch: OffsetDateTime) -> Vec<&Association> {
+        self.associations.iter().filter(|a| a.is_active(epoch)).collect()
+    }
+
     // Find a descendant by ID
     pub fn find_descendant(&self, id: Id) -> Option<&StellarBody> {
         for child in &self.children {
@@ -283,8 +287,86 @@ impl StellarBody {
         None
     }
 
+    // Draw fresh ids for this body and its descendants from `rng`, re-pointing
+    // parent links and pinning the epoch (and Keplerian elements) to `epoch`
+    pub fn reseed_ids<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, parent: SpatialParent, epoch: OffsetDateTime) {
+        self.id = crate::stellar_forge::core::seeded_id(rng);
+        self.spatial_parent = parent;
+        self.epoch = epoch;
+        if let Some(MotionModel::Keplerian(elements)) = &mut self.motion {
+            elements.epoch = epoch;
+        }
+
+        let own_id = self.id;
+        for child in &mut self.children {
+            child.reseed_ids(rng, SpatialParent::Body(own_id), epoch);
+        }
+    }
+
+    // Chain of bodies from this one down to the descendant with `id` (both inclusive)
+    pub fn lineage(&self, id: Id) -> Option<Vec<&StellarBody>> {
+        if self.id == id {
+            return Some(vec![self]);
+        }
+        self.children.iter().find_map(|child| {
+            child.lineage(id).map(|mut chain| {
+                chain.insert(0, self);
+                chain
+            })
+        })
+    }
+
+    // Hill sphere radius a(1-e)·cbrt(m / 3M) for this body orbiting a primary of
+    // `primary_mass_kg`; satellites beyond it are stripped away by the primary.
+    // Zero when this body has no mass.
+    pub fn hill_sphere_radius(&self, primary_mass_kg: f64, semi_major_axis_m: f64, eccentricity: f64) -> f64 {
+        match self.mass_kg() {
+            Some(mass) if mass > 0.0 && primary_mass_kg > 0.0 => {
+                semi_major_axis_m * (1.0 - eccentricity) * (mass / (3.0 * primary_mass_kg)).cbrt()
+            }
+            _ => 0.0,
+        }
+    }
+
+    // Whether tides from a star of `star_mass_kg` would have synchronized this body's spin
+    // with its orbit within `age_years`. Uses the despinning time
+    // t = ω a⁶ I Q / (3 G M² k₂ R⁵) with I = 0.4 m R², starting from a 12 hour day with
+    // rocky-planet Q = 100 and k₂ = 0.3. False without an orbit, mass or radius.
+    pub fn is_tidally_locked(&self, star_mass_kg: f64, age_years: f64) -> bool {
+        const G: f64 = 6.67430e-11;
+        const INITIAL_SPIN_RAD_S: f64 = 2.0 * std::f64::consts::PI / (12.0 * 3600.0);
+        const DISSIPATION_Q: f64 = 100.0;
+        const LOVE_NUMBER_K2: f64 = 0.3;
+        const SECONDS_PER_YEAR: f64 = 3.15576e7;
+
+        let (Some(a), Some(mass), Some(radius)) = (self.semi_major_axis_m(), self.mass_kg(), self.radius_m()) else {
+            return false;
+        };
+        if star_mass_kg <= 0.0 || radius <= 0.0 {
+            return false;
+        }
+
+        let lock_time_s = 0.4 * INITIAL_SPIN_RAD_S * a.powi(6) * mass * DISSIPATION_Q
+            / (3.0 * G * star_mass_kg.powi(2) * LOVE_NUMBER_K2 * radius.powi(3));
+        lock_time_s <= age_years * SECONDS_PER_YEAR
+    }
+
+    // Spin this body down to its orbital period if `is_tidally_locked`; returns whether it did
+    pub fn apply_tidal_locking(&mut self, star_mass_kg: f64, age_years: f64) -> bool {
+        if !self.is_tidally_locked(star_mass_kg, age_years) {
+            return false;
+        }
+        match self.orbital_period_s() {
+            Some(period_s) => {
+                self.rotation_period_hours = Some(period_s / 3600.0);
+                true
+            }
+            None => false,
+        }
+    }
+
     // Get hierarchical path as string (e.g., "Sol/Earth/Moon")
-    pub fn path(&self, separator: &str) -> String {
+    pub fn path(&self, _separator: &str) -> String {
         // This would need access to parent hierarchy
         // For now, just return the name
         self.name.clone()
@@ -292,17 +374,12 @@ impl StellarBody {
 
     // Validate orbital configuration
     pub fn validate_orbit(&self) -> Result<(), String> {
-        if let Some(motion) = &self.motion {
-            match motion {
-                MotionModel::Keplerian(elements) => {
-                    if elements.eccentricity >= 1.0 && !matches!(self.kind, BodyKind::Comet) {
-                        return Err("Non-comet body has hyperbolic orbit".into());
-                    }
-                    if elements.semi_major_axis_m <= 0.0 {
-                        return Err("Invalid semi-major axis".into());
-                    }
-                }
-                _ => {}
+        if let Some(MotionModel::Keplerian(elements)) = &self.motion {
+            if elements.eccentricity >= 1.0 && !matches!(self.kind, BodyKind::Comet) {
+                return Err("Non-comet body has hyperbolic orbit".into());
+            }
+            if elements.semi_major_axis_m <= 0.0 {
+                return Err("Invalid semi-major axis".into());
             }
         }
         Ok(())
@@ -368,6 +445,11 @@ impl Container for StellarBody {
             return Err(ContainerError::ChildAlreadyExists(child.id));
         }
 
+        // Refuse a subtree that already holds this body, e.g. a copy of an ancestor
+        if child.id == self.id || child.find_descendant(self.id).is_some() {
+            return Err(ContainerError::CircularReference);
+        }
+
         // Set child's spatial parent to this body
         child.spatial_parent = SpatialParent::Body(self.id);
 
@@ -507,4 +589,78 @@ impl Associable for StellarBody {
     fn is_member_of(&self, group: &str) -> bool {
         self.associations.iter().any(|a| a.group == group)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_child_refuses_cycle() {
+        let frame = Id::new_v4();
+        let mut planet = StellarBody::new_planet("Planet", SpatialParent::Frame(frame));
+        let moon = StellarBody::new_moon("Moon", SpatialParent::Frame(frame));
+        let moon_id = moon.id;
+        planet.add_child(moon).unwrap();
+
+        // A copy of the planet, relabelled so the kind check passes, cannot go under its own moon
+        let mut copy = planet.clone();
+        copy.kind = BodyKind::Station;
+        let moon = planet.find_descendant_mut(moon_id).unwrap();
+        assert!(matches!(moon.add_child(copy), Err(ContainerError::CircularReference)));
+
+        let mut station = StellarBody::new_station("Station", SpatialParent::Frame(frame));
+        let mut itself = station.clone();
+        itself.kind = BodyKind::Vehicle;
+        assert!(matches!(station.add_child(itself), Err(ContainerError::CircularReference)));
+        assert_eq!(planet.descendants().len(), 1);
+    }
+
+    #[test]
+    fn test_hill_sphere_radius() {
+        let mut earth = StellarBody::new_planet("Earth", SpatialParent::Frame(Id::new_v4()));
+        earth.physical = Some(Physical::Planet(crate::stellar_forge::physical::PlanetPhysical {
+            mass_kg: crate::stellar_forge::core::Units::EARTH_MASS,
+            ..Default::default()
+        }));
+
+        let hill = earth.hill_sphere_radius(1.989e30, crate::stellar_forge::core::Units::AU, 0.0167);
+        assert!((hill - 1.47e9).abs() < 0.02e9, "Earth's Hill radius is {} m", hill);
+
+        // The Moon sits at about a quarter of it
+        assert!(3.844e8 < hill / 3.0);
+
+        let massless = StellarBody::new_planet("Ghost", SpatialParent::Frame(Id::new_v4()));
+        assert_eq!(massless.hill_sphere_radius(1.989e30, 1.0e11, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_tidal_locking_close_in_m_dwarf_planet() {
+        use crate::stellar_forge::core::Units;
+        use crate::stellar_forge::motion::OrbitalElements;
+
+        fn planet_at(name: &str, star_mass_kg: f64, a_au: f64) -> StellarBody {
+            let mut planet = StellarBody::new_planet(name, SpatialParent::Frame(Id::new_v4()));
+            planet.physical = Some(Physical::Planet(Default::default()));
+            let elements = OrbitalElements::new(
+                a_au * Units::AU, 0.0, 0.0, 0.0, 0.0, 0.0, 6.67430e-11 * star_mass_kg, planet.epoch,
+            );
+            planet.set_orbital_motion(MotionModel::Keplerian(elements));
+            planet.rotation_period_hours = Some(24.0);
+            planet
+        }
+
+        // An Earth-sized world in the habitable zone of an old 0.3 solar-mass red dwarf
+        let red_dwarf = 0.3 * Units::SOLAR_MASS;
+        let mut close_in = planet_at("Close", red_dwarf, 0.05);
+        assert!(close_in.is_tidally_locked(red_dwarf, 5e9));
+        assert!(close_in.apply_tidal_locking(red_dwarf, 5e9));
+        let orbit_hours = close_in.orbital_period_s().unwrap() / 3600.0;
+        assert_eq!(close_in.rotation_period_hours, Some(orbit_hours));
+
+        let mut earth = planet_at("Earth", Units::SOLAR_MASS, 1.0);
+        assert!(!earth.is_tidally_locked(Units::SOLAR_MASS, 4.6e9));
+        assert!(!earth.apply_tidal_locking(Units::SOLAR_MASS, 4.6e9));
+        assert_eq!(earth.rotation_period_hours, Some(24.0));
+    }
+}
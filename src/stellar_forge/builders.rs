@@ -1,18 +1,25 @@
 // Builder patterns for creating stellar systems and objects in StellarForge
 
-use crate::stellar_forge::core::{Id, State, Vec3, Units};
-use crate::stellar_forge::bodies::{StellarBody, BodyKind, SpatialParent};
-use crate::stellar_forge::containers::{StarSystem, Galaxy, SystemType, PoliticalRegion};
-use crate::stellar_forge::frames::Frame;
-use crate::stellar_forge::motion::{MotionModel, OrbitalElements, FreeMotion};
+use crate::stellar_forge::core::{Id, Vec3, Units, Container, Massive, Taggable};
+use crate::stellar_forge::bodies::{StellarBody, SpatialParent};
+use crate::stellar_forge::containers::{StarSystem, Galaxy, SystemType, Sector, Bounds, BoundShape};
+use crate::stellar_forge::motion::{MotionModel, OrbitalElements};
 use crate::stellar_forge::physical::{
-    Physical, StarPhysical, PlanetPhysical, PlanetaryComposition,
-    Atmosphere, StationPhysical, BeltPhysical,
+    Physical, PlanetPhysical, PlanetaryComposition,
+    Atmosphere, StationPhysical, BeltPhysical, SpectralClass,
+    AsteroidPhysical, AsteroidComposition,
 };
 use crate::stellar_forge::associations::Tag;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use time::OffsetDateTime;
 
+// Epoch stamped on everything a seeded generator produces (J2000)
+const GENERATION_EPOCH: OffsetDateTime = time::macros::datetime!(2000-01-01 12:00:00 UTC);
+
+// Lowest mass, in solar masses, that sustains hydrogen fusion; brown dwarfs sit below it
+pub const HYDROGEN_BURNING_LIMIT_SOLAR: f64 = 0.075;
+
 // System builder for creating complete star systems
 pub struct SystemBuilder {
     name: String,
@@ -22,6 +29,7 @@ pub struct SystemBuilder {
     planets: Vec<PlanetBuilder>,
     belts: Vec<BeltBuilder>,
     stations: Vec<StationBuilder>,
+    comets: Vec<CometBuilder>,
     tags: Vec<Tag>,
 }
 
@@ -35,6 +43,7 @@ impl SystemBuilder {
             planets: Vec::new(),
             belts: Vec::new(),
             stations: Vec::new(),
+            comets: Vec::new(),
             tags: Vec::new(),
         }
     }
@@ -85,6 +94,24 @@ impl SystemBuilder {
         self
     }
 
+    // Add a substellar companion of spectral class L, T or Y (other classes become T5), with
+    // the low mass and luminosity of a brown dwarf. It is lettered after the stars so far.
+    pub fn with_brown_dwarf(mut self, spectral: impl Into<String>) -> Self {
+        let spectral = spectral.into();
+        let spectral = match crate::stellar_forge::physical::parse_spectral_type(&spectral).class {
+            SpectralClass::L | SpectralClass::T | SpectralClass::Y => spectral,
+            _ => "T5".to_string(),
+        };
+        let letter = (b'A' + self.stars.len().min(25) as u8) as char;
+        let dwarf = StellarBody::new_star(
+            format!("{} {}", self.name, letter),
+            SpatialParent::Frame(Id::new_v4()),
+            spectral,
+        );
+        self.stars.push(dwarf);
+        self
+    }
+
     pub fn with_binary_stars(
         mut self,
         primary_spectral: impl Into<String>,
@@ -109,35 +136,84 @@ impl SystemBuilder {
         let total_mass = primary_mass + secondary_mass;
 
         // Barycenter calculations
-        let r1 = separation_au * Units::AU * secondary_mass / total_mass;
-        let r2 = separation_au * Units::AU * primary_mass / total_mass;
+        let separation_m = separation_au * Units::AU;
+        let r1 = separation_m * secondary_mass / total_mass;
+        let r2 = separation_m * primary_mass / total_mass;
 
-        primary.set_position(Vec3::new(-r1, 0.0, 0.0));
-        secondary.set_position(Vec3::new(r2, 0.0, 0.0));
-
-        // Set orbital motion for secondary around barycenter
+        // Both components orbit the barycenter with the binary's period, on opposite sides.
+        // Scaling mu by (r_i / a)^3 keeps each star's mean motion equal to the pair's.
         let mu = 6.67430e-11 * total_mass;  // G * M_total
-        let orbital_period = 2.0 * std::f64::consts::PI
-            * ((separation_au * Units::AU).powi(3) / mu).sqrt();
-
-        let elements = OrbitalElements::new(
-            separation_au * Units::AU,
-            0.0,  // circular
-            0.0,  // coplanar
-            0.0,
-            0.0,
-            0.0,
-            mu,
-            OffsetDateTime::now_utc(),
-        );
-
-        secondary.set_orbital_motion(MotionModel::Keplerian(elements));
+        let epoch = primary.epoch;
+
+        for (star, radius, mean_anomaly) in [
+            (&mut primary, r1, std::f64::consts::PI),
+            (&mut secondary, r2, 0.0),
+        ] {
+            let elements = OrbitalElements::new(
+                radius,
+                0.0,  // circular
+                0.0,  // coplanar
+                0.0,
+                0.0,
+                mean_anomaly,
+                mu * (radius / separation_m).powi(3),
+                epoch,
+            );
+
+            star.epoch = epoch;
+            star.state = elements.propagate(star.state, 0.0);
+            star.set_orbital_motion(MotionModel::Keplerian(elements));
+        }
 
         self.stars.push(primary);
         self.stars.push(secondary);
         self
     }
 
+    // Add `count` planets on a Titius-Bode-like ladder scaled to the primary star.
+    // Worlds inside the frost line are rocky; beyond it gas giants give way to ice giants.
+    pub fn with_procedural_planets(self, count: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.with_procedural_planets_rng(count, &mut rng)
+    }
+
+    // Same as `with_procedural_planets`, drawing from a caller-owned RNG
+    pub fn with_procedural_planets_rng<R: Rng + ?Sized>(mut self, count: usize, rng: &mut R) -> Self {
+        // Fall back to solar values if no star has been added yet
+        let luminosity_solar = match self.stars.first().and_then(|s| s.physical.as_ref()) {
+            Some(Physical::Star(star)) => star.luminosity_solar(),
+            _ => 1.0,
+        };
+        let scale = luminosity_solar.sqrt();
+        let frost_line_au = 2.7 * scale;
+
+        let first_index = self.planets.len();
+        for n in 0..count {
+            // Mercury, Venus, Earth, Mars, ... at 0.4, 0.7, 1.0, 1.6, 2.8, 5.2 AU for the Sun
+            let bode_au = if n == 0 { 0.4 } else { 0.4 + 0.3 * 2f64.powi(n as i32 - 1) };
+            let orbit_au = bode_au * scale * rng.gen_range(0.9..1.1);
+            let name = format!("{} {}", self.name, planet_letter(first_index + n));
+
+            let planet = if orbit_au < frost_line_au {
+                let mass_earth: f64 = rng.gen_range(0.05..2.0);
+                PlanetBuilder::terrestrial(name, orbit_au)
+                    .with_mass_and_radius(mass_earth, mass_earth.powf(0.27))
+            } else if orbit_au < 4.0 * frost_line_au {
+                let mass_earth: f64 = rng.gen_range(30.0..400.0);
+                PlanetBuilder::gas_giant(name, orbit_au)
+                    .with_mass_and_radius(mass_earth, rng.gen_range(8.0..12.0))
+            } else {
+                let mass_earth: f64 = rng.gen_range(10.0..25.0);
+                PlanetBuilder::ice_giant(name, orbit_au)
+                    .with_mass_and_radius(mass_earth, rng.gen_range(3.5..4.5))
+            };
+
+            self.planets.push(planet.with_eccentricity(rng.gen_range(0.0..0.08)));
+        }
+
+        self
+    }
+
     pub fn with_planet(mut self, builder: PlanetBuilder) -> Self {
         self.planets.push(builder);
         self
@@ -153,6 +229,11 @@ impl SystemBuilder {
         self
     }
 
+    pub fn with_comet(mut self, builder: CometBuilder) -> Self {
+        self.comets.push(builder);
+        self
+    }
+
     pub fn with_tag(mut self, tag: impl Into<Tag>) -> Self {
         self.tags.push(tag.into());
         self
@@ -209,6 +290,13 @@ impl SystemBuilder {
             }
         }
 
+        // Build and add comets
+        for comet_builder in self.comets {
+            if let Ok(comet) = comet_builder.build(&system) {
+                system.add_comet(comet).ok();
+            }
+        }
+
         system
     }
 }
@@ -332,6 +420,17 @@ impl PlanetBuilder {
         physical.atmosphere = self.atmosphere;
         physical.surface_water_percent = self.water_percent;
 
+        // Climate from the primary star, greenhouse included, when the system has one
+        let primary_luminosity = system.stars.first().and_then(|star| match &star.physical {
+            Some(Physical::Star(star)) => Some(star.luminosity_w),
+            _ => None,
+        });
+        if let Some(luminosity_w) = primary_luminosity {
+            let temperature_k = physical.surface_temperature_at(luminosity_w, self.orbital_radius_au * Units::AU);
+            physical.surface_temperature_k = Some(temperature_k);
+            physical.habitability_score = Some(physical.assess_habitability(temperature_k));
+        }
+
         planet.physical = Some(Physical::Planet(physical));
 
         // Set orbital motion
@@ -346,21 +445,31 @@ impl PlanetBuilder {
             0.0,  // Argument of periapsis
             0.0,  // Mean anomaly at epoch
             mu,
-            OffsetDateTime::now_utc(),
+            planet.epoch,
         );
 
+        // Start the stored state on the orbit so it agrees with the motion model
+        planet.state = elements.propagate(planet.state, 0.0);
         planet.set_orbital_motion(MotionModel::Keplerian(elements));
 
-        // Add moons
+        // Spin the planet down to its orbit if the primary's tides have had time to lock it
+        let primary_tides = system.stars.first().and_then(|star| match &star.physical {
+            Some(Physical::Star(star)) => star.age_years.map(|age_years| (star.mass_kg, age_years)),
+            _ => None,
+        });
+        if let Some((star_mass_kg, age_years)) = primary_tides {
+            planet.apply_tidal_locking(star_mass_kg, age_years);
+        }
+
+        // Add moons, failing the planet if one cannot be placed (e.g. outside its Hill sphere)
         for moon_builder in self.moons {
-            if let Ok(moon) = moon_builder.build(&planet) {
-                planet.add_child(moon).ok();
-            }
+            let moon = moon_builder.build(&planet)?;
+            planet.add_child(moon).ok();
         }
 
         // Add tags
         for tag in self.tags {
-            planet.add_tag(tag);
+            planet.add_tag(tag.0);
         }
 
         Ok(planet)
@@ -411,6 +520,19 @@ impl MoonBuilder {
     }
 
     pub fn build(self, planet: &StellarBody) -> Result<StellarBody, String> {
+        // A moon outside the planet's Hill sphere would be pulled away by the star
+        if let Some(MotionModel::Keplerian(orbit)) = &planet.motion {
+            let star_mass = orbit.gravitational_param_m3s2 / 6.67430e-11;
+            let hill_m = planet.hill_sphere_radius(star_mass, orbit.semi_major_axis_m, orbit.eccentricity);
+            let orbit_m = self.orbital_radius_km * 1000.0;
+            if hill_m > 0.0 && orbit_m > hill_m {
+                return Err(format!(
+                    "{} orbits {} at {:.0} km, outside its Hill sphere ({:.0} km)",
+                    self.name, planet.name, self.orbital_radius_km, hill_m / 1000.0
+                ));
+            }
+        }
+
         let mut moon = StellarBody::new_moon(
             self.name,
             SpatialParent::Body(planet.id),
@@ -444,9 +566,10 @@ impl MoonBuilder {
             0.0,
             0.0,
             mu,
-            OffsetDateTime::now_utc(),
+            moon.epoch,
         );
 
+        moon.state = elements.propagate(moon.state, 0.0);
         moon.set_orbital_motion(MotionModel::Keplerian(elements));
 
         Ok(moon)
@@ -460,9 +583,21 @@ pub struct BeltBuilder {
     outer_radius_au: f64,
     total_mass_earth: f64,
     composition: String,
+    generated_asteroids: Option<(usize, u64)>,
+    kirkwood_giant_au: Option<f64>,
 }
 
 impl BeltBuilder {
+    // Asteroid diameters in km and the collisional-cascade size index, dN/dD ∝ D^-3.5
+    const MIN_ASTEROID_DIAMETER_KM: f64 = 1.0;
+    const MAX_ASTEROID_DIAMETER_KM: f64 = 500.0;
+    const ASTEROID_SIZE_INDEX: f64 = 3.5;
+
+    // Mean-motion resonances (asteroid orbits : giant orbits) that open Kirkwood gaps, and
+    // the gap half-width as a fraction of the resonant semi-major axis
+    const KIRKWOOD_RESONANCES: [(f64, f64); 4] = [(3.0, 1.0), (5.0, 2.0), (7.0, 3.0), (2.0, 1.0)];
+    const KIRKWOOD_GAP_HALF_WIDTH: f64 = 0.01;
+
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
@@ -470,6 +605,8 @@ impl BeltBuilder {
             outer_radius_au: 3.5,
             total_mass_earth: 0.0005,  // Asteroid belt mass
             composition: "Rocky/Metallic".to_string(),
+            generated_asteroids: None,
+            kirkwood_giant_au: None,
         }
     }
 
@@ -489,6 +626,28 @@ impl BeltBuilder {
         self
     }
 
+    // Populate the belt with `count` individual asteroids drawn reproducibly from `seed`
+    pub fn with_generated_asteroids(mut self, count: usize, seed: u64) -> Self {
+        self.generated_asteroids = Some((count, seed));
+        self
+    }
+
+    // Keep generated asteroids out of the Kirkwood gaps of a giant planet orbiting at
+    // `giant_orbit_au` (for Jupiter at 5.2 AU: 2.50, 2.82, 2.95 and 3.28 AU)
+    pub fn with_kirkwood_gaps(mut self, giant_orbit_au: f64) -> Self {
+        self.kirkwood_giant_au = Some(giant_orbit_au);
+        self
+    }
+
+    // Semi-major axes in AU of the resonances with a giant at `giant_orbit_au`; an asteroid
+    // making p orbits per q of the giant sits at a = a_giant (q / p)^(2/3)
+    pub fn kirkwood_gaps_au(giant_orbit_au: f64) -> Vec<f64> {
+        Self::KIRKWOOD_RESONANCES
+            .iter()
+            .map(|(p, q)| giant_orbit_au * (q / p).powf(2.0 / 3.0))
+            .collect()
+    }
+
     pub fn build(self, system: &StarSystem) -> Result<StellarBody, String> {
         let mut belt = StellarBody::new_belt(
             self.name,
@@ -498,7 +657,7 @@ impl BeltBuilder {
         let physical = BeltPhysical {
             inner_radius_m: self.inner_radius_au * Units::AU,
             outer_radius_m: self.outer_radius_au * Units::AU,
-            thickness_m: Some(0.1 * Units::AU),  // Typical thickness
+            thickness_m: 0.1 * Units::AU,  // Typical thickness
             total_mass_kg: Some(self.total_mass_earth * Units::EARTH_MASS),
             particle_density: Some(1000.0),  // particles per million cubic km
             average_particle_size_m: Some(1000.0),  // 1 km average
@@ -507,12 +666,111 @@ impl BeltBuilder {
 
         belt.physical = Some(Physical::Belt(physical));
 
-        // Belt orbits at average radius
-        let avg_radius = (self.inner_radius_au + self.outer_radius_au) / 2.0 * Units::AU;
-        belt.set_position(Vec3::new(avg_radius, 0.0, 0.0));
+        match self.generated_asteroids {
+            // A populated belt is centred on the star so its asteroids' orbits are too
+            Some((count, seed)) => {
+                let mu = 6.67430e-11 * system.total_mass();
+                let gaps_m: Vec<f64> = self.kirkwood_giant_au
+                    .map(Self::kirkwood_gaps_au)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|a| a * Units::AU)
+                    .collect();
+                let mut rng = StdRng::seed_from_u64(seed);
+                for n in 0..count {
+                    let name = format!("{} {}", belt.name, n + 1);
+                    let asteroid = Self::generate_asteroid(
+                        name,
+                        self.inner_radius_au * Units::AU,
+                        self.outer_radius_au * Units::AU,
+                        &gaps_m,
+                        mu,
+                        belt.epoch,
+                        &mut rng,
+                    );
+                    belt.add_child(asteroid).map_err(|e| format!("{:?}", e))?;
+                }
+            }
+            None => {
+                // Belt orbits at average radius
+                let avg_radius = (self.inner_radius_au + self.outer_radius_au) / 2.0 * Units::AU;
+                belt.set_position(Vec3::new(avg_radius, 0.0, 0.0));
+            }
+        }
 
         Ok(belt)
     }
+
+    // One asteroid on a low-eccentricity, low-inclination orbit that stays between `inner_m`
+    // and `outer_m`, spread evenly by area outside the resonances in `gaps_m`, with a
+    // power-law diameter and C/S/M-type mix
+    #[allow(clippy::too_many_arguments)]
+    fn generate_asteroid<R: Rng + ?Sized>(
+        name: String,
+        inner_m: f64,
+        outer_m: f64,
+        gaps_m: &[f64],
+        mu: f64,
+        epoch: OffsetDateTime,
+        rng: &mut R,
+    ) -> StellarBody {
+        let mut asteroid = StellarBody::new(
+            name,
+            crate::stellar_forge::bodies::BodyKind::Asteroid,
+            SpatialParent::Frame(Id::new_v4()),
+        );
+
+        // Redraw semi-major axes that land in a gap; give up if the gaps cover the belt
+        let mut a = 0.0;
+        for _ in 0..100 {
+            let u: f64 = rng.gen();
+            a = (inner_m * inner_m + u * (outer_m * outer_m - inner_m * inner_m)).sqrt();
+            let in_gap = gaps_m
+                .iter()
+                .any(|gap| (a - gap).abs() < gap * Self::KIRKWOOD_GAP_HALF_WIDTH);
+            if !in_gap {
+                break;
+            }
+        }
+        let max_e = ((a - inner_m) / a).min((outer_m - a) / a).min(0.15);
+        let elements = OrbitalElements::new(
+            a,
+            rng.gen_range(0.0..=max_e),
+            rng.gen_range(0.0..0.3),
+            rng.gen_range(0.0..std::f64::consts::TAU),
+            rng.gen_range(0.0..std::f64::consts::TAU),
+            rng.gen_range(0.0..std::f64::consts::TAU),
+            mu,
+            epoch,
+        );
+
+        // Roughly the main belt's mix of carbonaceous, stony and metallic bodies
+        let (composition, density) = match rng.gen_range(0.0..1.0) {
+            x if x < 0.75 => (AsteroidComposition::Carbonaceous, 1400.0),
+            x if x < 0.92 => (AsteroidComposition::Silicate, 2700.0),
+            _ => (AsteroidComposition::Metallic, 5300.0),
+        };
+        let diameter_km = sample_power_law(
+            rng,
+            Self::ASTEROID_SIZE_INDEX,
+            Self::MIN_ASTEROID_DIAMETER_KM,
+            Self::MAX_ASTEROID_DIAMETER_KM,
+        );
+        let radius_m = diameter_km * 500.0;
+        asteroid.physical = Some(Physical::Asteroid(AsteroidPhysical {
+            mass_kg: density * 4.0 / 3.0 * std::f64::consts::PI * radius_m.powi(3),
+            radius_m,
+            composition,
+            rotation_period_hours: Some(rng.gen_range(2.2..24.0)),
+            porosity: None,
+            resource_value: None,
+        }));
+
+        asteroid.epoch = epoch;
+        asteroid.state = elements.propagate(asteroid.state, 0.0);
+        asteroid.set_orbital_motion(MotionModel::Keplerian(elements));
+        asteroid
+    }
 }
 
 // Station builder
@@ -520,19 +778,26 @@ pub struct StationBuilder {
     name: String,
     station_type: crate::stellar_forge::physical::StationType,
     population: Option<u64>,
+    population_capacity: Option<u64>,
     docking_ports: u32,
     orbit_body_id: Option<Id>,
+    altitude_km: f64,
     position: Option<Vec3>,
 }
 
 impl StationBuilder {
+    // Low orbit, about that of the ISS
+    const DEFAULT_ALTITUDE_KM: f64 = 400.0;
+
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
             station_type: crate::stellar_forge::physical::StationType::Trading,
             population: None,
+            population_capacity: None,
             docking_ports: 4,
             orbit_body_id: None,
+            altitude_km: Self::DEFAULT_ALTITUDE_KM,
             position: None,
         }
     }
@@ -547,6 +812,11 @@ impl StationBuilder {
         self
     }
 
+    pub fn with_population_capacity(mut self, capacity: u64) -> Self {
+        self.population_capacity = Some(capacity);
+        self
+    }
+
     pub fn with_docking_ports(mut self, ports: u32) -> Self {
         self.docking_ports = ports;
         self
@@ -557,42 +827,356 @@ impl StationBuilder {
         self
     }
 
+    // Height above the surface of the body given to `orbiting`
+    pub fn at_altitude_km(mut self, altitude_km: f64) -> Self {
+        self.altitude_km = altitude_km;
+        self
+    }
+
     pub fn at_position(mut self, pos: Vec3) -> Self {
         self.position = Some(pos);
         self
     }
 
+    // An orbiting station gets a circular equatorial orbit around that body of the system
+    // Fails for a station without docking ports or with more people than it can house
     pub fn build(self, system: &StarSystem) -> Result<StellarBody, String> {
+        let mut physical = StationPhysical {
+            station_type: self.station_type,
+            docking_ports: self.docking_ports,
+            current_population: self.population,
+            ..StationPhysical::default()
+        };
+        if let Some(capacity) = self.population_capacity {
+            physical.population_capacity = Some(capacity);
+        }
+
+        if physical.docking_ports == 0 {
+            return Err(format!("{} has no docking ports", self.name));
+        }
+        if let (Some(population), Some(capacity)) = (physical.current_population, physical.population_capacity) {
+            if population > capacity {
+                return Err(format!(
+                    "{} has population {} but room for only {}",
+                    self.name, population, capacity
+                ));
+            }
+        }
+
         let mut station = StellarBody::new_station(
             self.name,
             SpatialParent::Body(system.id),
         );
 
-        let mut physical = StationPhysical::default();
-        physical.station_type = self.station_type;
-        physical.docking_ports = self.docking_ports;
-        physical.current_population = self.population;
-
         station.physical = Some(Physical::Station(physical));
 
         // Set position or orbit
         if let Some(pos) = self.position {
             station.set_position(pos);
-        } else if let Some(_body_id) = self.orbit_body_id {
-            // Set up orbital motion around specified body
-            // This would require looking up the body and setting appropriate motion
+        } else if let Some(body_id) = self.orbit_body_id {
+            let body = system.find_body_recursive(body_id)
+                .ok_or_else(|| format!("{} orbits {}, which is not in {}", station.name, body_id, system.name))?;
+            let mass = body.mass_kg()
+                .filter(|m| *m > 0.0)
+                .ok_or_else(|| format!("{} orbits {}, which has no mass", station.name, body.name))?;
+            if self.altitude_km < 0.0 {
+                return Err(format!("{} has negative altitude {} km", station.name, self.altitude_km));
+            }
+
+            let orbit_m = body.radius_m().unwrap_or(0.0) + self.altitude_km * 1000.0;
+            let elements = OrbitalElements::new(
+                orbit_m,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                6.67430e-11 * mass,
+                station.epoch,
+            );
+
+            station.spatial_parent = SpatialParent::Body(body_id);
+            station.state = elements.propagate(station.state, 0.0);
+            station.set_orbital_motion(MotionModel::Keplerian(elements));
         }
 
         Ok(station)
     }
 }
 
+// Comet builder; eccentricities of 1 or more give hyperbolic (interstellar) visitors
+pub struct CometBuilder {
+    name: String,
+    perihelion_au: f64,
+    eccentricity: f64,
+    inclination_rad: f64,
+    longitude_ascending_rad: f64,
+    argument_periapsis_rad: f64,
+    nucleus_radius_km: f64,
+    tail_direction: Option<Vec3>,
+}
+
+impl CometBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            perihelion_au: 1.0,
+            eccentricity: 0.97,  // Long-period, Halley-like
+            inclination_rad: 0.0,
+            longitude_ascending_rad: 0.0,
+            argument_periapsis_rad: 0.0,
+            nucleus_radius_km: 5.0,
+            tail_direction: None,
+        }
+    }
+
+    pub fn with_perihelion_au(mut self, perihelion_au: f64) -> Self {
+        self.perihelion_au = perihelion_au;
+        self
+    }
+
+    pub fn with_eccentricity(mut self, eccentricity: f64) -> Self {
+        self.eccentricity = eccentricity;
+        self
+    }
+
+    pub fn with_inclination(mut self, inclination_deg: f64) -> Self {
+        self.inclination_rad = inclination_deg.to_radians();
+        self
+    }
+
+    pub fn with_orientation(mut self, longitude_ascending_deg: f64, argument_periapsis_deg: f64) -> Self {
+        self.longitude_ascending_rad = longitude_ascending_deg.to_radians();
+        self.argument_periapsis_rad = argument_periapsis_deg.to_radians();
+        self
+    }
+
+    pub fn with_nucleus_radius_km(mut self, radius_km: f64) -> Self {
+        self.nucleus_radius_km = radius_km;
+        self
+    }
+
+    // Direction the tail should be drawn in; defaults to pointing away from the star
+    pub fn with_tail_direction(mut self, direction: Vec3) -> Self {
+        self.tail_direction = Some(direction);
+        self
+    }
+
+    // The comet starts at perihelion. Hyperbolic orbits store the semi-major axis as the
+    // positive magnitude q / (e - 1). The tail hint goes in metadata as "tail_direction".
+    pub fn build(self, system: &StarSystem) -> Result<StellarBody, String> {
+        if self.perihelion_au <= 0.0 {
+            return Err(format!("{} has non-positive perihelion {} AU", self.name, self.perihelion_au));
+        }
+        if self.eccentricity < 0.0 || self.eccentricity == 1.0 {
+            return Err(format!(
+                "{} has eccentricity {}; use below 1 for bound or above 1 for hyperbolic orbits",
+                self.name, self.eccentricity
+            ));
+        }
+
+        let mut comet = StellarBody::new(
+            self.name,
+            crate::stellar_forge::bodies::BodyKind::Comet,
+            SpatialParent::Body(system.id),
+        );
+
+        // Icy, porous nucleus of about comet density (600 kg/m^3)
+        let radius_m = self.nucleus_radius_km * 1000.0;
+        comet.physical = Some(Physical::Asteroid(AsteroidPhysical {
+            mass_kg: 600.0 * 4.0 / 3.0 * std::f64::consts::PI * radius_m.powi(3),
+            radius_m,
+            composition: AsteroidComposition::Icy,
+            rotation_period_hours: None,
+            porosity: Some(0.7),
+            resource_value: None,
+        }));
+
+        let perihelion_m = self.perihelion_au * Units::AU;
+        let elements = OrbitalElements::new(
+            perihelion_m / (1.0 - self.eccentricity).abs(),
+            self.eccentricity,
+            self.inclination_rad,
+            self.longitude_ascending_rad,
+            self.argument_periapsis_rad,
+            0.0,  // At perihelion at epoch
+            6.67430e-11 * system.total_mass(),
+            comet.epoch,
+        );
+
+        comet.state = elements.periapsis_state();
+        comet.set_orbital_motion(MotionModel::Keplerian(elements));
+
+        let tail = self.tail_direction
+            .filter(|d| d.norm() > 0.0)
+            .unwrap_or(comet.state.position_m)
+            .normalize();
+        comet.metadata.insert("tail_direction".to_string(), serde_json::json!([tail.x, tail.y, tail.z]));
+
+        Ok(comet)
+    }
+}
+
+// Initial mass function used to draw stellar masses for generated systems
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum InitialMassFunction {
+    Salpeter,  // Single power law, dN/dM ∝ M^-2.35
+    #[default]
+    Kroupa,    // Broken power law, α = 1.3 below 0.5 M☉ and 2.3 above
+}
+
+impl InitialMassFunction {
+    // Hydrogen-burning limit up to the most massive O stars, in solar masses
+    const MIN_MASS: f64 = 0.08;
+    const MAX_MASS: f64 = 100.0;
+    const KROUPA_BREAK: f64 = 0.5;
+
+    // Draw one stellar mass in solar masses
+    pub fn sample_mass_solar<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            InitialMassFunction::Salpeter => {
+                sample_power_law(rng, 2.35, Self::MIN_MASS, Self::MAX_MASS)
+            }
+            InitialMassFunction::Kroupa => {
+                // Segments join continuously at the break, so the upper one is scaled by m_break
+                let low = power_law_integral(1.3, Self::MIN_MASS, Self::KROUPA_BREAK);
+                let high = Self::KROUPA_BREAK * power_law_integral(2.3, Self::KROUPA_BREAK, Self::MAX_MASS);
+                if rng.gen_bool(low / (low + high)) {
+                    sample_power_law(rng, 1.3, Self::MIN_MASS, Self::KROUPA_BREAK)
+                } else {
+                    sample_power_law(rng, 2.3, Self::KROUPA_BREAK, Self::MAX_MASS)
+                }
+            }
+        }
+    }
+}
+
+// ∫ M^-α dM over [min, max]
+fn power_law_integral(alpha: f64, min: f64, max: f64) -> f64 {
+    (max.powf(1.0 - alpha) - min.powf(1.0 - alpha)) / (1.0 - alpha)
+}
+
+// Inverse-CDF draw from dN/dM ∝ M^-α on [min, max]
+fn sample_power_law<R: Rng + ?Sized>(rng: &mut R, alpha: f64, min: f64, max: f64) -> f64 {
+    let u: f64 = rng.gen();
+    let (lo, hi) = (min.powf(1.0 - alpha), max.powf(1.0 - alpha));
+    (lo + u * (hi - lo)).powf(1.0 / (1.0 - alpha))
+}
+
+// Main-sequence spectral type (e.g. "K3V") for a stellar mass in solar masses
+pub fn spectral_type_for_mass(mass_solar: f64) -> String {
+    // Lower mass bound of each class, hottest first
+    const CLASSES: [(char, f64, f64); 7] = [
+        ('O', 16.0, 100.0),
+        ('B', 2.1, 16.0),
+        ('A', 1.4, 2.1),
+        ('F', 1.04, 1.4),
+        ('G', 0.8, 1.04),
+        ('K', 0.45, 0.8),
+        ('M', 0.08, 0.45),
+    ];
+
+    let (class, min, max) = CLASSES
+        .iter()
+        .copied()
+        .find(|(_, min, _)| mass_solar >= *min)
+        .unwrap_or(CLASSES[CLASSES.len() - 1]);
+
+    // Subclass 0 at the top of the range down to 9 at the bottom, interpolated in log mass
+    let fraction = ((max.ln() - mass_solar.clamp(min, max).ln()) / (max.ln() - min.ln())).clamp(0.0, 1.0);
+    let subclass = (fraction * 9.0).round() as u8;
+    format!("{}{}V", class, subclass)
+}
+
+// Spiral disk layout for generated systems: logarithmic arms around a central bulge
+#[derive(Clone, Copy, Debug)]
+pub struct SpiralStructure {
+    pub arms: u32,
+    pub pitch_angle_deg: f64,
+    pub bulge_fraction: f64,  // Share of systems placed in the bulge
+}
+
+impl SpiralStructure {
+    // 1σ angular width of an arm
+    const ARM_SPREAD_RAD: f64 = 0.35;
+    // Bulge size and disk scale length as fractions of the disk radius
+    const BULGE_SCALE: f64 = 0.08;
+    const DISK_SCALE_LENGTH: f64 = 0.25;
+
+    // Position in light years within a disk of `radius_ly` and total `thickness_ly`
+    pub fn sample_position<R: Rng + ?Sized>(&self, rng: &mut R, radius_ly: f64, thickness_ly: f64) -> Vec3 {
+        let half_thickness = thickness_ly / 2.0;
+
+        if rng.gen_bool(self.bulge_fraction.clamp(0.0, 1.0)) {
+            let sigma = Self::BULGE_SCALE * radius_ly;
+            return Vec3::new(
+                standard_normal(rng) * sigma,
+                standard_normal(rng) * sigma,
+                (standard_normal(rng) * sigma).clamp(-half_thickness, half_thickness),
+            );
+        }
+
+        // Exponential disk: surface density ∝ e^(-r/h), so r follows a Gamma(2, h) draw
+        let scale_length = Self::DISK_SCALE_LENGTH * radius_ly;
+        let r = loop {
+            let r = -scale_length * (rng.gen::<f64>() * rng.gen::<f64>()).max(f64::MIN_POSITIVE).ln();
+            if r <= radius_ly {
+                break r;
+            }
+        };
+
+        let arm = rng.gen_range(0..self.arms.max(1));
+        let theta = self.arm_angle(r, radius_ly, arm) + standard_normal(rng) * Self::ARM_SPREAD_RAD;
+
+        // Exponential vertical profile with a scale height of a sixth of the thickness
+        let scale_height = thickness_ly / 6.0;
+        let mut z = -scale_height * rng.gen::<f64>().max(f64::MIN_POSITIVE).ln();
+        if rng.gen_bool(0.5) {
+            z = -z;
+        }
+
+        Vec3::new(r * theta.cos(), r * theta.sin(), z.clamp(-half_thickness, half_thickness))
+    }
+
+    // Angular distance (radians) from a point in the disk plane to the nearest arm
+    pub fn arm_offset_rad(&self, x_ly: f64, y_ly: f64, radius_ly: f64) -> f64 {
+        use std::f64::consts::{PI, TAU};
+
+        let r = x_ly.hypot(y_ly);
+        let theta = y_ly.atan2(x_ly);
+        (0..self.arms.max(1))
+            .map(|arm| {
+                let delta = (theta - self.arm_angle(r, radius_ly, arm)).rem_euclid(TAU);
+                if delta > PI { TAU - delta } else { delta }
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    // Azimuth of arm `arm` at radius `r`; arms wind out from the edge of the bulge
+    fn arm_angle(&self, r: f64, radius_ly: f64, arm: u32) -> f64 {
+        let start = Self::BULGE_SCALE * radius_ly;
+        let winding = r.max(f64::MIN_POSITIVE).ln() - start.ln();
+        winding / self.pitch_angle_deg.to_radians().tan()
+            + std::f64::consts::TAU * arm as f64 / self.arms.max(1) as f64
+    }
+}
+
+// Box-Muller draw from N(0, 1)
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
 // Galaxy builder for creating entire galaxies
 pub struct GalaxyBuilder {
     name: String,
     size_ly: Vec3,
+    imf: InitialMassFunction,
+    spiral: Option<SpiralStructure>,
     sectors: Vec<SectorBuilder>,
     systems: Vec<SystemBuilder>,
+    rng: Option<StdRng>,  // Set by seeded generation, reused for ids at build time
 }
 
 impl GalaxyBuilder {
@@ -600,11 +1184,27 @@ impl GalaxyBuilder {
         Self {
             name: name.into(),
             size_ly: Vec3::new(100000.0, 100000.0, 1000.0),  // Milky Way-like
+            imf: InitialMassFunction::default(),
+            spiral: None,
             sectors: Vec::new(),
             systems: Vec::new(),
+            rng: None,
         }
     }
 
+    // Mass function for stars created by `with_random_systems`
+    pub fn with_initial_mass_function(mut self, imf: InitialMassFunction) -> Self {
+        self.imf = imf;
+        self
+    }
+
+    // Place systems from `with_random_systems` along logarithmic spiral arms instead
+    // of uniformly through the box; the disk spans the smaller of the x/y extents
+    pub fn with_spiral_structure(mut self, arms: u32, pitch_angle_deg: f64, bulge_fraction: f64) -> Self {
+        self.spiral = Some(SpiralStructure { arms, pitch_angle_deg, bulge_fraction });
+        self
+    }
+
     pub fn with_size(mut self, x_ly: f64, y_ly: f64, z_ly: f64) -> Self {
         self.size_ly = Vec3::new(x_ly, y_ly, z_ly);
         self
@@ -620,25 +1220,40 @@ impl GalaxyBuilder {
         self
     }
 
+    // Generated systems, their planets and every id come from RNGs seeded with `seed`,
+    // so the same seeds always build the same galaxy. Each call draws its systems from its
+    // own seed; the ids drawn at build time mix in the streams of every call.
     pub fn with_random_systems(mut self, count: usize, seed: u64) -> Self {
-        use rand::SeedableRng;
-        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut rng = StdRng::seed_from_u64(seed);
 
         for i in 0..count {
-            let x = rng.gen_range(-self.size_ly.x / 2.0..self.size_ly.x / 2.0);
-            let y = rng.gen_range(-self.size_ly.y / 2.0..self.size_ly.y / 2.0);
-            let z = rng.gen_range(-self.size_ly.z / 2.0..self.size_ly.z / 2.0);
+            let position = match &self.spiral {
+                Some(spiral) => {
+                    let radius_ly = self.size_ly.x.min(self.size_ly.y) / 2.0;
+                    spiral.sample_position(&mut rng, radius_ly, self.size_ly.z)
+                }
+                None => Vec3::new(
+                    rng.gen_range(-self.size_ly.x / 2.0..self.size_ly.x / 2.0),
+                    rng.gen_range(-self.size_ly.y / 2.0..self.size_ly.y / 2.0),
+                    rng.gen_range(-self.size_ly.z / 2.0..self.size_ly.z / 2.0),
+                ),
+            };
 
-            let spectral_types = ["M5V", "M0V", "K5V", "K0V", "G5V", "G0V", "F5V", "F0V", "A5V", "A0V"];
-            let spectral = spectral_types[rng.gen_range(0..spectral_types.len())];
+            let spectral = spectral_type_for_mass(self.imf.sample_mass_solar(&mut rng));
 
+            let planet_count = rng.gen_range(0..=8);
             let system = SystemBuilder::new(format!("System-{}", i))
-                .at_position(x, y, z)
-                .with_star(spectral);
+                .at_position(position.x, position.y, position.z)
+                .with_star(spectral)
+                .with_procedural_planets_rng(planet_count, &mut rng);
 
             self.systems.push(system);
         }
 
+        self.rng = Some(match self.rng.take() {
+            Some(mut ids) => StdRng::seed_from_u64(ids.gen::<u64>() ^ rng.gen::<u64>()),
+            None => rng,
+        });
         self
     }
 
@@ -652,10 +1267,22 @@ impl GalaxyBuilder {
             galaxy.add_star_system(system).ok();
         }
 
+        if let Some(mut rng) = self.rng {
+            galaxy.reseed_ids(&mut rng, GENERATION_EPOCH);
+        }
+
         galaxy
     }
 }
 
+// Exoplanet-style designation: b, c, ..., z, then plain numbers
+fn planet_letter(index: usize) -> String {
+    match b"bcdefghijklmnopqrstuvwxyz".get(index) {
+        Some(letter) => (*letter as char).to_string(),
+        None => (index + 1).to_string(),
+    }
+}
+
 // Sector builder
 pub struct SectorBuilder {
     name: String,
@@ -677,6 +1304,15 @@ impl SectorBuilder {
         self.max_bound = max;
         self
     }
+
+    pub fn build(self) -> Sector {
+        Sector {
+            id: Id::new_v4(),
+            name: self.name,
+            bounds: Bounds { min: self.min_bound, max: self.max_bound, shape: BoundShape::Box },
+            system_ids: Vec::new(),
+        }
+    }
 }
 
 // Helper functions for common system configurations
@@ -690,6 +1326,7 @@ pub fn create_sol_like_system() -> SystemBuilder {
         .with_planet(
             PlanetBuilder::terrestrial("Venus", 0.723)
                 .with_mass_and_radius(0.815, 0.949)
+                .with_atmosphere(Atmosphere::venus_like())
                 .with_water(0.0)
         )
         .with_planet(
@@ -727,4 +1364,346 @@ pub fn create_sol_like_system() -> SystemBuilder {
         .with_planet(
             PlanetBuilder::ice_giant("Neptune", 30.0)
         )
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_procedural_planets_follow_frost_line() {
+        let system = SystemBuilder::new("Proc")
+            .with_star("G2V")
+            .with_procedural_planets(8, 7)
+            .build();
+        let frost_line_au = system.frost_line_au().unwrap();
+        assert_eq!(system.planets.len(), 8);
+
+        let mut last_orbit_au = 0.0;
+        for planet in &system.planets {
+            let Some(MotionModel::Keplerian(elements)) = &planet.motion else {
+                panic!("{} should have a Keplerian orbit", planet.name);
+            };
+            let orbit_au = elements.semi_major_axis_m / Units::AU;
+            assert!(orbit_au > last_orbit_au, "{} is out of order", planet.name);
+            last_orbit_au = orbit_au;
+
+            let Some(Physical::Planet(physical)) = &planet.physical else {
+                panic!("{} should have planet physicals", planet.name);
+            };
+            if orbit_au < frost_line_au {
+                assert!(
+                    matches!(physical.composition, PlanetaryComposition::Terrestrial),
+                    "{} at {} AU should be terrestrial", planet.name, orbit_au
+                );
+            } else {
+                assert!(
+                    matches!(physical.composition, PlanetaryComposition::GasGiant | PlanetaryComposition::IceGiant),
+                    "{} at {} AU should be a giant", planet.name, orbit_au
+                );
+            }
+        }
+
+        // The ladder spans both sides of the frost line
+        assert!(system.planets.iter().any(|p| matches!(
+            &p.physical, Some(Physical::Planet(p)) if matches!(p.composition, PlanetaryComposition::Terrestrial)
+        )));
+        assert!(last_orbit_au > frost_line_au);
+        assert_eq!(system.planets[0].name, "Proc b");
+    }
+
+    #[test]
+    fn test_mass_function_favors_m_dwarfs() {
+        for imf in [InitialMassFunction::Kroupa, InitialMassFunction::Salpeter] {
+            let mut rng = StdRng::seed_from_u64(1);
+            let mut counts = HashMap::new();
+            for _ in 0..10_000 {
+                let spectral = spectral_type_for_mass(imf.sample_mass_solar(&mut rng));
+                *counts.entry(spectral.chars().next().unwrap()).or_insert(0usize) += 1;
+            }
+
+            let m = counts.get(&'M').copied().unwrap_or(0);
+            let a = counts.get(&'A').copied().unwrap_or(0);
+            let o = counts.get(&'O').copied().unwrap_or(0);
+            assert!(m > 20 * a.max(1), "{:?}: {} M vs {} A", imf, m, a);
+            assert!(o < 50, "{:?}: {} O stars", imf, o);
+        }
+    }
+
+    #[test]
+    fn test_spectral_type_for_mass() {
+        assert_eq!(spectral_type_for_mass(1.2).chars().next(), Some('F'));
+        assert_eq!(spectral_type_for_mass(1.0).chars().next(), Some('G'));
+        assert_eq!(spectral_type_for_mass(0.08), "M9V");
+        assert_eq!(spectral_type_for_mass(150.0), "O0V");
+        assert_eq!(spectral_type_for_mass(0.01), "M9V");
+    }
+
+    #[test]
+    fn test_spiral_structure_density() {
+        let spiral = SpiralStructure { arms: 2, pitch_angle_deg: 12.0, bulge_fraction: 0.15 };
+        let radius = 50_000.0;
+        let mut rng = StdRng::seed_from_u64(3);
+        let points: Vec<Vec3> = (0..20_000)
+            .map(|_| spiral.sample_position(&mut rng, radius, 1000.0))
+            .collect();
+
+        assert!(points.iter().all(|p| p.z.abs() <= 500.0));
+
+        // Surface density per unit area falls off with radius
+        let density = |inner: f64, outer: f64| {
+            let count = points.iter().filter(|p| {
+                let r = p.x.hypot(p.y) / radius;
+                r >= inner && r < outer
+            }).count();
+            count as f64 / (outer * outer - inner * inner)
+        };
+        assert!(density(0.1, 0.3) > 2.0 * density(0.5, 0.7));
+        assert!(density(0.5, 0.7) > density(0.8, 1.0));
+
+        // Within the disk, arm regions (the half of each annulus nearest an arm) hold most stars
+        let disk: Vec<&Vec3> = points.iter()
+            .filter(|p| (0.3..0.8).contains(&(p.x.hypot(p.y) / radius)))
+            .collect();
+        let quarter_turn = std::f64::consts::PI / (2.0 * spiral.arms as f64);
+        let in_arms = disk.iter()
+            .filter(|p| spiral.arm_offset_rad(p.x, p.y, radius) < quarter_turn)
+            .count();
+        assert!(in_arms as f64 > 0.75 * disk.len() as f64, "{} of {} in arms", in_arms, disk.len());
+    }
+
+    #[test]
+    fn test_seeded_galaxy_is_reproducible() {
+        let generate = |seed| {
+            let galaxy = GalaxyBuilder::new("Seeded").with_random_systems(20, seed).build();
+            serde_json::to_string(&galaxy).unwrap()
+        };
+
+        let first = generate(42);
+        assert_eq!(first, generate(42));
+        assert_ne!(first, generate(43));
+
+        // A second call generates from its own seed rather than carrying on the first one's stream
+        let positions = |second_seed| {
+            let galaxy = GalaxyBuilder::new("Twice")
+                .with_random_systems(5, 42)
+                .with_random_systems(5, second_seed)
+                .build();
+            galaxy.star_systems.iter().map(|s| s.galactic_position()).collect::<Vec<_>>()
+        };
+        let (a, b) = (positions(7), positions(8));
+        assert_eq!(a[..5], b[..5]);
+        assert_ne!(a[5..], b[5..]);
+        assert_eq!(a, positions(7));
+    }
+
+    #[test]
+    fn test_moons_must_orbit_inside_hill_sphere() {
+        let system = SystemBuilder::new("Sol").with_star("G2V").build();
+        let earth = PlanetBuilder::terrestrial("Earth", 1.0).build(&system).unwrap();
+
+        assert!(MoonBuilder::new("Moon").build(&earth).is_ok());
+
+        // Earth's Hill sphere is about 1.5 million km
+        let err = MoonBuilder::new("Runaway").at_orbit_km(15.0e6).build(&earth).unwrap_err();
+        assert!(err.contains("outside its Hill sphere"), "{}", err);
+
+        // The planet builder reports the rejected moon instead of dropping it
+        let err = PlanetBuilder::terrestrial("Earth", 1.0)
+            .with_moon(MoonBuilder::new("Moon"))
+            .with_moon(MoonBuilder::new("Runaway").at_orbit_km(15.0e6))
+            .build(&system)
+            .unwrap_err();
+        assert!(err.starts_with("Runaway orbits Earth"), "{}", err);
+    }
+
+    #[test]
+    fn test_close_in_planets_are_built_tidally_locked() {
+        use crate::stellar_forge::core::Orbital;
+        let red_dwarf = SystemBuilder::new("Proxima").with_star("M4V").build();
+        let close_in = PlanetBuilder::terrestrial("Proxima b", 0.05).build(&red_dwarf).unwrap();
+        let orbit_hours = close_in.orbital_period_s().unwrap() / 3600.0;
+        assert_eq!(close_in.rotation_period_hours, Some(orbit_hours));
+
+        let sol = SystemBuilder::new("Sol").with_star("G2V").build();
+        let earth = PlanetBuilder::terrestrial("Earth", 1.0).build(&sol).unwrap();
+        let year_hours = earth.orbital_period_s().unwrap() / 3600.0;
+        assert_ne!(earth.rotation_period_hours, Some(year_hours));
+    }
+
+    #[test]
+    fn test_station_orbits_named_body() {
+        use crate::stellar_forge::core::Orbital;
+
+        let system = SystemBuilder::new("Sol")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Earth", 1.0))
+            .build();
+        let earth_id = system.planets[0].id;
+
+        let station = StationBuilder::new("Dock").orbiting(earth_id).build(&system).unwrap();
+        assert!(matches!(station.motion, Some(MotionModel::Keplerian(_))));
+        assert!(matches!(station.spatial_parent, SpatialParent::Body(id) if id == earth_id));
+        // 400 km up, like the ISS: about 92 minutes
+        let period_min = station.orbital_period_s().unwrap() / 60.0;
+        assert!((period_min - 92.5).abs() < 1.0, "period {} min", period_min);
+
+        let high = StationBuilder::new("High").orbiting(earth_id).at_altitude_km(35786.0).build(&system).unwrap();
+        let period_h = high.orbital_period_s().unwrap() / 3600.0;
+        assert!((period_h - 23.93).abs() < 0.1, "period {} h", period_h);
+
+        assert!(StationBuilder::new("Lost").orbiting(Id::new_v4()).build(&system).is_err());
+
+        let mut system = system;
+        system.add_station(station).unwrap();
+        assert!(matches!(system.stations[0].spatial_parent, SpatialParent::Body(id) if id == earth_id));
+    }
+
+    #[test]
+    fn test_station_rejects_over_capacity_population() {
+        let system = SystemBuilder::new("Sol").with_star("G2V").build();
+
+        // The default capacity is 10,000
+        let err = StationBuilder::new("Crowded").with_population(25_000).build(&system).unwrap_err();
+        assert!(err.contains("population 25000 but room for only 10000"), "{}", err);
+        assert!(StationBuilder::new("Roomy")
+            .with_population(25_000)
+            .with_population_capacity(30_000)
+            .build(&system)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_station_needs_docking_ports() {
+        let system = SystemBuilder::new("Sol").with_star("G2V").build();
+
+        let err = StationBuilder::new("Sealed").with_docking_ports(0).build(&system).unwrap_err();
+        assert!(err.contains("no docking ports"), "{}", err);
+        assert!(StationBuilder::new("Open").with_docking_ports(1).build(&system).is_ok());
+    }
+
+    #[test]
+    fn test_brown_dwarf_companion_is_substellar() {
+        let system = SystemBuilder::new("Luhman")
+            .with_star("G2V")
+            .with_brown_dwarf("T5")
+            .build();
+
+        assert_eq!(system.stars.len(), 2);
+        assert_eq!(system.stars[1].name, "Luhman B");
+        let dwarf_mass = system.stars[1].mass_kg().unwrap();
+        assert!(dwarf_mass < HYDROGEN_BURNING_LIMIT_SOLAR * Units::SOLAR_MASS);
+        assert!(system.stars[0].mass_kg().unwrap() > HYDROGEN_BURNING_LIMIT_SOLAR * Units::SOLAR_MASS);
+
+        // A stellar spectral type can't sneak in as a brown dwarf
+        let coerced = SystemBuilder::new("Odd").with_star("K1V").with_brown_dwarf("G2V").build();
+        assert!(coerced.stars[1].mass_kg().unwrap() < HYDROGEN_BURNING_LIMIT_SOLAR * Units::SOLAR_MASS);
+    }
+
+    #[test]
+    fn test_hyperbolic_comet() {
+        use crate::stellar_forge::core::Spatial;
+
+        let system = SystemBuilder::new("Sol")
+            .with_star("G2V")
+            .with_comet(
+                CometBuilder::new("Oumuamua")
+                    .with_perihelion_au(0.25)
+                    .with_eccentricity(1.2)
+                    .with_inclination(123.0),
+            )
+            .build();
+
+        assert_eq!(system.other_bodies.len(), 1);
+        let comet = &system.other_bodies[0];
+        assert_eq!(comet.kind, crate::stellar_forge::bodies::BodyKind::Comet);
+        assert!(comet.validate_orbit().is_ok());
+        let Some(MotionModel::Keplerian(elements)) = &comet.motion else {
+            panic!("comet has no Keplerian orbit");
+        };
+        assert!(elements.is_hyperbolic());
+        assert!((elements.periapsis() / Units::AU - 0.25).abs() < 1e-9);
+        assert!((comet.state.position_m.norm() / Units::AU - 0.25).abs() < 1e-9);
+
+        // Faster than escape speed at perihelion, tail pointing away from the star
+        let escape = (2.0 * elements.gravitational_param_m3s2 / comet.state.position_m.norm()).sqrt();
+        assert!(comet.state.velocity_mps.norm() > escape);
+
+        // Propagation follows the hyperbola: perihelion at the epoch, receding afterwards
+        let at_epoch = comet.state_at(comet.epoch);
+        assert!((at_epoch.position_m - comet.state.position_m).norm() < 1.0, "{:?}", at_epoch);
+        assert!((at_epoch.velocity_mps - comet.state.velocity_mps).norm() < 1e-6, "{:?}", at_epoch);
+        let later = comet.state_at(comet.epoch + time::Duration::days(365));
+        let r_later = later.position_m.norm();
+        assert!(r_later.is_finite() && r_later > 2.0 * Units::AU);
+        // Energy stays at mu / 2|a| above zero
+        let energy = later.velocity_mps.norm_squared() / 2.0 - elements.gravitational_param_m3s2 / r_later;
+        let expected = elements.gravitational_param_m3s2 / (2.0 * elements.semi_major_axis_m.abs());
+        assert!((energy / expected - 1.0).abs() < 1e-6);
+        let tail: Vec<f64> = serde_json::from_value(comet.metadata["tail_direction"].clone()).unwrap();
+        let tail = Vec3::new(tail[0], tail[1], tail[2]);
+        assert!(tail.dot(&comet.state.position_m) > 0.0);
+
+        assert!(CometBuilder::new("Parabolic").with_eccentricity(1.0).build(&system).is_err());
+    }
+
+    #[test]
+    fn test_generated_asteroids_stay_in_belt() {
+        let system = SystemBuilder::new("Sol")
+            .with_star("G2V")
+            .with_asteroid_belt(BeltBuilder::new("Main Belt").between(2.1, 3.3).with_generated_asteroids(200, 11))
+            .build();
+
+        let belt = &system.belts[0];
+        assert_eq!(belt.children.len(), 200);
+        let (inner, outer) = (2.1 * Units::AU, 3.3 * Units::AU);
+        let mut small = 0;
+        for asteroid in &belt.children {
+            assert_eq!(asteroid.kind, crate::stellar_forge::bodies::BodyKind::Asteroid);
+            let r = asteroid.state.position_m.norm();
+            assert!(r >= inner * (1.0 - 1e-9) && r <= outer * (1.0 + 1e-9), "{} at {} AU", asteroid.name, r / Units::AU);
+            let Some(Physical::Asteroid(physical)) = &asteroid.physical else {
+                panic!("{} has no asteroid physical data", asteroid.name);
+            };
+            if physical.radius_m < 1000.0 {
+                small += 1;
+            }
+        }
+        // The power law makes most of them small
+        assert!(small > 100, "only {} asteroids under 2 km across", small);
+
+        let again = BeltBuilder::new("Main Belt").between(2.1, 3.3).with_generated_asteroids(200, 11).build(&system).unwrap();
+        assert_eq!(again.children[7].state.position_m, belt.children[7].state.position_m);
+    }
+
+    #[test]
+    fn test_kirkwood_gaps_deplete_resonances() {
+        let system = SystemBuilder::new("Sol").with_star("G2V").build();
+        let gaps = BeltBuilder::kirkwood_gaps_au(5.2);
+        assert!((gaps[0] - 2.50).abs() < 0.01 && (gaps[3] - 3.28).abs() < 0.01, "{:?}", gaps);
+
+        let near_gap = |belt: &StellarBody, gap_au: f64| {
+            belt.children
+                .iter()
+                .filter(|a| match &a.motion {
+                    Some(MotionModel::Keplerian(e)) => (e.semi_major_axis_m / Units::AU - gap_au).abs() < 0.02,
+                    _ => false,
+                })
+                .count()
+        };
+        let belt = |gapped: bool| {
+            let builder = BeltBuilder::new("Main Belt").between(2.1, 3.5).with_generated_asteroids(3000, 5);
+            let builder = if gapped { builder.with_kirkwood_gaps(5.2) } else { builder };
+            builder.build(&system).unwrap()
+        };
+        let (smooth, gapped) = (belt(false), belt(true));
+
+        assert_eq!(gapped.children.len(), 3000);
+        for gap_au in [gaps[0], gaps[3]] {
+            let (before, after) = (near_gap(&smooth, gap_au), near_gap(&gapped, gap_au));
+            assert!(before > 20, "only {} asteroids near {:.2} AU without gaps", before, gap_au);
+            assert!(after * 4 < before, "{} asteroids near {:.2} AU with gaps, {} without", after, gap_au, before);
+        }
+    }
+}
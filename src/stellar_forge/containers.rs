@@ -1,9 +1,10 @@
 // Container hierarchy and management for StellarForge
 
 use crate::stellar_forge::core::{
-    Id, State, Vec3, Identified, Container, ContainerError, Spatial,
+    Id, State, Vec3, Identified, Container, ContainerError, Spatial, Massive, seeded_id,
 };
 use crate::stellar_forge::bodies::{StellarBody, BodyKind, SpatialParent};
+use crate::stellar_forge::coordinates::{CoordinateTransform, EquatorialCoordinates};
 use crate::stellar_forge::frames::{Frame, FrameHierarchy, FrameKind};
 use crate::stellar_forge::motion::MotionModel;
 use serde::{Deserialize, Serialize};
@@ -46,6 +47,85 @@ pub enum CoordinateReference {
     Relative { parent_id: Id, offset: Vec3 }, // Relative to parent
 }
 
+// What to do with an incoming system that conflicts with an existing one in Galaxy::merge
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    KeepExisting,  // Drop the incoming system
+    Replace,       // Incoming system replaces the existing one
+    Rename,        // Keep both, giving the incoming one a fresh name (and id if they clash)
+}
+
+impl std::str::FromStr for MergePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "keep" | "keep-existing" | "skip" => Ok(MergePolicy::KeepExisting),
+            "replace" | "overwrite" => Ok(MergePolicy::Replace),
+            "rename" => Ok(MergePolicy::Rename),
+            other => Err(format!("Unknown merge policy '{}' (expected keep, replace or rename)", other)),
+        }
+    }
+}
+
+// Counts of what Galaxy::merge did with the incoming systems, plus anything it could not add
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    pub added: usize,
+    pub replaced: usize,
+    pub renamed: usize,
+    pub skipped: usize,
+    pub failed: Vec<String>,
+}
+
+impl std::ops::AddAssign for MergeSummary {
+    fn add_assign(&mut self, other: Self) {
+        self.added += other.added;
+        self.replaced += other.replaced;
+        self.renamed += other.renamed;
+        self.skipped += other.skipped;
+        self.failed.extend(other.failed);
+    }
+}
+
+// Which published bounds StarSystem::habitable_zone_with_model uses
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HabitableZoneModel {
+    #[default]
+    Simple,                 // Fixed flux bounds 0.95-1.37 AU at one solar luminosity
+    Kasting,                // Water loss to maximum greenhouse (Kasting et al. 1993), 0.95-1.67 AU
+    KopparapuConservative,  // Runaway to maximum greenhouse (Kopparapu et al. 2014)
+    KopparapuOptimistic,    // Recent Venus to early Mars (Kopparapu et al. 2014)
+}
+
+impl HabitableZoneModel {
+    // Kopparapu et al. (2014) effective-flux fits for one Earth mass: S_eff at the Sun's
+    // temperature, then coefficients of (T_eff - 5780 K) to the 1st..4th power
+    const RECENT_VENUS: [f64; 5] = [1.776, 2.136e-4, 2.533e-8, -1.332e-11, -3.097e-15];
+    const RUNAWAY_GREENHOUSE: [f64; 5] = [1.107, 1.332e-4, 1.580e-8, -8.308e-12, -1.931e-15];
+    const MAXIMUM_GREENHOUSE: [f64; 5] = [0.356, 6.171e-5, 1.698e-9, -3.198e-12, -5.575e-16];
+    const EARLY_MARS: [f64; 5] = [0.320, 5.547e-5, 1.526e-9, -2.874e-12, -5.011e-16];
+
+    // Inner and outer edges in AU around a star of `luminosity_solar` and `temperature_k`
+    pub fn bounds_au(self, luminosity_solar: f64, temperature_k: f64) -> (f64, f64) {
+        let flux_limits = |inner: [f64; 5], outer: [f64; 5]| {
+            // The fits hold from 2600 to 7200 K
+            let t = temperature_k.clamp(2600.0, 7200.0) - 5780.0;
+            let s_eff = |c: [f64; 5]| c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * c[4])));
+            ((luminosity_solar / s_eff(inner)).sqrt(), (luminosity_solar / s_eff(outer)).sqrt())
+        };
+
+        match self {
+            HabitableZoneModel::Simple => (0.95 * luminosity_solar.sqrt(), 1.37 * luminosity_solar.sqrt()),
+            HabitableZoneModel::Kasting => (0.95 * luminosity_solar.sqrt(), 1.67 * luminosity_solar.sqrt()),
+            HabitableZoneModel::KopparapuConservative => {
+                flux_limits(Self::RUNAWAY_GREENHOUSE, Self::MAXIMUM_GREENHOUSE)
+            }
+            HabitableZoneModel::KopparapuOptimistic => flux_limits(Self::RECENT_VENUS, Self::EARLY_MARS),
+        }
+    }
+}
+
 // Spatial bounds for containers
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Bounds {
@@ -63,6 +143,139 @@ pub enum BoundShape {
     Custom(String),
 }
 
+impl Bounds {
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    // Round shapes are centered on the box and aligned with its z axis; boundaries count as inside.
+    // Custom shapes have no geometry to test against and contain nothing.
+    pub fn contains(&self, p: Vec3) -> bool {
+        let offset = p - self.center();
+        match &self.shape {
+            BoundShape::Box => (0..3).all(|i| p[i] >= self.min[i] && p[i] <= self.max[i]),
+            BoundShape::Sphere { radius } => offset.norm() <= *radius,
+            BoundShape::Cylinder { radius, height } => {
+                offset.x.hypot(offset.y) <= *radius && offset.z.abs() <= height / 2.0
+            }
+            BoundShape::Torus { major_radius, minor_radius } => {
+                let ring = offset.x.hypot(offset.y) - major_radius;
+                ring.hypot(offset.z) <= *minor_radius
+            }
+            BoundShape::Custom(_) => false,
+        }
+    }
+
+    pub fn volume(&self) -> f64 {
+        use std::f64::consts::PI;
+        match &self.shape {
+            BoundShape::Box => {
+                let size = self.max - self.min;
+                size.x * size.y * size.z
+            }
+            BoundShape::Sphere { radius } => 4.0 / 3.0 * PI * radius.powi(3),
+            BoundShape::Cylinder { radius, height } => PI * radius * radius * height,
+            BoundShape::Torus { major_radius, minor_radius } => {
+                2.0 * PI * PI * major_radius * minor_radius * minor_radius
+            }
+            BoundShape::Custom(_) => 0.0,
+        }
+    }
+
+    // Exact for any pairing with a sphere and among boxes and cylinders; a torus paired
+    // with a box, cylinder or torus is approximated by the cylinder enclosing it
+    pub fn intersects(&self, other: &Bounds) -> bool {
+        if let BoundShape::Sphere { radius } = self.shape {
+            let center = self.center();
+            return other.closest_point(center).is_some_and(|q| (q - center).norm() <= radius);
+        }
+        if let BoundShape::Sphere { .. } = other.shape {
+            return other.intersects(self);
+        }
+
+        let (Some((a, a_z)), Some((b, b_z))) = (self.footprint(), other.footprint()) else {
+            return false;
+        };
+        if a_z.0 > b_z.1 || b_z.0 > a_z.1 {
+            return false;
+        }
+        match (a, b) {
+            (Footprint::Rect { min: a_min, max: a_max }, Footprint::Rect { min: b_min, max: b_max }) => {
+                a_min.0 <= b_max.0 && b_min.0 <= a_max.0 && a_min.1 <= b_max.1 && b_min.1 <= a_max.1
+            }
+            (Footprint::Circle { center, radius }, Footprint::Rect { min, max })
+            | (Footprint::Rect { min, max }, Footprint::Circle { center, radius }) => {
+                let dx = center.0 - center.0.clamp(min.0, max.0);
+                let dy = center.1 - center.1.clamp(min.1, max.1);
+                dx.hypot(dy) <= radius
+            }
+            (Footprint::Circle { center: a_c, radius: a_r }, Footprint::Circle { center: b_c, radius: b_r }) => {
+                (a_c.0 - b_c.0).hypot(a_c.1 - b_c.1) <= a_r + b_r
+            }
+        }
+    }
+
+    // Nearest point of the solid shape to `p`, or `p` itself when inside
+    fn closest_point(&self, p: Vec3) -> Option<Vec3> {
+        let center = self.center();
+        let offset = p - center;
+        match &self.shape {
+            BoundShape::Box => Some(p.sup(&self.min).inf(&self.max)),
+            BoundShape::Sphere { radius } => {
+                let distance = offset.norm();
+                Some(if distance <= *radius { p } else { center + offset * (radius / distance) })
+            }
+            BoundShape::Cylinder { radius, height } => {
+                let radial = offset.x.hypot(offset.y);
+                let scale = if radial > *radius { radius / radial } else { 1.0 };
+                let half = height / 2.0;
+                Some(center + Vec3::new(offset.x * scale, offset.y * scale, offset.z.clamp(-half, half)))
+            }
+            BoundShape::Torus { major_radius, minor_radius } => {
+                if self.contains(p) {
+                    return Some(p);
+                }
+                let radial = offset.x.hypot(offset.y);
+                let direction = if radial > 0.0 {
+                    Vec3::new(offset.x / radial, offset.y / radial, 0.0)
+                } else {
+                    Vec3::x()
+                };
+                let ring = center + direction * *major_radius;
+                Some(ring + (p - ring).normalize() * *minor_radius)
+            }
+            BoundShape::Custom(_) => None,
+        }
+    }
+
+    // Cross-section in the x-y plane plus the z extent, for the non-spherical shapes
+    fn footprint(&self) -> Option<(Footprint, (f64, f64))> {
+        let center = self.center();
+        let circle = |radius: f64, half_height: f64| {
+            Some((
+                Footprint::Circle { center: (center.x, center.y), radius },
+                (center.z - half_height, center.z + half_height),
+            ))
+        };
+        match &self.shape {
+            BoundShape::Box => Some((
+                Footprint::Rect { min: (self.min.x, self.min.y), max: (self.max.x, self.max.y) },
+                (self.min.z, self.max.z),
+            )),
+            BoundShape::Cylinder { radius, height } => circle(*radius, height / 2.0),
+            BoundShape::Torus { major_radius, minor_radius } => {
+                circle(major_radius + minor_radius, *minor_radius)
+            }
+            BoundShape::Sphere { .. } | BoundShape::Custom(_) => None,
+        }
+    }
+}
+
+enum Footprint {
+    Rect { min: (f64, f64), max: (f64, f64) },
+    Circle { center: (f64, f64), radius: f64 },
+}
+
 // The Galaxy - the ultimate container
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Galaxy {
@@ -109,6 +322,14 @@ impl Galaxy {
         }
     }
 
+    // Root galactic frame created in `new`
+    pub fn galactic_frame_id(&self) -> Id {
+        self.frame_hierarchy
+            .find_frame_by_kind(FrameKind::GalacticIAU)
+            .map(|f| f.id)
+            .expect("galaxy always has a galactic frame")
+    }
+
     pub fn add_sector(&mut self, sector: Sector) -> Result<(), ContainerError> {
         if self.sectors.iter().any(|s| s.id == sector.id) {
             return Err(ContainerError::ChildAlreadyExists(sector.id));
@@ -123,13 +344,14 @@ impl Galaxy {
             return Err(ContainerError::ChildAlreadyExists(system.id));
         }
 
-        // Add system frame to hierarchy
-        let system_frame = Frame::new_barycentric(
+        // Add system frame to hierarchy, keyed by the system's own frame id
+        let mut system_frame = Frame::new_barycentric(
             format!("{} Barycenter", system.name),
-            self.frame_hierarchy.frames.values().next().unwrap().id,  // Galactic frame
+            self.galactic_frame_id(),
             system.galactic_position(),
             OffsetDateTime::now_utc(),
         );
+        system_frame.id = system.frame_id;
         self.frame_hierarchy.add_frame(system_frame).ok();
 
         self.star_systems.push(system);
@@ -138,10 +360,91 @@ impl Galaxy {
         Ok(())
     }
 
+    // Remove a system along with its frame and every frame nested under it
+    pub fn remove_system(&mut self, id: Id) -> Result<StarSystem, ContainerError> {
+        let index = self
+            .star_systems
+            .iter()
+            .position(|s| s.id == id)
+            .ok_or(ContainerError::ChildNotFound(id))?;
+        let system = self.star_systems.remove(index);
+
+        self.frame_hierarchy.remove_subtree(system.frame_id);
+        for sector in &mut self.sectors {
+            sector.system_ids.retain(|system_id| *system_id != id);
+        }
+        self.metadata.total_stars = self.metadata.total_stars.saturating_sub(1);
+        self.metadata.last_modified = OffsetDateTime::now_utc();
+        Ok(system)
+    }
+
+    // Fold another galaxy's systems, sectors and rogue objects into this one. A system
+    // conflicts with an existing one when it has the same id or the same name.
+    pub fn merge(&mut self, other: Galaxy, policy: MergePolicy) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        for mut system in other.star_systems {
+            let existing = self.star_systems.iter()
+                .find(|s| s.id == system.id || s.name.eq_ignore_ascii_case(&system.name))
+                .map(|s| (s.id, s.id == system.id));
+
+            let name = system.name.clone();
+            match (existing, policy) {
+                (None, _) => summary.added += 1,
+                (Some(_), MergePolicy::KeepExisting) => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                (Some((existing_id, _)), MergePolicy::Replace) => {
+                    if let Err(err) = self.remove_system(existing_id) {
+                        summary.failed.push(format!("{}: {}", name, err));
+                        continue;
+                    }
+                    summary.replaced += 1;
+                }
+                (Some((_, same_id)), MergePolicy::Rename) => {
+                    if same_id {
+                        system.id = Id::new_v4();
+                        system.frame_id = Id::new_v4();
+                    }
+                    let mut n = 2;
+                    while self.star_systems.iter().any(|s| s.name.eq_ignore_ascii_case(&system.name)) {
+                        system.name = format!("{} ({})", name, n);
+                        n += 1;
+                    }
+                    // A clash on id alone only needs fresh ids, the name is kept
+                    if system.name == name {
+                        summary.added += 1;
+                    } else {
+                        summary.renamed += 1;
+                    }
+                }
+            }
+            if let Err(err) = self.add_star_system(system) {
+                summary.failed.push(format!("{}: {}", name, err));
+            }
+        }
+
+        for sector in other.sectors {
+            if !self.sectors.iter().any(|s| s.id == sector.id) {
+                self.sectors.push(sector);
+            }
+        }
+        for object in other.rogue_objects {
+            if !self.rogue_objects.iter().any(|o| o.id == object.id) {
+                let name = object.name.clone();
+                if let Err(err) = self.add_rogue_object(object) {
+                    summary.failed.push(format!("{}: {}", name, err));
+                }
+            }
+        }
+
+        self.metadata.last_modified = OffsetDateTime::now_utc();
+        summary
+    }
+
     pub fn add_rogue_object(&mut self, mut object: StellarBody) -> Result<(), ContainerError> {
-        object.spatial_parent = SpatialParent::Frame(
-            self.frame_hierarchy.frames.values().next().unwrap().id
-        );
+        object.spatial_parent = SpatialParent::Frame(self.galactic_frame_id());
         self.rogue_objects.push(object);
         self.metadata.last_modified = OffsetDateTime::now_utc();
         Ok(())
@@ -155,6 +458,29 @@ impl Galaxy {
         self.star_systems.iter_mut().find(|s| s.id == id)
     }
 
+    // Case-insensitive lookup by system name
+    pub fn find_system_by_name(&self, name: &str) -> Option<&StarSystem> {
+        let name = name.trim();
+        self.star_systems.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+
+    // Rebuild every sector's membership from its bounds and the current system positions
+    pub fn assign_sectors(&mut self) {
+        for sector in &mut self.sectors {
+            sector.system_ids = self
+                .star_systems
+                .iter()
+                .filter(|system| sector.bounds.contains(system.galactic_position()))
+                .map(|system| system.id)
+                .collect();
+        }
+    }
+
+    // First sector listing the system; overlapping sectors may share members
+    pub fn sector_for_system(&self, system_id: Id) -> Option<&Sector> {
+        self.sectors.iter().find(|sector| sector.system_ids.contains(&system_id))
+    }
+
     pub fn find_body(&self, id: Id) -> Option<&StellarBody> {
         // Search in systems
         for system in &self.star_systems {
@@ -167,6 +493,186 @@ impl Galaxy {
         self.rogue_objects.iter().find(|b| b.id == id)
     }
 
+    // Mutable counterpart of `find_body`, for editing nested bodies in place
+    pub fn find_body_mut(&mut self, id: Id) -> Option<&mut StellarBody> {
+        // Search in systems
+        for system in &mut self.star_systems {
+            if let Some(body) = system.find_body_recursive_mut(id) {
+                return Some(body);
+            }
+        }
+
+        // Search in rogue objects
+        self.rogue_objects.iter_mut().find(|b| b.id == id)
+    }
+
+    // Political groups holding each system at `epoch`: the groups of Political associations
+    // active then on any body in the system. Systems with none are left out.
+    pub fn political_control_at(&self, epoch: OffsetDateTime) -> std::collections::BTreeMap<Id, Vec<String>> {
+        use crate::stellar_forge::associations::AssociationType;
+
+        self.star_systems
+            .iter()
+            .filter_map(|system| {
+                let mut groups: Vec<String> = system.stars.iter()
+                    .chain(&system.planets)
+                    .chain(&system.belts)
+                    .chain(&system.stations)
+                    .chain(&system.other_bodies)
+                    .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+                    .flat_map(|body| body.active_associations_at(epoch))
+                    .filter(|a| a.association_type == AssociationType::Political)
+                    .map(|a| a.group.clone())
+                    .collect();
+                groups.sort();
+                groups.dedup();
+                (!groups.is_empty()).then_some((system.id, groups))
+            })
+            .collect()
+    }
+
+    // Sum of population over every system and rogue world
+    pub fn total_population(&self) -> f64 {
+        let systems: f64 = self.star_systems.iter().map(|s| s.total_population()).sum();
+        let rogues: f64 = self.rogue_objects
+            .iter()
+            .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+            .map(body_population)
+            .sum();
+        systems + rogues
+    }
+
+    // Every planet and moon in a star system scoring above HABITABLE_THRESHOLD, most
+    // habitable first
+    pub fn habitable_worlds(&self) -> Vec<HabitableWorld> {
+        let mut worlds: Vec<HabitableWorld> = self.star_systems
+            .iter()
+            .flat_map(|system| {
+                system.planets
+                    .iter()
+                    .flat_map(|planet| std::iter::once(planet).chain(planet.descendants()))
+                    .filter_map(move |body| match &body.physical {
+                        Some(crate::stellar_forge::physical::Physical::Planet(p))
+                        | Some(crate::stellar_forge::physical::Physical::Moon(p)) => {
+                            let score = p.habitability_score.filter(|&h| h > HABITABLE_THRESHOLD)?;
+                            Some(HabitableWorld {
+                                body_id: body.id,
+                                name: body.name.clone(),
+                                system_name: system.name.clone(),
+                                habitability_score: score,
+                                surface_temperature_k: p.surface_temperature_k,
+                                galactic_coordinates: system.galactic_coordinates,
+                            })
+                        }
+                        _ => None,
+                    })
+            })
+            .collect();
+        worlds.sort_by(|a, b| b.habitability_score.total_cmp(&a.habitability_score));
+        worlds
+    }
+
+    // Every body named `name` (ignoring case), from all system hierarchies and the rogue
+    // objects; names aren't unique, so there may be several
+    pub fn find_bodies_by_name(&self, name: &str) -> Vec<&StellarBody> {
+        let name = name.trim();
+        self.star_systems
+            .iter()
+            .flat_map(|system| {
+                system.stars.iter()
+                    .chain(&system.planets)
+                    .chain(&system.belts)
+                    .chain(&system.stations)
+                    .chain(&system.other_bodies)
+            })
+            .chain(&self.rogue_objects)
+            .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+            .filter(|body| body.name.eq_ignore_ascii_case(name))
+            .collect()
+    }
+
+    // Every body carrying `tag`, from all system hierarchies and the rogue objects
+    pub fn bodies_with_tag(&self, tag: &str) -> Vec<&StellarBody> {
+        use crate::stellar_forge::core::Taggable;
+
+        self.star_systems
+            .iter()
+            .flat_map(|system| system.bodies_with_tag(tag))
+            .chain(
+                self.rogue_objects
+                    .iter()
+                    .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+                    .filter(|body| body.has_tag(tag)),
+            )
+            .collect()
+    }
+
+    // Reassign every id from `rng` and pin all timestamps to `epoch`, so a seeded
+    // generator always yields an identical galaxy. The frame hierarchy is rebuilt
+    // as the galactic root plus one barycentric frame per system.
+    pub fn reseed_ids<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, epoch: OffsetDateTime) {
+        self.id = seeded_id(rng);
+        self.metadata.creation_date = epoch;
+        self.metadata.last_modified = epoch;
+
+        let mut frame_hierarchy = FrameHierarchy::new();
+        let mut galactic_frame = Frame::new_galactic_iau("Galactic IAU");
+        galactic_frame.id = seeded_id(rng);
+        let galactic_id = galactic_frame.id;
+        frame_hierarchy.add_frame(galactic_frame).ok();
+
+        let mut system_ids = HashMap::new();
+        for system in &mut self.star_systems {
+            let mut system_frame = Frame::new_barycentric(
+                format!("{} Barycenter", system.name),
+                galactic_id,
+                system.galactic_position(),
+                epoch,
+            );
+            system_frame.id = seeded_id(rng);
+
+            let old_id = system.id;
+            system.reseed_ids(rng, system_frame.id, epoch);
+            system_ids.insert(old_id, system.id);
+            frame_hierarchy.add_frame(system_frame).ok();
+        }
+        self.frame_hierarchy = frame_hierarchy;
+
+        for object in &mut self.rogue_objects {
+            object.reseed_ids(rng, SpatialParent::Frame(galactic_id), epoch);
+        }
+
+        for sector in &mut self.sectors {
+            sector.id = seeded_id(rng);
+            for id in &mut sector.system_ids {
+                if let Some(new_id) = system_ids.get(id) {
+                    *id = *new_id;
+                }
+            }
+        }
+    }
+
+    // Absolute galactic position (meters) of any body, summing relative positions up to its system
+    pub fn absolute_position(&self, body_id: Id) -> Option<Vec3> {
+        for system in &self.star_systems {
+            if let Some(chain) = system.body_lineage(body_id) {
+                let offset: Vec3 = chain.iter().map(|b| b.position()).sum();
+                return Some(system.galactic_position() + offset);
+            }
+        }
+
+        // Rogue objects sit directly in the galactic frame
+        self.rogue_objects
+            .iter()
+            .find_map(|body| body.lineage(body_id))
+            .map(|chain| chain.iter().map(|b| b.position()).sum())
+    }
+
+    // Regions whose claimed bounds include the point
+    pub fn regions_controlling<'a>(&self, point: Vec3, regions: &'a [PoliticalRegion]) -> Vec<&'a PoliticalRegion> {
+        regions.iter().filter(|region| region.contains_point(point)).collect()
+    }
+
     pub fn systems_within(&self, center: Vec3, radius_m: f64) -> Vec<&StarSystem> {
         self.star_systems
             .iter()
@@ -179,6 +685,21 @@ impl Galaxy {
         self.systems_within(center, radius_m)
     }
 
+    // Habitable worlds whose system lies within `radius_ly` of `center`, nearest first,
+    // each with its distance in light-years
+    pub fn habitable_worlds_within_ly(&self, center: Vec3, radius_ly: f64) -> Vec<(HabitableWorld, f64)> {
+        let mut worlds: Vec<_> = self.habitable_worlds()
+            .into_iter()
+            .map(|world| {
+                let distance_m = (world.galactic_coordinates.to_cartesian() - center).norm();
+                (world, distance_m / crate::stellar_forge::core::Units::LIGHT_YEAR)
+            })
+            .filter(|(_, distance_ly)| *distance_ly <= radius_ly)
+            .collect();
+        worlds.sort_by(|a, b| a.1.total_cmp(&b.1));
+        worlds
+    }
+
     pub fn nearest_systems(&self, position: Vec3, count: usize) -> Vec<(&StarSystem, f64)> {
         let mut systems_with_distance: Vec<_> = self.star_systems
             .iter()
@@ -200,6 +721,71 @@ pub struct Sector {
     pub system_ids: Vec<Id>,  // References to systems in this sector
 }
 
+// Problem found while validating a system's body tree
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum HierarchyViolation {
+    #[error("{body} references missing parent {parent_id}")]
+    MissingParent { body: String, parent_id: Id },
+
+    #[error("{body} is nested under {parent} but references a different parent")]
+    MisplacedChild { body: String, parent: String },
+
+    #[error("{parent} cannot contain {body} ({kind:?})")]
+    InvalidChild { body: String, kind: BodyKind, parent: String },
+}
+
+// Neighbouring planets packed closer than StarSystem::MIN_HILL_SEPARATION mutual Hill radii
+#[derive(Clone, Debug, PartialEq)]
+pub struct StabilityWarning {
+    pub inner: Id,
+    pub outer: Id,
+    pub inner_name: String,
+    pub outer_name: String,
+    pub mutual_hill_separation: f64,  // (a_outer - a_inner) in mutual Hill radii
+}
+
+impl std::fmt::Display for StabilityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} and {} are only {:.1} mutual Hill radii apart",
+            self.inner_name, self.outer_name, self.mutual_hill_separation
+        )
+    }
+}
+
+fn body_population(body: &StellarBody) -> f64 {
+    match &body.physical {
+        Some(crate::stellar_forge::physical::Physical::Planet(p))
+        | Some(crate::stellar_forge::physical::Physical::Moon(p)) => p.population.unwrap_or(0.0),
+        _ => 0.0,
+    }
+}
+
+// Habitability score above which a planet or moon counts as a habitable world
+pub const HABITABLE_THRESHOLD: f64 = 0.5;
+
+// A planet or moon listed by Galaxy::habitable_worlds
+#[derive(Clone, Debug)]
+pub struct HabitableWorld {
+    pub body_id: Id,
+    pub name: String,
+    pub system_name: String,
+    pub habitability_score: f64,
+    pub surface_temperature_k: Option<f64>,
+    pub galactic_coordinates: crate::stellar_forge::coordinates::GalacticCoordinates,
+}
+
+// One populated world's population after StarSystem::simulate_population
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopulationProjection {
+    pub body_id: Id,
+    pub name: String,
+    pub initial: f64,
+    pub projected: f64,
+    pub carrying_capacity: f64,
+}
+
 // Star system container
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StarSystem {
@@ -302,6 +888,11 @@ impl StarSystem {
         self.galactic_coordinates.to_cartesian()
     }
 
+    /// Position as J2000 right ascension / declination, for matching against star catalogs
+    pub fn equatorial_coordinates(&self) -> EquatorialCoordinates {
+        CoordinateTransform::galactic_to_equatorial(self.galactic_coordinates)
+    }
+
     pub fn add_planet(&mut self, mut planet: StellarBody) -> Result<(), ContainerError> {
         if !matches!(planet.kind, BodyKind::Planet | BodyKind::RoguePlanet) {
             return Err(ContainerError::InvalidChildType);
@@ -323,12 +914,29 @@ impl StarSystem {
         Ok(())
     }
 
+    pub fn add_comet(&mut self, mut comet: StellarBody) -> Result<(), ContainerError> {
+        if comet.kind != BodyKind::Comet {
+            return Err(ContainerError::InvalidChildType);
+        }
+
+        comet.spatial_parent = SpatialParent::Body(self.id);
+        self.other_bodies.push(comet);
+        Ok(())
+    }
+
     pub fn add_station(&mut self, mut station: StellarBody) -> Result<(), ContainerError> {
         if station.kind != BodyKind::Station {
             return Err(ContainerError::InvalidChildType);
         }
 
-        station.spatial_parent = SpatialParent::Body(self.id);
+        // A station orbiting one of this system's bodies keeps that body as its parent
+        let orbits_member = matches!(
+            station.spatial_parent,
+            SpatialParent::Body(id) if id != self.id && self.find_body_recursive(id).is_some()
+        );
+        if !orbits_member {
+            station.spatial_parent = SpatialParent::Body(self.id);
+        }
         self.stations.push(station);
         Ok(())
     }
@@ -383,6 +991,137 @@ impl StarSystem {
         None
     }
 
+    // Find a body by ID (mutable), searching the same places as `find_body_recursive`
+    pub fn find_body_recursive_mut(&mut self, id: Id) -> Option<&mut StellarBody> {
+        for body in self.stars.iter_mut()
+            .chain(&mut self.planets)
+            .chain(&mut self.belts)
+            .chain(&mut self.stations)
+            .chain(&mut self.other_bodies)
+        {
+            if body.id == id {
+                return Some(body);
+            }
+            if let Some(found) = body.find_descendant_mut(id) {
+                return Some(found);
+            }
+        }
+
+        None
+    }
+
+    // Every body in this system's hierarchy carrying `tag`
+    pub fn bodies_with_tag(&self, tag: &str) -> Vec<&StellarBody> {
+        use crate::stellar_forge::core::Taggable;
+
+        self.stars.iter()
+            .chain(&self.planets)
+            .chain(&self.belts)
+            .chain(&self.stations)
+            .chain(&self.other_bodies)
+            .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+            .filter(|body| body.has_tag(tag))
+            .collect()
+    }
+
+    // Chain of bodies from a top-level system body down to `id`
+    pub fn body_lineage(&self, id: Id) -> Option<Vec<&StellarBody>> {
+        self.stars.iter()
+            .chain(&self.planets)
+            .chain(&self.belts)
+            .chain(&self.stations)
+            .chain(&self.other_bodies)
+            .find_map(|body| body.lineage(id))
+    }
+
+    // Check that every parent reference resolves and every nesting is allowed by can_contain.
+    // Top-level bodies may hang off a frame, the system itself or one of its bodies.
+    pub fn validate_hierarchy(&self) -> Vec<HierarchyViolation> {
+        let mut violations = Vec::new();
+        let top_level = [
+            (&self.stars, &[BodyKind::Star, BodyKind::BinarySystem][..]),
+            (&self.planets, &[BodyKind::Planet, BodyKind::RoguePlanet][..]),
+            (&self.belts, &[BodyKind::AsteroidBelt][..]),
+            (&self.stations, &[BodyKind::Station][..]),
+        ];
+
+        for (bodies, allowed) in top_level {
+            for body in bodies {
+                if !allowed.contains(&body.kind) {
+                    violations.push(HierarchyViolation::InvalidChild {
+                        body: body.name.clone(),
+                        kind: body.kind,
+                        parent: self.name.clone(),
+                    });
+                }
+            }
+        }
+
+        for body in self.stars.iter()
+            .chain(&self.planets)
+            .chain(&self.belts)
+            .chain(&self.stations)
+            .chain(&self.other_bodies)
+        {
+            if let SpatialParent::Body(parent_id) = body.spatial_parent {
+                if parent_id != self.id {
+                    match self.find_body_recursive(parent_id) {
+                        Some(parent) if !parent.can_contain(body.kind) => {
+                            violations.push(HierarchyViolation::InvalidChild {
+                                body: body.name.clone(),
+                                kind: body.kind,
+                                parent: parent.name.clone(),
+                            });
+                        }
+                        Some(_) => {}
+                        None => violations.push(HierarchyViolation::MissingParent {
+                            body: body.name.clone(),
+                            parent_id,
+                        }),
+                    }
+                }
+            }
+            self.validate_children(body, &mut violations);
+        }
+
+        violations
+    }
+
+    fn validate_children(&self, parent: &StellarBody, violations: &mut Vec<HierarchyViolation>) {
+        for child in &parent.children {
+            match child.spatial_parent {
+                SpatialParent::Body(id) if id == parent.id => {}
+                SpatialParent::Body(id) if id != self.id && self.find_body_recursive(id).is_none() => {
+                    violations.push(HierarchyViolation::MissingParent { body: child.name.clone(), parent_id: id });
+                }
+                _ => violations.push(HierarchyViolation::MisplacedChild {
+                    body: child.name.clone(),
+                    parent: parent.name.clone(),
+                }),
+            }
+            if !parent.can_contain(child.kind) {
+                violations.push(HierarchyViolation::InvalidChild {
+                    body: child.name.clone(),
+                    kind: child.kind,
+                    parent: parent.name.clone(),
+                });
+            }
+            self.validate_children(child, violations);
+        }
+    }
+
+    // Sum of population over every planet and moon in the system
+    pub fn total_population(&self) -> f64 {
+        self.stars.iter()
+            .chain(&self.planets)
+            .chain(&self.belts)
+            .chain(&self.stations)
+            .chain(&self.other_bodies)
+            .flat_map(|body| std::iter::once(body).chain(body.descendants()))
+            .map(body_population)
+            .sum()
+    }
+
     pub fn total_mass(&self) -> f64 {
         let mut mass = 0.0;
 
@@ -402,21 +1141,247 @@ impl StarSystem {
     }
 
     pub fn habitable_zone(&self) -> Option<(f64, f64)> {
-        // Simplified habitable zone calculation
-        if let Some(primary) = self.stars.first() {
-            if let Some(physical) = &primary.physical {
-                if let crate::stellar_forge::physical::Physical::Star(star) = physical {
-                    let luminosity_solar = star.luminosity_w / 3.828e26;
-                    let inner = 0.95 * luminosity_solar.sqrt();  // AU
-                    let outer = 1.37 * luminosity_solar.sqrt();  // AU
-                    return Some((
-                        inner * crate::stellar_forge::core::Units::AU,
-                        outer * crate::stellar_forge::core::Units::AU,
-                    ));
+        self.habitable_zone_with_model(HabitableZoneModel::default())
+    }
+
+    // Inner and outer habitable-zone radii in meters from the barycenter under `model`. All
+    // stars count toward it, as one source of their total luminosity at their
+    // luminosity-weighted temperature, which holds for circumbinary orbits around close pairs
+    pub fn habitable_zone_with_model(&self, model: HabitableZoneModel) -> Option<(f64, f64)> {
+        let stars: Vec<_> = self.stars
+            .iter()
+            .filter_map(|body| match &body.physical {
+                Some(crate::stellar_forge::physical::Physical::Star(star)) => Some(star),
+                _ => None,
+            })
+            .collect();
+        let luminosity_solar: f64 = stars.iter().map(|star| star.luminosity_solar()).sum();
+        if stars.is_empty() || luminosity_solar <= 0.0 {
+            return None;
+        }
+
+        let temperature_k = stars
+            .iter()
+            .map(|star| star.temperature_k * star.luminosity_solar())
+            .sum::<f64>() / luminosity_solar;
+        let (inner, outer) = model.bounds_au(luminosity_solar, temperature_k);
+        Some((
+            inner * crate::stellar_forge::core::Units::AU,
+            outer * crate::stellar_forge::core::Units::AU,
+        ))
+    }
+
+    // Snow line in AU, where water ice condenses (~2.7 AU for the Sun)
+    pub fn frost_line_au(&self) -> Option<f64> {
+        let primary = self.stars.first()?;
+        match &primary.physical {
+            Some(crate::stellar_forge::physical::Physical::Star(star)) => {
+                let luminosity_solar = star.luminosity_solar();
+                Some(2.7 * luminosity_solar.sqrt())
+            }
+            _ => None,
+        }
+    }
+
+    // Stellar flux at `orbital_radius_m` in units of Earth's insolation (S = L / r², L in
+    // solar luminosities, r in AU); 0.0 without a primary star
+    pub fn insolation_at(&self, orbital_radius_m: f64) -> f64 {
+        let Some(crate::stellar_forge::physical::Physical::Star(star)) =
+            self.stars.first().and_then(|primary| primary.physical.as_ref())
+        else {
+            return 0.0;
+        };
+        let radius_au = orbital_radius_m / crate::stellar_forge::core::Units::AU;
+        if radius_au <= 0.0 {
+            return 0.0;
+        }
+        star.luminosity_solar() / (radius_au * radius_au)
+    }
+
+    // People a fully habitable (score 1.0) Earth-sized world can sustain
+    pub const EARTH_CARRYING_CAPACITY: f64 = 1.2e10;
+
+    // Project every populated planet and moon `years` ahead with logistic growth at
+    // `growth_rate` per year, P(t) = K / (1 + (K - P0) / P0 · e^(-rt)). The carrying capacity
+    // K scales EARTH_CARRYING_CAPACITY by habitability score and surface area; worlds without
+    // a positive K keep their current population.
+    pub fn simulate_population(&self, years: f64, growth_rate: f64) -> Vec<PopulationProjection> {
+        use crate::stellar_forge::physical::Physical;
+
+        self.planets
+            .iter()
+            .flat_map(|planet| std::iter::once(planet).chain(planet.descendants()))
+            .filter_map(|body| match &body.physical {
+                Some(Physical::Planet(p)) | Some(Physical::Moon(p)) => {
+                    let initial = p.population.filter(|pop| *pop > 0.0)?;
+                    let area = (p.radius_m / crate::stellar_forge::core::Units::EARTH_RADIUS).powi(2);
+                    let carrying_capacity =
+                        Self::EARTH_CARRYING_CAPACITY * p.habitability_score.unwrap_or(0.0).max(0.0) * area;
+                    let projected = if carrying_capacity > 0.0 {
+                        let decay = (-growth_rate * years).exp();
+                        carrying_capacity / (1.0 + (carrying_capacity - initial) / initial * decay)
+                    } else {
+                        initial
+                    };
+                    Some(PopulationProjection {
+                        body_id: body.id,
+                        name: body.name.clone(),
+                        initial,
+                        projected,
+                        carrying_capacity,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    // Spacing below which neighbouring planets scatter each other within a few orbits
+    pub const MIN_HILL_SEPARATION: f64 = 10.0;
+
+    // Adjacent planet pairs (by semi-major axis) whose separation is below
+    // MIN_HILL_SEPARATION mutual Hill radii, R = ((m1 + m2) / 3M)^(1/3) (a1 + a2) / 2
+    pub fn check_orbital_stability(&self) -> Vec<StabilityWarning> {
+        const G: f64 = 6.67430e-11;
+
+        let mut orbits: Vec<(&StellarBody, f64, f64)> = self.planets.iter()
+            .filter_map(|planet| match (&planet.motion, planet.physical.as_ref().and_then(|p| p.mass_kg())) {
+                (Some(MotionModel::Keplerian(elements)), Some(mass)) => {
+                    Some((planet, elements.semi_major_axis_m, mass))
+                }
+                _ => None,
+            })
+            .collect();
+        orbits.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        // Stars without a mass fall back on the mass the orbits were built around
+        let star_mass: f64 = self.stars.iter()
+            .filter_map(|s| s.physical.as_ref().and_then(|p| p.mass_kg()))
+            .sum();
+
+        orbits.windows(2)
+            .filter_map(|pair| {
+                let (inner, a1, m1) = pair[0];
+                let (outer, a2, m2) = pair[1];
+                let primary = match &inner.motion {
+                    Some(MotionModel::Keplerian(elements)) if star_mass <= 0.0 => {
+                        elements.gravitational_param_m3s2 / G
+                    }
+                    _ => star_mass,
+                };
+                let mutual_hill = ((m1 + m2) / (3.0 * primary)).cbrt() * (a1 + a2) / 2.0;
+                let separation = (a2 - a1) / mutual_hill;
+                (separation < Self::MIN_HILL_SEPARATION).then(|| StabilityWarning {
+                    inner: inner.id,
+                    outer: outer.id,
+                    inner_name: inner.name.clone(),
+                    outer_name: outer.name.clone(),
+                    mutual_hill_separation: separation,
+                })
+            })
+            .collect()
+    }
+
+    // Planet pairs whose Keplerian periods are close to a small-integer ratio, as
+    // (inner id, outer id, p, q) with outer:inner period = p:q and p, q <= 7. `tolerance` is
+    // the allowed fractional deviation from the exact ratio; only the smallest q is reported.
+    pub fn find_resonances(&self, tolerance: f64) -> Vec<(Id, Id, u32, u32)> {
+        const MAX_TERM: u32 = 7;
+
+        let mut periods: Vec<(Id, f64)> = self.planets.iter()
+            .filter_map(|planet| match &planet.motion {
+                Some(MotionModel::Keplerian(elements)) => Some((planet.id, elements.orbital_period())),
+                _ => None,
+            })
+            .filter(|(_, period)| period.is_finite() && *period > 0.0)
+            .collect();
+        periods.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut resonances = Vec::new();
+        for (i, &(inner, inner_period)) in periods.iter().enumerate() {
+            for &(outer, outer_period) in &periods[i + 1..] {
+                let ratio = outer_period / inner_period;
+                let matched = (1..=MAX_TERM).find_map(|q| {
+                    let p = (ratio * q as f64).round() as u32;
+                    let exact = p as f64 / q as f64;
+                    (p <= MAX_TERM && (ratio / exact - 1.0).abs() <= tolerance).then_some((p, q))
+                });
+                if let Some((p, q)) = matched {
+                    resonances.push((inner, outer, p, q));
                 }
             }
         }
-        None
+        resonances
+    }
+
+    // Draw fresh ids for the system and all of its bodies; see `Galaxy::reseed_ids`
+    pub fn reseed_ids<R: rand::Rng + ?Sized>(&mut self, rng: &mut R, frame_id: Id, epoch: OffsetDateTime) {
+        self.id = seeded_id(rng);
+        self.frame_id = frame_id;
+
+        for star in &mut self.stars {
+            star.reseed_ids(rng, SpatialParent::Frame(frame_id), epoch);
+        }
+
+        let system_id = self.id;
+        for body in self.planets.iter_mut()
+            .chain(&mut self.belts)
+            .chain(&mut self.stations)
+            .chain(&mut self.other_bodies)
+        {
+            body.reseed_ids(rng, SpatialParent::Body(system_id), epoch);
+        }
+    }
+
+    // Mass-weighted center of the stars at `epoch`, propagating each through its motion model
+    pub fn barycenter_at(&self, epoch: OffsetDateTime) -> Vec3 {
+        let mut total_mass = 0.0;
+        let mut weighted_pos = Vec3::zeros();
+
+        for star in &self.stars {
+            if let Some(mass) = star.mass_kg() {
+                weighted_pos += star.state_at(epoch).position_m * mass;
+                total_mass += mass;
+            }
+        }
+
+        if total_mass > 0.0 {
+            weighted_pos / total_mass
+        } else {
+            self.barycenter
+        }
+    }
+
+    // Absolute galactic state of every body at `epoch`, parents before their children
+    pub fn snapshot_at(&self, epoch: OffsetDateTime) -> Vec<(Id, State)> {
+        let origin = State {
+            position_m: self.galactic_position(),
+            velocity_mps: Vec3::zeros(),
+        };
+
+        let mut snapshot = Vec::new();
+        for body in self.stars.iter()
+            .chain(&self.planets)
+            .chain(&self.belts)
+            .chain(&self.stations)
+            .chain(&self.other_bodies)
+        {
+            Self::collect_states(body, origin, epoch, &mut snapshot);
+        }
+        snapshot
+    }
+
+    fn collect_states(body: &StellarBody, parent: State, epoch: OffsetDateTime, out: &mut Vec<(Id, State)>) {
+        let relative = body.state_at(epoch);
+        let absolute = State {
+            position_m: parent.position_m + relative.position_m,
+            velocity_mps: parent.velocity_mps + relative.velocity_mps,
+        };
+        out.push((body.id, absolute));
+
+        for child in &body.children {
+            Self::collect_states(child, absolute, epoch, out);
+        }
     }
 
     pub fn is_binary(&self) -> bool {
@@ -469,6 +1434,10 @@ impl PoliticalRegion {
     pub fn claim_region(&mut self, bounds: Bounds) {
         self.claimed_regions.push(bounds);
     }
+
+    pub fn contains_point(&self, p: Vec3) -> bool {
+        self.claimed_regions.iter().any(|bounds| bounds.contains(p))
+    }
 }
 
 // Fleet or group container for mobile objects
@@ -482,6 +1451,9 @@ pub struct Fleet {
     pub current_position: State,
     pub destination: Option<Vec3>,
     pub motion_model: Option<MotionModel>,
+    // Seconds advanced so far; motion_model is evaluated this long after its own epoch
+    #[serde(default)]
+    pub elapsed_s: f64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -507,9 +1479,13 @@ impl Fleet {
             },
             destination: None,
             motion_model: None,
+            elapsed_s: 0.0,
         }
     }
 
+    // Distance between neighbouring vessels in a formation
+    pub const FORMATION_SPACING_M: f64 = 1.0e4;
+
     pub fn add_vessel(&mut self, vessel: StellarBody) -> Result<(), ContainerError> {
         if vessel.kind != BodyKind::Vehicle {
             return Err(ContainerError::InvalidChildType);
@@ -533,8 +1509,834 @@ impl Fleet {
         self.arrange_formation();
     }
 
-    fn arrange_formation(&mut self) {
-        // Arrange vessels according to formation pattern
-        // This would calculate relative positions for each vessel
+    // Move the fleet `dt_s` seconds on. A motion model sets the state outright; otherwise the
+    // fleet heads straight for its destination at its current speed, stopping on arrival, or
+    // drifts at its current velocity. Vessels move with the fleet, keeping their offsets.
+    pub fn advance(&mut self, dt_s: f64) {
+        self.elapsed_s += dt_s;
+        let start = self.current_position.position_m;
+
+        self.current_position = match (&self.motion_model, self.destination) {
+            (Some(model), _) => {
+                let epoch = model.epoch().unwrap_or(OffsetDateTime::UNIX_EPOCH);
+                model.propagate(self.current_position, epoch, epoch + time::Duration::seconds_f64(self.elapsed_s))
+            }
+            (None, Some(destination)) => {
+                let speed = self.current_position.velocity_mps.norm();
+                let to_go = destination - start;
+                let distance = to_go.norm();
+                if distance <= speed * dt_s {
+                    self.destination = None;
+                    State { position_m: destination, velocity_mps: Vec3::zeros() }
+                } else {
+                    let velocity = to_go / distance * speed;
+                    State { position_m: start + velocity * dt_s, velocity_mps: velocity }
+                }
+            }
+            (None, None) => State {
+                position_m: start + self.current_position.velocity_mps * dt_s,
+                velocity_mps: self.current_position.velocity_mps,
+            },
+        };
+
+        let shift = self.current_position.position_m - start;
+        for vessel in &mut self.vessels {
+            vessel.state.position_m += shift;
+            vessel.state.velocity_mps = self.current_position.velocity_mps;
+        }
+    }
+
+    // Time to cover the straight line from the fleet's position to `destination` at
+    // `speed_mps`; Duration::MAX when the speed isn't positive and the fleet would never arrive
+    pub fn eta_to(&self, destination: Vec3, speed_mps: f64) -> time::Duration {
+        let distance = (destination - self.current_position.position_m).norm();
+        if distance == 0.0 {
+            return time::Duration::ZERO;
+        }
+        if speed_mps.is_nan() || speed_mps <= 0.0 {
+            return time::Duration::MAX;
+        }
+        time::Duration::checked_seconds_f64(distance / speed_mps).unwrap_or(time::Duration::MAX)
+    }
+
+    // Campaign date of arrival at `destination` leaving at `departure`, if the fleet gets there
+    pub fn arrival_epoch(&self, destination: Vec3, speed_mps: f64, departure: OffsetDateTime) -> Option<OffsetDateTime> {
+        departure.checked_add(self.eta_to(destination, speed_mps))
+    }
+
+    // Offsets from the fleet position for each vessel, in vessel order, oriented along the
+    // heading (toward the destination, else along the velocity, else +x). The first vessel
+    // leads. Custom formations have no computed offsets.
+    pub fn formation_offsets(&self) -> Vec<Vec3> {
+        let n = self.vessels.len();
+        let spacing = Self::FORMATION_SPACING_M;
+
+        let heading = self.destination
+            .map(|d| d - self.current_position.position_m)
+            .filter(|h| h.norm() > 0.0)
+            .or_else(|| Some(self.current_position.velocity_mps).filter(|v| v.norm() > 0.0))
+            .map(|h| h.normalize())
+            .unwrap_or_else(Vec3::x);
+        let side = if heading.cross(&Vec3::z()).norm() > 1e-9 { heading.cross(&Vec3::z()) } else { heading.cross(&Vec3::y()) };
+        let right = side.normalize();
+        let up = right.cross(&heading);
+
+        match &self.formation {
+            // Single file behind the leader
+            Formation::Line => (0..n).map(|i| -heading * (i as f64 * spacing)).collect(),
+            // Leader at the point, the rest alternating right and left down the arms of a V
+            Formation::Wedge => (0..n)
+                .map(|i| {
+                    let rank = i.div_ceil(2) as f64;
+                    let side = if i % 2 == 1 { 1.0 } else { -1.0 };
+                    (-heading + right * side) * (rank * spacing)
+                })
+                .collect(),
+            // Near-square grid facing the heading
+            Formation::Wall => {
+                let columns = (n as f64).sqrt().ceil().max(1.0) as usize;
+                let rows = n.div_ceil(columns);
+                (0..n)
+                    .map(|i| {
+                        let column = (i % columns) as f64 - (columns - 1) as f64 / 2.0;
+                        let row = (i / columns) as f64 - (rows.max(1) - 1) as f64 / 2.0;
+                        (right * column + up * row) * spacing
+                    })
+                    .collect()
+            }
+            // Evenly spread over a shell (Fibonacci lattice) sized to keep vessels apart
+            Formation::Sphere => {
+                if n < 2 {
+                    return vec![Vec3::zeros(); n];
+                }
+                let radius = spacing * (n as f64 / 4.0).sqrt().max(1.0);
+                let golden_angle = std::f64::consts::PI * (3.0 - 5f64.sqrt());
+                (0..n)
+                    .map(|i| {
+                        let z = 1.0 - 2.0 * (i as f64 + 0.5) / n as f64;
+                        let ring = (1.0 - z * z).sqrt();
+                        let theta = golden_angle * i as f64;
+                        (right * (ring * theta.cos()) + up * (ring * theta.sin()) + heading * z) * radius
+                    })
+                    .collect()
+            }
+            Formation::Custom(_) => Vec::new(),
+        }
     }
-}
\ No newline at end of file
+
+    // Place every vessel at the fleet position plus its formation offset. Custom formations
+    // leave vessel positions as they are.
+    pub fn arrange_formation(&mut self) {
+        let offsets = self.formation_offsets();
+        if offsets.len() != self.vessels.len() {
+            return;
+        }
+        for (vessel, offset) in self.vessels.iter_mut().zip(offsets) {
+            vessel.state.position_m = self.current_position.position_m + offset;
+            vessel.state.velocity_mps = self.current_position.velocity_mps;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stellar_forge::builders::{MoonBuilder, PlanetBuilder, SystemBuilder};
+    use crate::stellar_forge::core::Units;
+    use crate::stellar_forge::physical::Physical;
+
+    fn system_with_luminosity(luminosity_solar: f64) -> StarSystem {
+        let mut system = SystemBuilder::new("Test").with_star("G2V").build();
+        if let Some(Physical::Star(star)) = &mut system.stars[0].physical {
+            star.luminosity_w = luminosity_solar * crate::stellar_forge::core::Units::SOLAR_LUMINOSITY;
+        }
+        system
+    }
+
+    #[test]
+    fn test_moon_absolute_position() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+
+        let sol = create_sol_like_system().at_position(3.0, 4.0, 0.0).build();
+        let sol_position = sol.galactic_position();
+        let earth = sol.planets.iter().find(|p| p.name == "Earth").unwrap();
+        let moon_id = earth.children[0].id;
+        let earth_id = earth.id;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(sol).unwrap();
+
+        let earth_position = galaxy.absolute_position(earth_id).unwrap();
+        let moon_position = galaxy.absolute_position(moon_id).unwrap();
+
+        // Earth sits ~1 AU from Sol and the Moon ~384,400 km from Earth
+        assert!(((earth_position - sol_position).norm() / Units::AU - 1.0).abs() < 0.02);
+        assert!(((moon_position - earth_position).norm() / 3.844e8 - 1.0).abs() < 0.01);
+        assert!((moon_position - sol_position).norm() < 1.1 * Units::AU);
+        assert!(galaxy.absolute_position(Id::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_returns_after_one_period() {
+        use crate::stellar_forge::builders::PlanetBuilder;
+
+        let system = SystemBuilder::new("Test")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Circular", 1.0))
+            .build();
+        let planet = &system.planets[0];
+        let period = planet.motion.as_ref().unwrap().orbital_period_s().unwrap();
+        let start = planet.epoch;
+
+        let state_of = |snapshot: Vec<(Id, State)>| {
+            snapshot.into_iter().find(|(id, _)| *id == planet.id).unwrap().1
+        };
+        let initial = state_of(system.snapshot_at(start));
+        let half = state_of(system.snapshot_at(start + time::Duration::seconds_f64(period / 2.0)));
+        let full = state_of(system.snapshot_at(start + time::Duration::seconds_f64(period)));
+
+        let orbit_m = (initial.position_m - system.galactic_position()).norm();
+        assert!((full.position_m - initial.position_m).norm() < 1e-6 * orbit_m);
+        assert!(((half.position_m - initial.position_m).norm() / orbit_m - 2.0).abs() < 1e-6);
+        assert_eq!(system.snapshot_at(start).len(), 2);
+    }
+
+    #[test]
+    fn test_binary_barycenter_stays_fixed() {
+        let system = SystemBuilder::new("Pair").with_binary_stars("G2V", "K5V", 20.0).build();
+        let (a, b) = (&system.stars[0], &system.stars[1]);
+        let period = b.motion.as_ref().unwrap().orbital_period_s().unwrap();
+        assert!((a.motion.as_ref().unwrap().orbital_period_s().unwrap() / period - 1.0).abs() < 1e-9);
+
+        let start = a.epoch;
+        let origin = system.barycenter_at(start);
+        let radius_a = (a.state_at(start).position_m - origin).norm();
+        let radius_b = (b.state_at(start).position_m - origin).norm();
+        assert!(radius_a > 0.0 && radius_b > 0.0);
+
+        for step in 1..8 {
+            let epoch = start + time::Duration::seconds_f64(period * step as f64 / 8.0);
+            let drift = (system.barycenter_at(epoch) - origin).norm();
+            assert!(drift < 1e-6 * Units::AU, "barycenter moved {} m", drift);
+
+            // Both stars stay on their own circles, always on opposite sides
+            let pos_a = a.state_at(epoch).position_m - origin;
+            let pos_b = b.state_at(epoch).position_m - origin;
+            assert!((pos_a.norm() / radius_a - 1.0).abs() < 1e-9);
+            assert!((pos_b.norm() / radius_b - 1.0).abs() < 1e-9);
+            assert!(((pos_a - pos_b).norm() / (20.0 * Units::AU) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_equatorial_round_trip_for_neighbors() {
+        use crate::stellar_forge::coordinates::ReferencePositions;
+
+        for coords in [
+            ReferencePositions::alpha_centauri(),
+            ReferencePositions::vega(),
+            ReferencePositions::polaris(),
+            ReferencePositions::betelgeuse(),
+        ] {
+            let system = StarSystem::new_single_star(
+                "Neighbor",
+                coords,
+                StellarBody::new_star("Primary", SpatialParent::Frame(Id::new_v4()), "G2V"),
+            );
+            let equatorial = system.equatorial_coordinates();
+            assert!((0.0..24.0).contains(&equatorial.ra_hours()));
+            assert!((-90.0..=90.0).contains(&equatorial.dec_degrees()));
+
+            let back = CoordinateTransform::equatorial_to_galactic(equatorial);
+            let error = (back.to_cartesian() - system.galactic_position()).norm();
+            assert!(error < 1e-6 * coords.distance_m, "round trip drifted {} m", error);
+        }
+    }
+
+    #[test]
+    fn test_frost_line_scales_with_luminosity() {
+        let sun_like = system_with_luminosity(1.0).frost_line_au().unwrap();
+        assert!((sun_like - 2.7).abs() < 1e-9);
+
+        let bright = system_with_luminosity(4.0).frost_line_au().unwrap();
+        assert!((bright - 5.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_find_body_mut_edits_moon_in_place() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(create_sol_like_system().build()).unwrap();
+        let moon_id = galaxy.star_systems[0]
+            .planets
+            .iter()
+            .flat_map(|p| p.children.iter())
+            .find(|m| m.name == "Moon")
+            .unwrap()
+            .id;
+
+        galaxy.find_body_mut(moon_id).unwrap().name = "Selene".to_string();
+
+        assert_eq!(galaxy.find_body(moon_id).unwrap().name, "Selene");
+        assert!(galaxy.find_body_mut(Id::new_v4()).is_none());
+    }
+
+    #[test]
+    fn test_bodies_with_tag() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+        use crate::stellar_forge::core::Taggable;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(create_sol_like_system().build()).unwrap();
+        galaxy.add_star_system(SystemBuilder::new("Alpha").with_star("K1V").build()).unwrap();
+        let system = &mut galaxy.star_systems[0];
+        system.planets[2].add_tag("habitable");
+        system.planets[2].children[0].add_tag("mining");
+        system.planets[3].add_tag("mining");
+        galaxy.star_systems[1].stars[0].add_tag("mining");
+
+        let mining: Vec<&str> = galaxy.bodies_with_tag("mining").iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(mining.len(), 3);
+        assert!(mining.contains(&"Moon") && mining.contains(&"Mars"));
+        assert_eq!(galaxy.bodies_with_tag("habitable").len(), 1);
+        assert!(galaxy.bodies_with_tag("derelict").is_empty());
+    }
+
+    #[test]
+    fn test_habitable_worlds_lists_earth() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(create_sol_like_system().build()).unwrap();
+        galaxy.add_star_system(SystemBuilder::new("Barren").with_star("M5V").build()).unwrap();
+
+        let worlds = galaxy.habitable_worlds();
+        let earth = worlds.iter().find(|w| w.name == "Earth").expect("Earth should be habitable");
+        assert_eq!(earth.system_name, galaxy.star_systems[0].name);
+        assert!(earth.habitability_score > HABITABLE_THRESHOLD);
+        assert!(earth.surface_temperature_k.is_some());
+        assert_eq!(earth.galactic_coordinates.distance_m, galaxy.star_systems[0].galactic_coordinates.distance_m);
+        assert!(worlds.iter().all(|w| w.system_name != "Barren"));
+    }
+
+    #[test]
+    fn test_habitable_worlds_within_radius() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(create_sol_like_system().at_position(1.0, 0.0, 0.0).build()).unwrap();
+        let mut far = create_sol_like_system().at_position(40.0, 0.0, 0.0).build();
+        far.name = "Far Sol".to_string();
+        galaxy.add_star_system(far).unwrap();
+        galaxy.add_star_system(SystemBuilder::new("Barren").with_star("M5V").at_position(0.5, 0.0, 0.0).build()).unwrap();
+
+        let near = galaxy.habitable_worlds_within_ly(Vec3::zeros(), 10.0);
+        assert!(!near.is_empty());
+        assert!(near.iter().all(|(w, d)| w.system_name != "Far Sol" && w.system_name != "Barren" && *d <= 10.0));
+        assert!(near.iter().any(|(w, d)| w.name == "Earth" && (d - 1.0).abs() < 1e-6));
+
+        let all = galaxy.habitable_worlds_within_ly(Vec3::zeros(), 50.0);
+        assert!(all.iter().any(|(w, _)| w.system_name == "Far Sol"));
+        assert!(all.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn test_political_control_skips_expired_associations() {
+        use crate::stellar_forge::associations::{Association, AssociationType};
+        use crate::stellar_forge::core::Associable;
+
+        let start = OffsetDateTime::UNIX_EPOCH;
+        let query = start + time::Duration::days(365);
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(SystemBuilder::new("Border").with_star("K1V").build()).unwrap();
+
+        let mut lapsed = Association::new(AssociationType::Political, "member", "Old Empire");
+        lapsed.since_epoch = Some(start);
+        lapsed.terminate(start + time::Duration::days(100));
+        let mut current = Association::new(AssociationType::Political, "member", "Republic");
+        current.since_epoch = Some(start + time::Duration::days(100));
+        let star = &mut galaxy.star_systems[0].stars[0];
+        star.add_association(lapsed);
+        star.add_association(current);
+
+        let active: Vec<&str> = star.active_associations_at(query).iter().map(|a| a.group.as_str()).collect();
+        assert_eq!(active, vec!["Republic"]);
+
+        let control = galaxy.political_control_at(query);
+        assert_eq!(control.get(&galaxy.star_systems[0].id), Some(&vec!["Republic".to_string()]));
+        let earlier = galaxy.political_control_at(start + time::Duration::days(50));
+        assert_eq!(earlier.get(&galaxy.star_systems[0].id), Some(&vec!["Old Empire".to_string()]));
+    }
+
+    #[test]
+    fn test_population_grows_toward_carrying_capacity() {
+        let mut system = SystemBuilder::new("Colony")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Haven", 1.0))
+            .with_planet(PlanetBuilder::terrestrial("Empty", 1.5))
+            .build();
+        if let Some(Physical::Planet(p)) = &mut system.planets[0].physical {
+            p.radius_m = Units::EARTH_RADIUS;
+            p.habitability_score = Some(0.5);
+            p.population = Some(1e6);
+        }
+
+        let projections = system.simulate_population(50.0, 0.03);
+        assert_eq!(projections.len(), 1);
+        let haven = &projections[0];
+        assert_eq!(haven.name, "Haven");
+        assert!((haven.carrying_capacity - 6e9).abs() < 1.0);
+        assert!(haven.projected > haven.initial);
+
+        let mut previous = haven.initial;
+        for years in [100.0, 300.0, 1000.0, 5000.0] {
+            let projected = system.simulate_population(years, 0.03)[0].projected;
+            assert!(projected >= previous && projected <= haven.carrying_capacity);
+            previous = projected;
+        }
+        assert!((previous - haven.carrying_capacity).abs() / haven.carrying_capacity < 1e-6);
+    }
+
+    #[test]
+    fn test_total_population_rollup() {
+        let mut galaxy = Galaxy::new("Test");
+        for name in ["Alpha", "Beta"] {
+            galaxy.add_star_system(
+                SystemBuilder::new(name)
+                    .with_star("G2V")
+                    .with_planet(PlanetBuilder::terrestrial(format!("{} b", name), 1.0).with_moon(MoonBuilder::new("Moon")))
+                    .build(),
+            ).unwrap();
+        }
+        let set_population = |body: &mut StellarBody, population: f64| match &mut body.physical {
+            Some(Physical::Planet(p)) | Some(Physical::Moon(p)) => p.population = Some(population),
+            _ => panic!("{} has no planetary physical data", body.name),
+        };
+        set_population(&mut galaxy.star_systems[0].planets[0], 8e9);
+        set_population(&mut galaxy.star_systems[0].planets[0].children[0], 2.5e4);
+        set_population(&mut galaxy.star_systems[1].planets[0], 3e6);
+
+        assert_eq!(galaxy.star_systems[0].total_population(), 8e9 + 2.5e4);
+        assert_eq!(galaxy.star_systems[1].total_population(), 3e6);
+        assert_eq!(galaxy.total_population(), 8e9 + 2.5e4 + 3e6);
+    }
+
+    #[test]
+    fn test_fleet_wedge_formation_and_advance() {
+        let mut fleet = Fleet::new("Task Force");
+        for i in 0..5 {
+            fleet
+                .add_vessel(StellarBody::new(format!("Ship {}", i), BodyKind::Vehicle, SpatialParent::Frame(Id::new_v4())))
+                .unwrap();
+        }
+        fleet.current_position.position_m = Vec3::new(1.0e6, 0.0, 0.0);
+        fleet.current_position.velocity_mps = Vec3::new(100.0, 0.0, 0.0);
+        fleet.set_formation(Formation::Wedge);
+
+        // Heading +x: the leader at the point, pairs trailing behind on either side
+        let spacing = Fleet::FORMATION_SPACING_M;
+        let offsets: Vec<Vec3> = fleet.vessels.iter().map(|v| v.state.position_m - fleet.current_position.position_m).collect();
+        assert!(offsets[0].norm() < 1e-6);
+        for pair in [(1, 2), (3, 4)] {
+            let (a, b) = (offsets[pair.0], offsets[pair.1]);
+            assert!(a.x < 0.0 && (a.x - b.x).abs() < 1e-6, "pair {:?} not level behind the leader", pair);
+            assert!(a.y * b.y < 0.0, "pair {:?} not on opposite arms", pair);
+        }
+        assert!((offsets[3].x - 2.0 * offsets[1].x).abs() < 1e-6);
+        assert!((offsets[1].norm() - spacing * 2f64.sqrt()).abs() < 1e-6);
+
+        let before: Vec<Vec3> = fleet.vessels.iter().map(|v| v.state.position_m).collect();
+        fleet.advance(60.0);
+        assert!((fleet.current_position.position_m - Vec3::new(1.006e6, 0.0, 0.0)).norm() < 1e-6);
+        for (vessel, start) in fleet.vessels.iter().zip(&before) {
+            assert!((vessel.state.position_m - start - Vec3::new(6000.0, 0.0, 0.0)).norm() < 1e-6);
+        }
+
+        // Heading for a destination: arrive and stop rather than overshoot
+        fleet.destination = Some(Vec3::new(1.006e6, 3000.0, 0.0));
+        fleet.advance(60.0);
+        assert!((fleet.current_position.position_m - Vec3::new(1.006e6, 3000.0, 0.0)).norm() < 1e-6);
+        assert!(fleet.destination.is_none());
+        assert_eq!(fleet.current_position.velocity_mps, Vec3::zeros());
+    }
+
+    #[test]
+    fn test_fleet_follows_ephemeris_from_its_epoch() {
+        use crate::stellar_forge::motion::{EphemerisSample, EphemerisTable, InterpolationMethod};
+
+        let epoch = time::macros::datetime!(2300-01-01 00:00:00 UTC);
+        let sample = |hours: i64, x: f64| EphemerisSample {
+            epoch: epoch + time::Duration::hours(hours),
+            state: State { position_m: Vec3::new(x, 0.0, 0.0), velocity_mps: Vec3::zeros() },
+        };
+        let mut fleet = Fleet::new("Patrol");
+        fleet.motion_model = Some(MotionModel::TableEphemeris(EphemerisTable {
+            samples: vec![sample(0, 0.0), sample(2, 2.0e6)],
+            interpolation: InterpolationMethod::Linear,
+        }));
+
+        // Halfway between the samples, rather than centuries before them in 1970
+        fleet.advance(3600.0);
+        assert!((fleet.current_position.position_m - Vec3::new(1.0e6, 0.0, 0.0)).norm() < 1e-6);
+    }
+
+    #[test]
+    fn test_fleet_eta_is_distance_over_speed() {
+        let mut fleet = Fleet::new("Convoy");
+        fleet.current_position.position_m = Vec3::new(Units::AU, 0.0, 0.0);
+        let destination = Vec3::new(Units::AU, 3.0e8, 4.0e8);
+
+        // 5e8 m at 1000 km/s
+        let eta = fleet.eta_to(destination, 1.0e6);
+        assert!((eta.as_seconds_f64() - 500.0).abs() < 1e-6);
+
+        let departure = OffsetDateTime::UNIX_EPOCH;
+        assert_eq!(fleet.arrival_epoch(destination, 1.0e6, departure), Some(departure + time::Duration::seconds(500)));
+        assert_eq!(fleet.eta_to(destination, 0.0), time::Duration::MAX);
+        assert_eq!(fleet.arrival_epoch(destination, 0.0, departure), None);
+
+        // Advancing at that speed for the ETA lands on the destination
+        fleet.current_position.velocity_mps = Vec3::new(0.0, 0.0, 1.0e6);
+        fleet.destination = Some(destination);
+        fleet.advance(eta.as_seconds_f64());
+        assert!((fleet.current_position.position_m - destination).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_find_bodies_by_name() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(create_sol_like_system().build()).unwrap();
+        galaxy.add_star_system(
+            SystemBuilder::new("Kepler")
+                .with_star("K1V")
+                .with_planet(PlanetBuilder::terrestrial("New Earth", 0.9).with_moon(MoonBuilder::new("Moon")))
+                .build(),
+        ).unwrap();
+
+        let sol = galaxy.find_system_by_name("sol").unwrap();
+        let earth = galaxy.find_bodies_by_name("EARTH");
+        assert_eq!(earth.len(), 1);
+        assert_eq!(earth[0].kind, BodyKind::Planet);
+        assert!(sol.find_body_recursive(earth[0].id).is_some());
+
+        // Both systems have a "Moon"
+        assert_eq!(galaxy.find_bodies_by_name(" moon ").len(), 2);
+        assert!(galaxy.find_bodies_by_name("Vulcan").is_empty());
+    }
+
+    #[test]
+    fn test_insolation_in_earth_flux_units() {
+        let system = system_with_luminosity(1.0);
+        assert!((system.insolation_at(Units::AU) - 1.0).abs() < 1e-9);
+        assert!((system.insolation_at(1.524 * Units::AU) - 0.43).abs() < 0.005);
+
+        let bright = system_with_luminosity(4.0);
+        assert!((bright.insolation_at(2.0 * Units::AU) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_habitable_zone_models_for_sun() {
+        let mut system = system_with_luminosity(1.0);
+        if let Some(Physical::Star(star)) = &mut system.stars[0].physical {
+            star.temperature_k = 5780.0;
+        }
+        let zone_au = |model| {
+            let (inner, outer) = system.habitable_zone_with_model(model).unwrap();
+            (inner / Units::AU, outer / Units::AU)
+        };
+
+        assert_eq!(system.habitable_zone(), system.habitable_zone_with_model(HabitableZoneModel::Simple));
+        let simple = zone_au(HabitableZoneModel::Simple);
+        assert!((simple.0 - 0.95).abs() < 1e-9 && (simple.1 - 1.37).abs() < 1e-9);
+        let kasting = zone_au(HabitableZoneModel::Kasting);
+        assert!((kasting.0 - 0.95).abs() < 1e-9 && (kasting.1 - 1.67).abs() < 1e-9);
+
+        // Kopparapu et al. (2014) give 0.95-1.68 AU and 0.75-1.77 AU for the Sun
+        let conservative = zone_au(HabitableZoneModel::KopparapuConservative);
+        assert!((conservative.0 - 0.95).abs() < 0.01 && (conservative.1 - 1.68).abs() < 0.01, "{:?}", conservative);
+        let optimistic = zone_au(HabitableZoneModel::KopparapuOptimistic);
+        assert!((optimistic.0 - 0.75).abs() < 0.01 && (optimistic.1 - 1.77).abs() < 0.01, "{:?}", optimistic);
+        assert!(optimistic.0 < conservative.0 && optimistic.1 > conservative.1);
+    }
+
+    #[test]
+    fn test_close_binary_widens_habitable_zone() {
+        let single = system_with_luminosity(1.0);
+        let mut binary = system_with_luminosity(1.0);
+        let mut companion = binary.stars[0].clone();
+        companion.id = Id::new_v4();
+        companion.name = "Test B".to_string();
+        binary.stars.push(companion);
+
+        let (single_inner, single_outer) = single.habitable_zone().unwrap();
+        let (binary_inner, binary_outer) = binary.habitable_zone().unwrap();
+        assert!(binary_inner > single_inner && binary_outer > single_outer);
+        assert!((binary_outer - binary_inner) > (single_outer - single_inner));
+        // Twice the light pushes the zone out by √2
+        assert!((binary_outer / single_outer - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    fn bounds(shape: BoundShape) -> Bounds {
+        bounds_at(Vec3::zeros(), shape)
+    }
+
+    fn bounds_at(center: Vec3, shape: BoundShape) -> Bounds {
+        let half = Vec3::repeat(10.0);
+        Bounds { min: center - half, max: center + half, shape }
+    }
+
+    #[test]
+    fn test_bounds_volume() {
+        use std::f64::consts::PI;
+        assert_eq!(bounds(BoundShape::Box).volume(), 8000.0);
+        assert!((bounds(BoundShape::Sphere { radius: 3.0 }).volume() - 36.0 * PI).abs() < 1e-9);
+        assert!((bounds(BoundShape::Cylinder { radius: 2.0, height: 5.0 }).volume() - 20.0 * PI).abs() < 1e-9);
+        let torus = bounds(BoundShape::Torus { major_radius: 4.0, minor_radius: 1.0 });
+        assert!((torus.volume() - 8.0 * PI * PI).abs() < 1e-9);
+        assert_eq!(bounds(BoundShape::Custom("nebula".into())).volume(), 0.0);
+    }
+
+    #[test]
+    fn test_bounds_intersection() {
+        let sphere = |x: f64, radius: f64| bounds_at(Vec3::new(x, 0.0, 0.0), BoundShape::Sphere { radius });
+        let cylinder = |x: f64, z: f64| {
+            bounds_at(Vec3::new(x, 0.0, z), BoundShape::Cylinder { radius: 5.0, height: 4.0 })
+        };
+        let cube = |x: f64| bounds_at(Vec3::new(x, 0.0, 0.0), BoundShape::Box);
+
+        // Spheres touching at a single surface point still intersect
+        assert!(sphere(0.0, 5.0).intersects(&sphere(10.0, 5.0)));
+        assert!(!sphere(0.0, 5.0).intersects(&sphere(10.1, 5.0)));
+
+        assert!(cube(0.0).intersects(&cube(20.0)));
+        assert!(!cube(0.0).intersects(&cube(20.5)));
+
+        assert!(sphere(0.0, 5.0).intersects(&cube(15.0)));
+        assert!(cube(15.0).intersects(&sphere(0.0, 5.0)));
+        assert!(!sphere(0.0, 5.0).intersects(&cube(15.5)));
+
+        assert!(cylinder(0.0, 0.0).intersects(&cylinder(10.0, 0.0)));
+        assert!(!cylinder(0.0, 0.0).intersects(&cylinder(10.5, 0.0)));
+        assert!(!cylinder(0.0, 0.0).intersects(&cylinder(0.0, 4.5)));
+        assert!(cylinder(0.0, 0.0).intersects(&cube(15.0)));
+        assert!(!cylinder(0.0, 0.0).intersects(&cube(15.5)));
+        assert!(sphere(0.0, 2.0).intersects(&cylinder(6.0, 0.0)));
+        assert!(!sphere(0.0, 2.0).intersects(&cylinder(8.0, 3.0)));
+
+        // A sphere in the torus hole misses it, one on the ring hits it
+        let torus = bounds(BoundShape::Torus { major_radius: 6.0, minor_radius: 2.0 });
+        assert!(!torus.intersects(&sphere(0.0, 3.0)));
+        assert!(torus.intersects(&sphere(6.0, 1.0)));
+        assert!(torus.intersects(&cube(17.0)));
+
+        let custom = bounds(BoundShape::Custom("nebula".into()));
+        assert!(!custom.intersects(&cube(0.0)));
+        assert!(!sphere(0.0, 5.0).intersects(&custom));
+    }
+
+    #[test]
+    fn test_bounds_contain_interior_and_boundary() {
+        let cube = bounds(BoundShape::Box);
+        assert!(cube.contains(Vec3::new(1.0, -2.0, 3.0)));
+        assert!(cube.contains(Vec3::new(10.0, -10.0, 10.0)));
+        assert!(!cube.contains(Vec3::new(10.1, 0.0, 0.0)));
+
+        let sphere = bounds(BoundShape::Sphere { radius: 5.0 });
+        assert!(sphere.contains(Vec3::new(1.0, 1.0, 1.0)));
+        assert!(sphere.contains(Vec3::new(0.0, 0.0, -5.0)));
+        assert!(!sphere.contains(Vec3::new(4.0, 4.0, 0.0)));
+
+        let cylinder = bounds(BoundShape::Cylinder { radius: 5.0, height: 4.0 });
+        assert!(cylinder.contains(Vec3::new(3.0, 0.0, 1.0)));
+        assert!(cylinder.contains(Vec3::new(0.0, 5.0, 2.0)));
+        assert!(!cylinder.contains(Vec3::new(0.0, 0.0, 2.5)));
+        assert!(!cylinder.contains(Vec3::new(4.0, 4.0, 0.0)));
+
+        let torus = bounds(BoundShape::Torus { major_radius: 6.0, minor_radius: 2.0 });
+        assert!(torus.contains(Vec3::new(6.0, 0.0, 1.0)));
+        assert!(torus.contains(Vec3::new(0.0, 8.0, 0.0)));
+        assert!(torus.contains(Vec3::new(-6.0, 0.0, -2.0)));
+        assert!(!torus.contains(Vec3::zeros()));
+        assert!(!torus.contains(Vec3::new(6.0, 0.0, 2.5)));
+
+        assert!(!bounds(BoundShape::Custom("nebula".into())).contains(Vec3::zeros()));
+    }
+
+    #[test]
+    fn test_regions_controlling_point() {
+        let galaxy = Galaxy::new("Test");
+        let mut inner = PoliticalRegion::new("Inner", "Republic");
+        inner.claim_region(bounds(BoundShape::Sphere { radius: 5.0 }));
+        let mut outer = PoliticalRegion::new("Outer", "Monarchy");
+        outer.claim_region(bounds(BoundShape::Box));
+        let regions = [inner, outer];
+
+        let names = |p: Vec3| -> Vec<String> {
+            galaxy.regions_controlling(p, &regions).iter().map(|r| r.name.clone()).collect()
+        };
+        assert_eq!(names(Vec3::zeros()), vec!["Inner", "Outer"]);
+        assert_eq!(names(Vec3::new(8.0, 0.0, 0.0)), vec!["Outer"]);
+        assert!(names(Vec3::new(20.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_assign_sectors_by_position() {
+        let mut galaxy = Galaxy::new("Test");
+        let inside = SystemBuilder::new("Inside").at_position(5.0, 5.0, 0.0).with_star("G2V").build();
+        let outside = SystemBuilder::new("Outside").at_position(25.0, 0.0, 0.0).with_star("K1V").build();
+        let (inside_id, outside_id) = (inside.id, outside.id);
+        galaxy.add_star_system(inside).unwrap();
+        galaxy.add_star_system(outside).unwrap();
+
+        let sector = Sector {
+            id: Id::new_v4(),
+            name: "Core".into(),
+            bounds: Bounds {
+                min: Vec3::repeat(-10.0 * Units::LIGHT_YEAR),
+                max: Vec3::repeat(10.0 * Units::LIGHT_YEAR),
+                shape: BoundShape::Box,
+            },
+            system_ids: vec![outside_id],
+        };
+        let sector_id = sector.id;
+        galaxy.add_sector(sector).unwrap();
+
+        galaxy.assign_sectors();
+        assert_eq!(galaxy.sectors[0].system_ids, vec![inside_id]);
+        assert_eq!(galaxy.sector_for_system(inside_id).map(|s| s.id), Some(sector_id));
+        assert!(galaxy.sector_for_system(outside_id).is_none());
+    }
+
+    #[test]
+    fn test_validate_hierarchy_reports_misparented_moon() {
+        let mut system = SystemBuilder::new("Test")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Terra", 1.0).with_moon(MoonBuilder::new("Luna")))
+            .build();
+        assert_eq!(system.validate_hierarchy(), vec![]);
+
+        // Moon pointing at a body that is not in the system
+        let stray = Id::new_v4();
+        system.planets[0].children[0].spatial_parent = SpatialParent::Body(stray);
+        assert_eq!(
+            system.validate_hierarchy(),
+            vec![HierarchyViolation::MissingParent { body: "Luna".into(), parent_id: stray }]
+        );
+
+        // Moon moved directly under the star, which cannot hold moons
+        let mut moon = system.planets[0].children.remove(0);
+        moon.spatial_parent = SpatialParent::Body(system.stars[0].id);
+        system.stars[0].children.push(moon);
+        let violations = system.validate_hierarchy();
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(&violations[0], HierarchyViolation::InvalidChild { body, kind: BodyKind::Moon, .. } if body == "Luna"));
+    }
+
+    #[test]
+    fn test_remove_system_prunes_frames() {
+        let mut galaxy = Galaxy::new("Test");
+        let kept = SystemBuilder::new("Kept").with_star("G2V").build();
+        let removed = SystemBuilder::new("Removed").with_star("K1V").build();
+        let (kept_id, removed_id) = (kept.id, removed.id);
+        let (kept_frame, removed_frame) = (kept.frame_id, removed.frame_id);
+        galaxy.add_star_system(kept).unwrap();
+        galaxy.add_star_system(removed).unwrap();
+
+        // Planet frame hanging off the system frame goes with it
+        let planet_frame = Frame::new_planetary("Planet", removed_frame, Id::new_v4(), OffsetDateTime::now_utc());
+        let planet_frame_id = planet_frame.id;
+        galaxy.frame_hierarchy.add_frame(planet_frame).unwrap();
+
+        let system = galaxy.remove_system(removed_id).unwrap();
+        assert_eq!(system.name, "Removed");
+        assert_eq!(galaxy.star_systems.len(), 1);
+        assert_eq!(galaxy.metadata.total_stars, 1);
+        assert!(galaxy.frame_hierarchy.get_frame(removed_frame).is_none());
+        assert!(galaxy.frame_hierarchy.get_frame(planet_frame_id).is_none());
+        assert!(galaxy.frame_hierarchy.get_frame(kept_frame).is_some());
+        assert!(galaxy.find_system(kept_id).is_some());
+
+        assert!(matches!(galaxy.remove_system(removed_id), Err(ContainerError::ChildNotFound(_))));
+    }
+
+    #[test]
+    fn test_merge_policies() {
+        let galaxy = |names: &[&str]| {
+            let mut galaxy = Galaxy::new("Part");
+            for name in names {
+                galaxy.add_star_system(SystemBuilder::new(*name).with_star("G2V").build()).unwrap();
+            }
+            galaxy
+        };
+
+        let mut kept = galaxy(&["Alpha", "Beta"]);
+        let summary = kept.merge(galaxy(&["beta", "Gamma"]), MergePolicy::KeepExisting);
+        assert_eq!(summary, MergeSummary { added: 1, skipped: 1, ..Default::default() });
+        assert_eq!(kept.star_systems.len(), 3);
+
+        let mut replaced = galaxy(&["Alpha", "Beta"]);
+        replaced.merge(galaxy(&["beta"]), MergePolicy::Replace);
+        let names: Vec<&str> = replaced.star_systems.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Alpha", "beta"]);
+
+        let mut renamed = galaxy(&["Alpha"]);
+        let copy = renamed.clone();
+        let summary = renamed.merge(copy, MergePolicy::Rename);
+        assert_eq!(summary, MergeSummary { renamed: 1, ..Default::default() });
+        assert_eq!(renamed.star_systems[1].name, "Alpha (2)");
+        assert_ne!(renamed.star_systems[0].id, renamed.star_systems[1].id);
+
+        // Same id under another name: re-identified but not renamed
+        let mut reidentified = galaxy(&["Alpha"]);
+        let mut copy = reidentified.clone();
+        copy.star_systems[0].name = "Alpha Prime".to_string();
+        let summary = reidentified.merge(copy, MergePolicy::Rename);
+        assert_eq!(summary, MergeSummary { added: 1, ..Default::default() });
+        assert_eq!(reidentified.star_systems[1].name, "Alpha Prime");
+        assert_ne!(reidentified.star_systems[0].id, reidentified.star_systems[1].id);
+
+        assert_eq!("Keep".parse::<MergePolicy>(), Ok(MergePolicy::KeepExisting));
+        assert!("merge".parse::<MergePolicy>().is_err());
+    }
+
+    #[test]
+    fn test_two_to_one_resonance() {
+        // Kepler's third law: doubling the period scales the orbit by 2^(2/3)
+        let system = SystemBuilder::new("Resonant")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Inner", 1.0))
+            .with_planet(PlanetBuilder::terrestrial("Outer", 2f64.powf(2.0 / 3.0)))
+            .with_planet(PlanetBuilder::terrestrial("Loner", 7.3))
+            .build();
+        let (inner, outer) = (system.planets[0].id, system.planets[1].id);
+
+        let resonances = system.find_resonances(0.01);
+        // The distant planet's ~20:1 and ~10:1 ratios are too high-order to count
+        assert_eq!(resonances, vec![(inner, outer, 2, 1)]);
+    }
+
+    #[test]
+    fn test_tightly_packed_giants_are_unstable() {
+        let system = SystemBuilder::new("Crowded")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::gas_giant("Near", 1.0))
+            .with_planet(PlanetBuilder::gas_giant("Far", 1.1))
+            .with_planet(PlanetBuilder::terrestrial("Distant", 3.0))
+            .build();
+
+        let warnings = system.check_orbital_stability();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!((warnings[0].inner, warnings[0].outer), (system.planets[0].id, system.planets[1].id));
+        assert!(warnings[0].mutual_hill_separation < 2.0);
+
+        let spaced = SystemBuilder::new("Spaced")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Earth", 1.0))
+            .with_planet(PlanetBuilder::terrestrial("Mars", 1.52))
+            .build();
+        assert!(spaced.check_orbital_stability().is_empty());
+    }
+}
@@ -1,7 +1,8 @@
 // Proper astronomical coordinate systems for StellarForge
 // Following IAU standards and conventions
 
-use crate::stellar_forge::core::{Vec3, Units};
+use crate::stellar_forge::core::{Vec3, Units, Quaternion};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::f64::consts::PI;
 
@@ -158,6 +159,17 @@ impl EquatorialCoordinates {
     }
 }
 
+/// Velocity relative to the Sun, in galactic UVW axes (m/s)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HeliocentricVelocity(pub Vec3);
+
+/// Velocity relative to the Local Standard of Rest, in galactic UVW axes (m/s)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LsrVelocity(pub Vec3);
+
+/// Mean obliquity of the ecliptic at J2000.0 (IAU 2006)
+pub const J2000_OBLIQUITY_DEG: f64 = 23.439_279_4;
+
 /// Coordinate transformation matrices and methods
 pub struct CoordinateTransform;
 
@@ -170,12 +182,12 @@ impl CoordinateTransform {
         const T11: f64 = -0.054875539390;
         const T12: f64 = -0.873437104725;
         const T13: f64 = -0.483834991775;
-        const T21: f64 = +0.494109453633;
+        const T21: f64 = 0.494109453633;
         const T22: f64 = -0.444829594298;
-        const T23: f64 = +0.746982248696;
+        const T23: f64 = 0.746982248696;
         const T31: f64 = -0.867666135681;
         const T32: f64 = -0.198076389622;
-        const T33: f64 = +0.455983794523;
+        const T33: f64 = 0.455983794523;
 
         let eq_cart = eq.to_cartesian();
 
@@ -193,12 +205,12 @@ impl CoordinateTransform {
         const T11: f64 = -0.054875539390;
         const T21: f64 = -0.873437104725;
         const T31: f64 = -0.483834991775;
-        const T12: f64 = +0.494109453633;
+        const T12: f64 = 0.494109453633;
         const T22: f64 = -0.444829594298;
-        const T32: f64 = +0.746982248696;
+        const T32: f64 = 0.746982248696;
         const T13: f64 = -0.867666135681;
         const T23: f64 = -0.198076389622;
-        const T33: f64 = +0.455983794523;
+        const T33: f64 = 0.455983794523;
 
         let gal_cart = gal.to_cartesian();
 
@@ -223,6 +235,34 @@ impl CoordinateTransform {
         }
     }
 
+    /// Orientation of the galactic axes in ICRS, i.e. the rotation taking galactic
+    /// cartesian vectors to equatorial ones (transpose of the matrix above)
+    pub fn galactic_to_icrs_rotation() -> Quaternion {
+        let icrs_to_galactic = nalgebra::Matrix3::new(
+            -0.054875539390, -0.873437104725, -0.483834991775,
+            0.494109453633, -0.444829594298, 0.746982248696,
+            -0.867666135681, -0.198076389622, 0.455983794523,
+        );
+        Quaternion::from_matrix(&icrs_to_galactic.transpose())
+    }
+
+    /// Rotate a galactic-frame vector (velocity, proper motion, direction) into equatorial
+    /// axes. Only the orientation changes; lengths are preserved.
+    pub fn rotate_galactic_to_equatorial(v: Vec3) -> Vec3 {
+        Self::galactic_to_icrs_rotation() * v
+    }
+
+    /// Inverse of `rotate_galactic_to_equatorial`
+    pub fn rotate_equatorial_to_galactic(v: Vec3) -> Vec3 {
+        Self::galactic_to_icrs_rotation().inverse() * v
+    }
+
+    /// Orientation of the J2000 ecliptic axes in ICRS, a tilt about the shared x axis
+    /// (vernal equinox) by the obliquity of the ecliptic
+    pub fn ecliptic_to_icrs_rotation() -> Quaternion {
+        Quaternion::from_axis_angle(&Vec3::x_axis(), J2000_OBLIQUITY_DEG.to_radians())
+    }
+
     /// Convert from Astrosynthesis coordinates to standard Galactic
     /// Astrosynthesis uses a different orientation that needs correction
     pub fn astrosynthesis_to_galactic(astro_x: f64, astro_y: f64, astro_z: f64) -> GalacticCoordinates {
@@ -241,6 +281,15 @@ impl CoordinateTransform {
         GalacticCoordinates::from_cartesian(Vec3::new(gal_x, gal_y, gal_z))
     }
 
+    /// Convert many Astrosynthesis positions at once, in parallel
+    /// Results are in input order and identical to calling `astrosynthesis_to_galactic` on each
+    pub fn transform_batch(points: &[(f64, f64, f64)]) -> Vec<GalacticCoordinates> {
+        points
+            .par_iter()
+            .map(|&(x, y, z)| Self::astrosynthesis_to_galactic(x, y, z))
+            .collect()
+    }
+
     /// Convert from standard Galactic to Astrosynthesis coordinates
     pub fn galactic_to_astrosynthesis(gal: GalacticCoordinates) -> (f64, f64, f64) {
         let cart = gal.to_cartesian();
@@ -253,22 +302,23 @@ impl CoordinateTransform {
         (astro_x, astro_y, astro_z)
     }
 
+    /// Sun's motion relative to the LSR in galactic UVW axes, m/s (Schönrich et al. 2010)
+    /// U = 11.1 km/s (toward galactic center)
+    /// V = 12.24 km/s (in direction of rotation)
+    /// W = 7.25 km/s (toward NGP)
+    pub fn solar_peculiar_motion() -> Vec3 {
+        Vec3::new(11100.0, 12240.0, 7250.0)
+    }
+
     /// Apply Local Standard of Rest (LSR) correction
-    /// Corrects for the Sun's peculiar motion relative to nearby stars
-    pub fn apply_lsr_correction(vel: Vec3) -> Vec3 {
-        // Sun's motion relative to LSR (Schönrich et al. 2010)
-        // U = 11.1 km/s (toward galactic center)
-        // V = 12.24 km/s (in direction of rotation)
-        // W = 7.25 km/s (toward NGP)
-        const U_SOLAR: f64 = 11100.0;  // m/s
-        const V_SOLAR: f64 = 12240.0;  // m/s
-        const W_SOLAR: f64 = 7250.0;   // m/s
+    /// Adds back the Sun's peculiar motion so the velocity is measured against nearby stars
+    pub fn apply_lsr_correction(vel: HeliocentricVelocity) -> LsrVelocity {
+        LsrVelocity(vel.0 + Self::solar_peculiar_motion())
+    }
 
-        Vec3::new(
-            vel.x - U_SOLAR,
-            vel.y - V_SOLAR,
-            vel.z - W_SOLAR,
-        )
+    /// Inverse of `apply_lsr_correction`: a star at rest in the LSR shows the Sun's reflex motion
+    pub fn remove_lsr_correction(vel: LsrVelocity) -> HeliocentricVelocity {
+        HeliocentricVelocity(vel.0 - Self::solar_peculiar_motion())
     }
 
     /// Get Sun's position in galactic coordinates
@@ -289,7 +339,7 @@ impl CoordinateTransform {
         EquatorialCoordinates::new(
             12.0 + 51.0/60.0 + 26.28/3600.0,  // hours
             27.0 + 7.0/60.0 + 41.7/3600.0,    // degrees
-            f64::INFINITY,  // At infinity
+            1.0,  // Direction only; an infinite distance turns the cartesian form into NaN
         )
     }
 }
@@ -389,6 +439,38 @@ impl CoordinateFormatter {
     }
 }
 
+/// A distance in meters that displays in the most readable astronomical unit
+/// Under a tenth of a light-year prints in AU, under 1000 ly in light-years, under
+/// 1000 pc in parsecs and anything farther in kiloparsecs, with about three significant digits.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Distance(pub f64);
+
+impl std::fmt::Display for Distance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let meters = self.0;
+        let (value, unit) = if meters.abs() < 0.1 * Units::LIGHT_YEAR {
+            (meters / Units::AU, "AU")
+        } else if meters.abs() < 1000.0 * Units::LIGHT_YEAR {
+            (meters / Units::LIGHT_YEAR, "ly")
+        } else if meters.abs() < 1000.0 * Units::PARSEC {
+            (meters / Units::PARSEC, "pc")
+        } else {
+            (meters / (1000.0 * Units::PARSEC), "kpc")
+        };
+        let decimals = match value.abs() {
+            v if v < 10.0 => 2,
+            v if v < 100.0 => 1,
+            _ => 0,
+        };
+        write!(f, "{:.*} {}", decimals, value, unit)
+    }
+}
+
+/// Format a distance in meters with an automatically chosen unit, see `Distance`
+pub fn format_distance(meters: f64) -> String {
+    Distance(meters).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,4 +509,62 @@ mod tests {
         let alpha_cen = ReferencePositions::alpha_centauri();
         assert!((alpha_cen.distance_pc() - 1.34).abs() < 0.01);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_transform_batch_matches_sequential() {
+        let points: Vec<(f64, f64, f64)> = (0..10_000)
+            .map(|i| {
+                let t = i as f64;
+                (t.sin() * 500.0, (t * 0.37).cos() * 80.0, t * 0.05 - 250.0)
+            })
+            .collect();
+
+        let batched = CoordinateTransform::transform_batch(&points);
+        assert_eq!(batched.len(), points.len());
+
+        for (&(x, y, z), gal) in points.iter().zip(&batched) {
+            let expected = CoordinateTransform::astrosynthesis_to_galactic(x, y, z);
+            assert_eq!(gal.longitude_rad.to_bits(), expected.longitude_rad.to_bits());
+            assert_eq!(gal.latitude_rad.to_bits(), expected.latitude_rad.to_bits());
+            assert_eq!(gal.distance_m.to_bits(), expected.distance_m.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_vector_rotation_round_trip() {
+        for v in [Vec3::x(), Vec3::y(), Vec3::z(), Vec3::new(0.6, -0.48, 0.64)] {
+            let equatorial = CoordinateTransform::rotate_galactic_to_equatorial(v);
+            assert!((equatorial.norm() - 1.0).abs() < 1e-12);
+
+            let back = CoordinateTransform::rotate_equatorial_to_galactic(equatorial);
+            assert!((back - v).norm() < 1e-12);
+        }
+
+        // Same orientation as the position transform, without the distance
+        let velocity = Vec3::new(0.0, 0.0, 7250.0);
+        let rotated = CoordinateTransform::rotate_galactic_to_equatorial(velocity);
+        let pole = CoordinateTransform::galactic_to_equatorial(GalacticCoordinates::new(0.0, 90.0, 1.0));
+        assert!((rotated / 7250.0 - pole.to_cartesian() / pole.distance_m).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_lsr_correction_round_trip() {
+        let heliocentric = HeliocentricVelocity(Vec3::new(20000.0, -15000.0, 3000.0));
+        let lsr = CoordinateTransform::apply_lsr_correction(heliocentric);
+        assert_eq!(CoordinateTransform::remove_lsr_correction(lsr), heliocentric);
+
+        let at_rest = CoordinateTransform::remove_lsr_correction(LsrVelocity(Vec3::zeros()));
+        assert_eq!(at_rest.0, -CoordinateTransform::solar_peculiar_motion());
+        assert_eq!(CoordinateTransform::apply_lsr_correction(at_rest).0, Vec3::zeros());
+    }
+
+    #[test]
+    fn test_format_distance_picks_unit() {
+        assert_eq!(format_distance(Units::AU), "1.00 AU");
+        assert_eq!(format_distance(4.0 * Units::LIGHT_YEAR), "4.00 ly");
+        assert_eq!(format_distance(8000.0 * Units::PARSEC), "8.00 kpc");
+        assert_eq!(format_distance(1e6 * Units::LIGHT_YEAR), "307 kpc");
+        assert_eq!(format_distance(500.0 * Units::PARSEC), "500 pc");
+        assert_eq!(format_distance(42.5 * Units::LIGHT_YEAR), "42.5 ly");
+    }
+}
@@ -10,6 +10,11 @@ pub type Id = Uuid;
 pub type Vec3 = nalgebra::Vector3<f64>;
 pub type Quaternion = nalgebra::UnitQuaternion<f64>;
 
+// Random (v4-shaped) id drawn from a caller-supplied RNG, for reproducible generation
+pub fn seeded_id<R: rand::Rng + ?Sized>(rng: &mut R) -> Id {
+    uuid::Builder::from_random_bytes(rng.gen()).into_uuid()
+}
+
 // Universal units (SI internally, display conversions as needed)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Units;
@@ -23,6 +28,7 @@ impl Units {
     pub const SOLAR_MASS: f64 = 1.989e30;  // Solar mass in kg
     pub const EARTH_MASS: f64 = 5.972e24;  // Earth mass in kg
     pub const SOLAR_RADIUS: f64 = 6.96e8;  // Solar radius in meters
+    pub const SOLAR_LUMINOSITY: f64 = 3.828e26;  // Nominal solar luminosity in watts
     pub const EARTH_RADIUS: f64 = 6.371e6;  // Earth radius in meters
 }
 
@@ -41,6 +47,52 @@ pub struct Transform {
     pub angular_velocity_rps: Option<Vec3>,  // radians per second
 }
 
+impl Transform {
+    pub fn identity() -> Self {
+        Self {
+            translation_m: Vec3::zeros(),
+            rotation: Quaternion::identity(),
+            angular_velocity_rps: None,
+        }
+    }
+
+    // Single transform equal to applying `self` and then `outer`. None when `outer` rotates
+    // about an origin `self` has moved away from, which adds a velocity offset no Transform can hold.
+    pub fn then(&self, outer: &Transform) -> Option<Transform> {
+        if outer.is_rotating() && self.translation_m != Vec3::zeros() {
+            return None;
+        }
+        let angular_velocity_rps = match (self.angular_velocity_rps, outer.angular_velocity_rps) {
+            (None, None) => None,
+            (inner, outer_omega) => Some(
+                outer.rotation * inner.unwrap_or_else(Vec3::zeros) + outer_omega.unwrap_or_else(Vec3::zeros),
+            ),
+        };
+        Some(Transform {
+            translation_m: outer.rotation * self.translation_m + outer.translation_m,
+            rotation: outer.rotation * self.rotation,
+            angular_velocity_rps,
+        })
+    }
+
+    // Undo this transform; None for a rotating frame with a translated origin, for the same reason as `then`
+    pub fn inverse(&self) -> Option<Transform> {
+        if self.is_rotating() && self.translation_m != Vec3::zeros() {
+            return None;
+        }
+        let rotation = self.rotation.inverse();
+        Some(Transform {
+            translation_m: -(rotation * self.translation_m),
+            rotation,
+            angular_velocity_rps: self.angular_velocity_rps.map(|omega| -(rotation * omega)),
+        })
+    }
+
+    fn is_rotating(&self) -> bool {
+        self.angular_velocity_rps.is_some_and(|omega| omega != Vec3::zeros())
+    }
+}
+
 // Core identification trait
 pub trait Identified {
     fn id(&self) -> Id;
@@ -164,6 +216,9 @@ pub enum CoordinateError {
     #[error("Cannot transform between disconnected frames")]
     DisconnectedFrames,
 
+    #[error("Frame {0} still has child frames")]
+    FrameHasChildren(Id),
+
     #[error("Coordinate transformation failed: {0}")]
     TransformFailed(String),
 }
@@ -186,7 +186,7 @@ async fn create_group_tables(pool: &Pool<Postgres>) -> Result<()> {
     Ok(())
 }
 
-async fn create_functions(pool: &Pool<Postgres>) -> Result<()> {
+async fn create_functions(_pool: &Pool<Postgres>) -> Result<()> {
     // Functions are created in the table SQL files
     Ok(())
 }
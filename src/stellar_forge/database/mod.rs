@@ -6,7 +6,10 @@ pub mod models;
 pub mod queries;
 pub mod migrations;
 
-pub use connection::{DatabaseConnection, ConnectionPool};
+pub use connection::{
+    DatabaseConnection, ConnectionPool, PoolConfig, DEFAULT_DATABASE_URL, resolve_database_url,
+    redact_database_url, retry_backoff_schedule, MAX_RETRY_BACKOFF,
+};
 pub use repository::{
     SessionRepository,
     SystemRepository,
@@ -22,7 +25,7 @@ use anyhow::Result;
 /// Initialize the database with all required tables
 pub async fn initialize_database(database_url: &str) -> Result<()> {
     let pool = ConnectionPool::new(database_url).await?;
-    migrations::run_migrations(&pool).await?;
+    migrations::run_migrations(pool.pool()).await?;
     Ok(())
 }
 
@@ -3,7 +3,6 @@
 use anyhow::Result;
 use sqlx::{Pool, Postgres, Row};
 use uuid::Uuid;
-use serde_json::Value as JsonValue;
 
 /// Spatial query examples demonstrating PostGIS capabilities
 pub struct SpatialQueries<'a> {
@@ -647,8 +646,6 @@ impl<'a> AnalyticalQueries<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     // Note: These tests would require a running PostgreSQL instance with PostGIS
     // and would be marked with #[ignore] in a real implementation
 }
\ No newline at end of file
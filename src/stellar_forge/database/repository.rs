@@ -1,7 +1,7 @@
 // Repository layer for database operations
 
 use anyhow::Result;
-use sqlx::{Pool, Postgres, Row};
+use sqlx::Row;
 use uuid::Uuid;
 
 use super::connection::ConnectionPool;
@@ -447,6 +447,52 @@ impl<'a> PoliticalRepository<'a> {
         Ok(())
     }
 
+    /// Export all influence zones in a session as a GeoJSON FeatureCollection
+    /// One feature per entity: its zones are unioned and flattened onto the x-y plane
+    /// with coordinates converted from meters to light-years and the entity id and name as properties.
+    pub async fn export_zones_geojson(&self, session_id: Uuid) -> Result<String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                pe.id,
+                pe.name,
+                ST_AsGeoJSON(
+                    ST_Scale(ST_Force2D(ST_Union(iz.zone_geometry)), 1.0 / 9.461e15, 1.0 / 9.461e15)
+                ) AS geometry
+            FROM political.influence_zones iz
+            JOIN political.entities pe ON pe.id = iz.political_entity_id
+            WHERE iz.session_id = $1
+            GROUP BY pe.id, pe.name
+            ORDER BY pe.name
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(self.pool.pool())
+        .await?;
+
+        let mut features = Vec::with_capacity(rows.len());
+        for row in rows {
+            let entity_id: Uuid = row.get("id");
+            let name: String = row.get("name");
+            let geometry: String = row.get("geometry");
+
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "geometry": serde_json::from_str::<serde_json::Value>(&geometry)?,
+                "properties": {
+                    "entity_id": entity_id.to_string(),
+                    "name": name,
+                },
+            }));
+        }
+
+        let collection = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": features,
+        });
+        Ok(serde_json::to_string(&collection)?)
+    }
+
     /// Find disputed zones
     pub async fn find_disputed_zones(&self, session_id: Uuid) -> Result<Vec<(Uuid, Uuid)>> {
         let rows = sqlx::query(
@@ -577,4 +623,47 @@ impl<'a> GroupRepository<'a> {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL with PostGIS and the StellarForge schema
+    async fn test_export_zones_geojson() {
+        let url = crate::stellar_forge::database::resolve_database_url(None);
+        let pool = ConnectionPool::new(&url).await.unwrap();
+
+        let session_id = SessionRepository::new(&pool)
+            .create_session("GeoJSON export test", None, "test")
+            .await
+            .unwrap();
+        let systems = SystemRepository::new(&pool);
+        let political = PoliticalRepository::new(&pool);
+
+        for (name, x) in [("Federation", 0.0), ("Empire", 40.0)] {
+            let entity_id = political.create_entity(session_id, name, "republic").await.unwrap();
+            for offset in [0.0, 3.0] {
+                let system_id = systems
+                    .create_system(session_id, &format!("{} {}", name, offset), x + offset, 0.0, 0.0, "single")
+                    .await
+                    .unwrap();
+                political
+                    .add_system_control(session_id, entity_id, system_id, "controlled", 1.0)
+                    .await
+                    .unwrap();
+            }
+            political.generate_influence_zone(session_id, entity_id, 5.0).await.unwrap();
+        }
+
+        let geojson = political.export_zones_geojson(session_id).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+
+        assert_eq!(parsed["type"], "FeatureCollection");
+        let features = parsed["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0]["properties"]["name"], "Empire");
+        assert!(features.iter().all(|f| f["geometry"]["type"].as_str().unwrap().ends_with("Polygon")));
+    }
+}
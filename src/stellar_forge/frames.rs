@@ -1,8 +1,10 @@
 // Coordinate frames and transformations for StellarForge
 
+use crate::stellar_forge::coordinates::CoordinateTransform;
 use crate::stellar_forge::core::{Id, State, Transform, Vec3, Quaternion, CoordinateError};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
 use time::OffsetDateTime;
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
@@ -23,6 +25,8 @@ pub enum FrameKind {
     Orbital,
     /// Local frame for stations/ships
     LocalVehicle,
+    /// Ecliptic J2000 (Earth's orbital plane, X toward the vernal equinox)
+    Ecliptic,
     /// Legacy Astrosynthesis coordinates (for import)
     AstrosynthesisLegacy,
     /// User-defined frames
@@ -102,6 +106,25 @@ impl Frame {
         }
     }
 
+    /// Create J2000 ecliptic frame
+    pub fn new_ecliptic(name: impl Into<String>) -> Self {
+        Self {
+            id: Id::new_v4(),
+            name: name.into(),
+            kind: FrameKind::Ecliptic,
+            parent: None,
+            epoch: time::macros::datetime!(2000-01-01 12:00:00 UTC),
+            to_parent: None,
+            coordinate_system: CoordinateSystem::Cartesian,
+            metadata: FrameMetadata {
+                description: Some("J2000 ecliptic frame (X toward the vernal equinox)".into()),
+                is_inertial: true,
+                primary_body: None,
+                orientation_reference: Some("J2000".into()),
+            },
+        }
+    }
+
     pub fn new_barycentric(
         name: impl Into<String>,
         parent: Id,
@@ -158,15 +181,17 @@ impl Frame {
 }
 
 // Frame hierarchy and transformation service
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct FrameHierarchy {
-    frames: HashMap<Id, Frame>,
+    frames: BTreeMap<Id, Frame>,  // Ordered so serialization is stable
+    #[serde(skip)]
     cache: TransformCache,
 }
 
 impl FrameHierarchy {
     pub fn new() -> Self {
         Self {
-            frames: HashMap::new(),
+            frames: BTreeMap::new(),
             cache: TransformCache::new(),
         }
     }
@@ -193,6 +218,15 @@ impl FrameHierarchy {
         self.frames.get_mut(&id)
     }
 
+    pub fn frames(&self) -> impl Iterator<Item = &Frame> {
+        self.frames.values()
+    }
+
+    // First frame of the given kind, e.g. the galactic root
+    pub fn find_frame_by_kind(&self, kind: FrameKind) -> Option<&Frame> {
+        self.frames.values().find(|f| f.kind == kind)
+    }
+
     // Transform a state from one frame to another
     pub fn transform_state(
         &self,
@@ -205,60 +239,155 @@ impl FrameHierarchy {
             return Ok(state);
         }
 
+        if let Some(transform) = self.cache.get(from_frame, to_frame) {
+            return Ok(self.apply_transform(state, &transform, epoch));
+        }
+        match self.composite_transform(from_frame, to_frame) {
+            Ok(transform) => {
+                let result = self.apply_transform(state, &transform, epoch);
+                self.cache.store(from_frame, to_frame, transform);
+                return Ok(result);
+            }
+            // Rotating frames that don't compose are walked one step at a time below
+            Err(CoordinateError::TransformFailed(_)) => {}
+            Err(e) => return Err(e),
+        }
+
         // Find path from source to target through common ancestor
         let path = self.find_transform_path(from_frame, to_frame)?;
 
         let mut current_state = state;
 
         // Apply transforms along the path
-        for (frame_id, direction) in path {
-            let frame = self.frames.get(&frame_id)
-                .ok_or(CoordinateError::FrameNotFound(frame_id))?;
+        for (transform, direction) in path {
+            current_state = match direction {
+                TransformDirection::ToParent => {
+                    self.apply_transform(current_state, &transform, epoch)
+                }
+                TransformDirection::FromParent => {
+                    self.apply_inverse_transform(current_state, &transform, epoch)
+                }
+            };
+        }
+
+        Ok(current_state)
+    }
+
+    // Single transform taking coordinates in `from_frame` to `to_frame`
+    pub fn composite_transform(&self, from_frame: Id, to_frame: Id) -> Result<Transform, CoordinateError> {
+        let mut composite = Transform::identity();
+        for (transform, direction) in self.find_transform_path(from_frame, to_frame)? {
+            let step = match direction {
+                TransformDirection::ToParent => Some(transform),
+                TransformDirection::FromParent => transform.inverse(),
+            };
+            composite = step
+                .and_then(|step| composite.then(&step))
+                .ok_or_else(|| CoordinateError::TransformFailed(
+                    "Rotating frames along the path cannot be combined into one transform".into(),
+                ))?;
+        }
+        Ok(composite)
+    }
+
+    // Remove a frame, moving its children under `reparent_to` with transforms composed so
+    // their coordinates are unchanged. Without a target, a frame with children is refused.
+    pub fn remove_frame(&mut self, id: Id, reparent_to: Option<Id>) -> Result<Frame, CoordinateError> {
+        if !self.frames.contains_key(&id) {
+            return Err(CoordinateError::FrameNotFound(id));
+        }
+        let children: Vec<Id> = self.get_child_frames(id).iter().map(|f| f.id).collect();
 
-            if let Some(transform) = &frame.to_parent {
-                current_state = match direction {
-                    TransformDirection::ToParent => {
-                        self.apply_transform(current_state, transform, epoch)
-                    }
-                    TransformDirection::FromParent => {
-                        self.apply_inverse_transform(current_state, transform, epoch)
-                    }
-                };
+        if !children.is_empty() {
+            let target = reparent_to.ok_or(CoordinateError::FrameHasChildren(id))?;
+            if self.path_to_root(target)?.contains(&id) {
+                return Err(CoordinateError::TransformFailed(
+                    "Cannot reparent frames into the subtree being removed".into(),
+                ));
+            }
+            let to_target = self.composite_transform(id, target)?;
+
+            let mut reparented = Vec::with_capacity(children.len());
+            for child_id in &children {
+                let child = &self.frames[child_id];
+                let to_parent = child.to_parent.clone().unwrap_or_else(Transform::identity)
+                    .then(&to_target)
+                    .ok_or_else(|| CoordinateError::TransformFailed(
+                        format!("Cannot compose the transform of rotating frame {}", child_id),
+                    ))?;
+                reparented.push((*child_id, to_parent));
+            }
+            for (child_id, to_parent) in reparented {
+                if let Some(child) = self.frames.get_mut(&child_id) {
+                    child.parent = Some(target);
+                    child.to_parent = Some(to_parent);
+                }
             }
         }
 
-        Ok(current_state)
+        self.cache.invalidate();
+        Ok(self.frames.remove(&id).expect("frame checked above"))
     }
 
-    // Find the path of transforms needed to go from one frame to another
+    // Transforms to apply, in order, to go from one frame to another. Trees rooted in
+    // different celestial frames (ICRS, galactic, ecliptic) are joined by the fixed rotation
+    // between those roots, which are taken to share an origin.
     fn find_transform_path(
         &self,
         from: Id,
         to: Id,
-    ) -> Result<Vec<(Id, TransformDirection)>, CoordinateError> {
+    ) -> Result<Vec<(Transform, TransformDirection)>, CoordinateError> {
         // Get paths to root for both frames
         let from_path = self.path_to_root(from)?;
         let to_path = self.path_to_root(to)?;
 
-        // Find common ancestor
-        let common_ancestor = self.find_common_ancestor(&from_path, &to_path)?;
+        // Climb to the common ancestor, or to each root when the trees are separate
+        let (up, bridge, down) = match self.find_common_ancestor(&from_path, &to_path) {
+            Ok(common_ancestor) => {
+                let from_common_idx = from_path.iter().position(|&id| id == common_ancestor).unwrap();
+                let to_common_idx = to_path.iter().position(|&id| id == common_ancestor).unwrap();
+                (&from_path[..from_common_idx], None, &to_path[..to_common_idx])
+            }
+            Err(CoordinateError::DisconnectedFrames) => {
+                let bridge = self
+                    .root_rotation(*from_path.last().unwrap(), *to_path.last().unwrap())
+                    .ok_or(CoordinateError::DisconnectedFrames)?;
+                (&from_path[..], Some(bridge), &to_path[..])
+            }
+            Err(e) => return Err(e),
+        };
 
+        let to_parent = |id: &Id| self.frames.get(id).and_then(|f| f.to_parent.clone());
         let mut path = Vec::new();
 
-        // Path from source to common ancestor (going up)
-        for &frame_id in from_path.iter().take_while(|&&id| id != common_ancestor) {
-            path.push((frame_id, TransformDirection::ToParent));
+        // Path from source upward
+        for transform in up.iter().filter_map(to_parent) {
+            path.push((transform, TransformDirection::ToParent));
+        }
+
+        if let Some(rotation) = bridge {
+            path.push((Transform { rotation, ..Transform::identity() }, TransformDirection::ToParent));
         }
 
-        // Path from common ancestor to target (going down)
-        let to_common_idx = to_path.iter().position(|&id| id == common_ancestor).unwrap();
-        for &frame_id in to_path[..to_common_idx].iter().rev() {
-            path.push((frame_id, TransformDirection::FromParent));
+        // Path down to the target
+        for transform in down.iter().rev().filter_map(to_parent) {
+            path.push((transform, TransformDirection::FromParent));
         }
 
         Ok(path)
     }
 
+    // Fixed rotation from one celestial root frame's axes to another's
+    fn root_rotation(&self, from_root: Id, to_root: Id) -> Option<Quaternion> {
+        let orientation_in_icrs = |id: Id| match self.frames.get(&id)?.kind {
+            FrameKind::ICRS => Some(Quaternion::identity()),
+            FrameKind::GalacticIAU => Some(CoordinateTransform::galactic_to_icrs_rotation()),
+            FrameKind::Ecliptic => Some(CoordinateTransform::ecliptic_to_icrs_rotation()),
+            _ => None,
+        };
+        Some(orientation_in_icrs(to_root)?.inverse() * orientation_in_icrs(from_root)?)
+    }
+
     fn path_to_root(&self, mut frame_id: Id) -> Result<Vec<Id>, CoordinateError> {
         let mut path = Vec::new();
         let mut visited = std::collections::HashSet::new();
@@ -292,11 +421,10 @@ impl FrameHierarchy {
 
         // Find where paths diverge
         for i in 0..rev1.len().min(rev2.len()) {
-            if rev1[i] == rev2[i] {
-                if i == rev1.len() - 1 || i == rev2.len() - 1 || rev1[i + 1] != rev2[i + 1] {
+            if rev1[i] == rev2[i]
+                && (i == rev1.len() - 1 || i == rev2.len() - 1 || rev1[i + 1] != rev2[i + 1]) {
                     return Ok(rev1[i]);
                 }
-            }
         }
 
         Err(CoordinateError::DisconnectedFrames)
@@ -346,6 +474,20 @@ impl FrameHierarchy {
             .collect()
     }
 
+    // Remove a frame and all of its descendants, returning what was removed
+    pub fn remove_subtree(&mut self, id: Id) -> Vec<Frame> {
+        let mut removed = Vec::new();
+        let mut pending = vec![id];
+        while let Some(frame_id) = pending.pop() {
+            if let Some(frame) = self.frames.remove(&frame_id) {
+                pending.extend(self.get_child_frames(frame_id).iter().map(|f| f.id));
+                removed.push(frame);
+            }
+        }
+        self.cache.invalidate();
+        removed
+    }
+
     // Convert between coordinate systems
     pub fn convert_coordinates(
         &self,
@@ -393,23 +535,70 @@ enum TransformDirection {
     FromParent,
 }
 
-// Cache for frequently used transforms
+// Cache for frequently used transforms, filled from `&self` lookups
+#[derive(Debug)]
 struct TransformCache {
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Debug)]
+struct CacheEntries {
     transforms: HashMap<(Id, Id), Transform>,
     valid: bool,
+    hits: usize,
 }
 
 impl TransformCache {
     fn new() -> Self {
         Self {
-            transforms: HashMap::new(),
-            valid: true,
+            entries: Mutex::new(CacheEntries {
+                transforms: HashMap::new(),
+                valid: true,
+                hits: 0,
+            }),
         }
     }
 
     fn invalidate(&mut self) {
-        self.valid = false;
-        self.transforms.clear();
+        if let Ok(entries) = self.entries.get_mut() {
+            entries.valid = false;
+            entries.transforms.clear();
+        }
+    }
+
+    fn get(&self, from: Id, to: Id) -> Option<Transform> {
+        let mut entries = self.entries.lock().ok()?;
+        if !entries.valid {
+            return None;
+        }
+        let transform = entries.transforms.get(&(from, to)).cloned()?;
+        entries.hits += 1;
+        Some(transform)
+    }
+
+    fn store(&self, from: Id, to: Id, transform: Transform) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.valid = true;
+            entries.transforms.insert((from, to), transform);
+        }
+    }
+
+    #[cfg(test)]
+    fn hits(&self) -> usize {
+        self.entries.lock().map(|entries| entries.hits).unwrap_or(0)
+    }
+}
+
+// Clones start with an empty cache
+impl Clone for TransformCache {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl Default for TransformCache {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -450,4 +639,108 @@ mod tests {
 
         assert!((cart - back).norm() < 1e-10);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_remove_frame_reparents_children() {
+        let mut hierarchy = FrameHierarchy::new();
+        let galactic = Frame::new_galactic_iau("Milky Way");
+        let galactic_id = galactic.id;
+        hierarchy.add_frame(galactic).unwrap();
+
+        let epoch = OffsetDateTime::now_utc();
+        let system = Frame::new_barycentric("System", galactic_id, Vec3::new(100.0, 0.0, 0.0), epoch);
+        let system_id = system.id;
+        hierarchy.add_frame(system).unwrap();
+
+        // Child rotated a quarter turn about z and offset inside the system frame
+        let mut planet = Frame::new_planetary("Planet", system_id, Id::new_v4(), epoch);
+        planet.to_parent = Some(Transform {
+            translation_m: Vec3::new(0.0, 10.0, 0.0),
+            rotation: Quaternion::from_axis_angle(&Vec3::z_axis(), std::f64::consts::FRAC_PI_2),
+            angular_velocity_rps: None,
+        });
+        let planet_id = planet.id;
+        hierarchy.add_frame(planet).unwrap();
+
+        let state = State { position_m: Vec3::new(1.0, 0.0, 0.0), velocity_mps: Vec3::new(0.0, 0.0, 1.0) };
+        let before = hierarchy.transform_state(state, planet_id, galactic_id, epoch).unwrap();
+
+        assert!(matches!(
+            hierarchy.remove_frame(system_id, None),
+            Err(CoordinateError::FrameHasChildren(id)) if id == system_id
+        ));
+
+        let removed = hierarchy.remove_frame(system_id, Some(galactic_id)).unwrap();
+        assert_eq!(removed.id, system_id);
+        assert!(hierarchy.get_frame(system_id).is_none());
+        assert_eq!(hierarchy.get_frame(planet_id).unwrap().parent, Some(galactic_id));
+
+        let after = hierarchy.transform_state(state, planet_id, galactic_id, epoch).unwrap();
+        assert!((before.position_m - after.position_m).norm() < 1e-9);
+        assert!((after.position_m - Vec3::new(100.0, 11.0, 0.0)).norm() < 1e-9);
+        assert!((before.velocity_mps - after.velocity_mps).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_transform_cache_hits_until_invalidated() {
+        let mut hierarchy = FrameHierarchy::new();
+        let galactic = Frame::new_galactic_iau("Milky Way");
+        let galactic_id = galactic.id;
+        hierarchy.add_frame(galactic).unwrap();
+
+        let epoch = OffsetDateTime::now_utc();
+        let system = Frame::new_barycentric("System", galactic_id, Vec3::new(5.0, 0.0, 0.0), epoch);
+        let system_id = system.id;
+        hierarchy.add_frame(system).unwrap();
+
+        let state = State { position_m: Vec3::new(1.0, 2.0, 3.0), velocity_mps: Vec3::zeros() };
+        let first = hierarchy.transform_state(state, system_id, galactic_id, epoch).unwrap();
+        assert_eq!(hierarchy.cache.hits(), 0);
+        for _ in 0..3 {
+            let again = hierarchy.transform_state(state, system_id, galactic_id, epoch).unwrap();
+            assert_eq!(again.position_m, first.position_m);
+        }
+        assert_eq!(hierarchy.cache.hits(), 3);
+        assert_eq!(first.position_m, Vec3::new(6.0, 2.0, 3.0));
+
+        // Any structural change drops the cached composite
+        let other = Frame::new_barycentric("Other", galactic_id, Vec3::zeros(), epoch);
+        hierarchy.add_frame(other).unwrap();
+        hierarchy.transform_state(state, system_id, galactic_id, epoch).unwrap();
+        assert_eq!(hierarchy.cache.hits(), 3);
+        hierarchy.transform_state(state, system_id, galactic_id, epoch).unwrap();
+        assert_eq!(hierarchy.cache.hits(), 4);
+    }
+
+    #[test]
+    fn test_galactic_pole_in_icrs() {
+        let mut hierarchy = FrameHierarchy::new();
+        let galactic = Frame::new_galactic_iau("Galactic");
+        let icrs = Frame::new_icrs("ICRS");
+        let ecliptic = Frame::new_ecliptic("Ecliptic");
+        let (galactic_id, icrs_id, ecliptic_id) = (galactic.id, icrs.id, ecliptic.id);
+        hierarchy.add_frame(galactic).unwrap();
+        hierarchy.add_frame(icrs).unwrap();
+        hierarchy.add_frame(ecliptic).unwrap();
+
+        let epoch = OffsetDateTime::now_utc();
+        let pole = State { position_m: Vec3::z(), velocity_mps: Vec3::zeros() };
+        let in_icrs = hierarchy.transform_state(pole, galactic_id, icrs_id, epoch).unwrap().position_m;
+
+        // NGP at RA 12h 51m 26.28s, Dec +27° 07' 41.7"
+        let ra_hours = in_icrs.y.atan2(in_icrs.x).rem_euclid(std::f64::consts::TAU).to_degrees() / 15.0;
+        let dec_deg = in_icrs.z.asin().to_degrees();
+        assert!((ra_hours - (12.0 + 51.0 / 60.0 + 26.28 / 3600.0)).abs() < 1e-4, "RA {}h", ra_hours);
+        assert!((dec_deg - (27.0 + 7.0 / 60.0 + 41.7 / 3600.0)).abs() < 1e-3, "Dec {}°", dec_deg);
+
+        // Ecliptic north pole is at RA 18h, Dec 90° - obliquity
+        let ecliptic_pole = hierarchy.transform_state(pole, ecliptic_id, icrs_id, epoch).unwrap().position_m;
+        assert!((ecliptic_pole.z.asin().to_degrees() - (90.0 - 23.4392794)).abs() < 1e-6);
+        assert!(ecliptic_pole.x.abs() < 1e-12 && ecliptic_pole.y < 0.0);
+
+        // Going round through all three frames comes back to the start
+        let via_ecliptic = hierarchy.transform_state(pole, galactic_id, ecliptic_id, epoch).unwrap();
+        let back = hierarchy.transform_state(via_ecliptic, ecliptic_id, galactic_id, epoch).unwrap();
+        assert!((back.position_m - pole.position_m).norm() < 1e-12);
+    }
+}
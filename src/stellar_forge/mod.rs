@@ -9,6 +9,7 @@ pub mod frames;
 pub mod motion;
 pub mod physical;
 pub mod associations;
+pub mod politics;
 pub mod services;
 pub mod builders;
 pub mod storage;
@@ -25,4 +26,28 @@ pub use self::motion::{MotionModel, OrbitalElements};
 pub use self::physical::{Physical, StarPhysical, PlanetPhysical};
 pub use self::associations::{Association, Tag};
 pub use self::services::{NodeService, FrameService, QueryService};
-pub use self::builders::{SystemBuilder, GalaxyBuilder};
\ No newline at end of file
+pub use self::builders::{SystemBuilder, GalaxyBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Touches the modules that used to break the build, including the database-backed
+    // ones that need no live server to construct
+    #[test]
+    fn test_module_builds() {
+        let galaxy = GalaxyBuilder::new("Smoke")
+            .with_system(SystemBuilder::new("Sol").with_star("G2V"))
+            .build();
+        let dataset = storage::StellarForgeDataset::new(galaxy);
+        assert_eq!(dataset.metadata.statistics.total_systems, 1);
+
+        let mut service = services::StellarForgeService::new("Smoke");
+        service.initialize().unwrap();
+        assert!(service.galaxy.star_systems.is_empty());
+
+        let config = import::ImportConfig::default();
+        assert!(!config.database_url.is_empty());
+        assert_eq!(database::PoolConfig::default().max_connections, 10);
+    }
+}
@@ -1,6 +1,6 @@
 // Motion models and orbital mechanics for StellarForge
 
-use crate::stellar_forge::core::{State, Vec3, Units};
+use crate::stellar_forge::core::{State, Vec3};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -30,6 +30,19 @@ impl MotionModel {
         }
     }
 
+    // Time the model is anchored at: its elements' or initial state's epoch, or the first
+    // ephemeris sample. None for N-body motion, which only ever steps relative to now.
+    pub fn epoch(&self) -> Option<OffsetDateTime> {
+        match self {
+            MotionModel::Keplerian(elements) => Some(elements.epoch),
+            MotionModel::Free(free) => Some(free.epoch),
+            MotionModel::TableEphemeris(table) => table.samples.first().map(|sample| sample.epoch),
+            MotionModel::Scripted(scripted) => Some(scripted.epoch),
+            MotionModel::TwoBody(twobody) => Some(twobody.elements.epoch),
+            MotionModel::NBody(_) => None,
+        }
+    }
+
     pub fn orbital_period_s(&self) -> Option<f64> {
         match self {
             MotionModel::Keplerian(e) => Some(e.orbital_period()),
@@ -53,6 +66,7 @@ pub struct OrbitalElements {
 }
 
 impl OrbitalElements {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         a: f64,
         e: f64,
@@ -190,7 +204,11 @@ impl OrbitalElements {
     }
 
     // Propagate orbital elements by time dt (seconds)
-    pub fn propagate(&self, _initial: State, dt: f64) -> State {
+    pub fn propagate(&self, initial: State, dt: f64) -> State {
+        if self.is_hyperbolic() {
+            return self.propagate_hyperbolic(initial, dt);
+        }
+
         // Mean motion
         let n = (self.gravitational_param_m3s2 / (self.semi_major_axis_m.powi(3))).sqrt();
 
@@ -232,6 +250,48 @@ impl OrbitalElements {
         }
     }
 
+    // Hyperbolic counterpart of propagate, with |a| as the scale and the hyperbolic anomaly H
+    // solving M = e sinh H - H
+    fn propagate_hyperbolic(&self, _initial: State, dt: f64) -> State {
+        let a = self.semi_major_axis_m.abs();
+        let e = self.eccentricity;
+        let mu = self.gravitational_param_m3s2;
+
+        let m = self.mean_anomaly_rad + (mu / a.powi(3)).sqrt() * dt;
+        let h_anom = Self::solve_hyperbolic_kepler(m, e);
+        let nu = 2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (h_anom / 2.0).tanh()).atan();
+
+        let p = a * (e * e - 1.0);
+        let r = p / (1.0 + e * nu.cos());
+        let pos_orbital = Vec3::new(r * nu.cos(), r * nu.sin(), 0.0);
+
+        // Radial and transverse speeds from the semi-latus rectum
+        let k = (mu / p).sqrt();
+        let (vr, vtheta) = (k * e * nu.sin(), k * (1.0 + e * nu.cos()));
+        let vel_orbital = Vec3::new(
+            vr * nu.cos() - vtheta * nu.sin(),
+            vr * nu.sin() + vtheta * nu.cos(),
+            0.0,
+        );
+
+        let (position_m, velocity_mps) = self.orbital_to_inertial(pos_orbital, vel_orbital);
+        State { position_m, velocity_mps }
+    }
+
+    // Solve the hyperbolic Kepler equation M = e sinh(H) - H by Newton's method
+    fn solve_hyperbolic_kepler(m: f64, e: f64) -> f64 {
+        let mut h_anom = (m / e).asinh();
+        for _ in 0..100 {
+            let f = e * h_anom.sinh() - h_anom - m;
+            let dh = f / (e * h_anom.cosh() - 1.0);
+            h_anom -= dh;
+            if dh.abs() < 1e-10 {
+                break;
+            }
+        }
+        h_anom
+    }
+
     // Transform from orbital plane to inertial coordinates
     fn orbital_to_inertial(&self, r_orbital: Vec3, v_orbital: Vec3) -> (Vec3, Vec3) {
         let cos_omega = self.longitude_ascending_rad.cos();
@@ -296,8 +356,7 @@ impl OrbitalElements {
     // Convert eccentric anomaly to true anomaly
     fn eccentric_to_true_anomaly(&self, e_anom: f64, e: f64) -> f64 {
         let beta = e / (1.0 + (1.0 - e * e).sqrt());
-        let nu = e_anom + 2.0 * (beta * e_anom.sin() / (1.0 - beta * e_anom.cos())).atan();
-        nu
+        e_anom + 2.0 * (beta * e_anom.sin() / (1.0 - beta * e_anom.cos())).atan()
     }
 
     // Convert true anomaly to eccentric anomaly
@@ -326,9 +385,18 @@ impl OrbitalElements {
         self.semi_major_axis_m * (1.0 + self.eccentricity)
     }
 
-    // Get periapsis distance
+    // Get periapsis distance (hyperbolic orbits may store a as a positive magnitude)
     pub fn periapsis(&self) -> f64 {
-        self.semi_major_axis_m * (1.0 - self.eccentricity)
+        (self.semi_major_axis_m * (1.0 - self.eccentricity)).abs()
+    }
+
+    // Position and velocity at periapsis, for any eccentricity: r = q, v = sqrt(mu (1 + e) / q)
+    pub fn periapsis_state(&self) -> State {
+        let q = self.periapsis();
+        let speed = (self.gravitational_param_m3s2 * (1.0 + self.eccentricity) / q).sqrt();
+        let (position_m, velocity_mps) =
+            self.orbital_to_inertial(Vec3::new(q, 0.0, 0.0), Vec3::new(0.0, speed, 0.0));
+        State { position_m, velocity_mps }
     }
 
     // Check if orbit is hyperbolic
@@ -446,7 +514,7 @@ pub struct ScriptedMotion {
 }
 
 impl ScriptedMotion {
-    pub fn evaluate(&self, epoch: OffsetDateTime) -> State {
+    pub fn evaluate(&self, _epoch: OffsetDateTime) -> State {
         // This would call out to a scripting system
         // For now, return a default state
         State {
@@ -568,7 +636,7 @@ mod tests {
         );
 
         let e_anom = elements.solve_kepler(0.5, 0.1);
-        let m_check = elements.eccentric_to_mean_anomaly(e_anom, 0.1);
+        let m_check = OrbitalElements::eccentric_to_mean_anomaly(e_anom, 0.1);
 
         assert!((m_check - 0.5).abs() < 1e-9);
     }
@@ -55,6 +55,37 @@ impl Physical {
             _ => None,
         }
     }
+
+    // Surface gravity, using a planet's stored value when it has one and GM/R² otherwise.
+    // Belts and stations have no surface, so they return None.
+    pub fn surface_gravity_mps2(&self) -> Option<f64> {
+        match self {
+            Physical::Planet(p) | Physical::Moon(p) if p.gravity_mps2 > 0.0 => Some(p.gravity_mps2),
+            Physical::Belt(_) | Physical::Station(_) => None,
+            _ => {
+                let (mass, radius) = self.surface_mass_and_radius()?;
+                Some(6.67430e-11 * mass / (radius * radius))
+            }
+        }
+    }
+
+    // Escape velocity from the surface, stored or computed as sqrt(2GM/R)
+    pub fn escape_velocity_mps(&self) -> Option<f64> {
+        match self {
+            Physical::Planet(p) | Physical::Moon(p) if p.escape_velocity_mps > 0.0 => Some(p.escape_velocity_mps),
+            Physical::Belt(_) | Physical::Station(_) => None,
+            _ => {
+                let (mass, radius) = self.surface_mass_and_radius()?;
+                Some((2.0 * 6.67430e-11 * mass / radius).sqrt())
+            }
+        }
+    }
+
+    fn surface_mass_and_radius(&self) -> Option<(f64, f64)> {
+        let mass = self.mass_kg().filter(|m| *m > 0.0)?;
+        let radius = self.radius_m().filter(|r| *r > 0.0)?;
+        Some((mass, radius))
+    }
 }
 
 // Star physical properties
@@ -76,7 +107,7 @@ pub struct StarPhysical {
 impl StarPhysical {
     // Create star properties from spectral type string (e.g., "G2V")
     pub fn from_spectral_type(spectral: &str) -> Self {
-        let (class, subclass, luminosity) = parse_spectral_type(spectral);
+        let SpectralTypeParts { class, subclass, luminosity, .. } = parse_spectral_type(spectral);
 
         // Get base properties for the spectral class
         let (mass, radius, temp, lum) = match class {
@@ -84,59 +115,114 @@ impl StarPhysical {
                 let temp = 30000.0 + subclass as f64 * 2000.0;
                 let mass = 16.0 * Units::SOLAR_MASS;
                 let radius = 6.6 * Units::SOLAR_RADIUS;
-                let lum = 30000.0 * 3.828e26;  // Solar luminosity
+                let lum = 30000.0 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::B => {
                 let temp = 10000.0 + subclass as f64 * 2000.0;
                 let mass = 2.9 * Units::SOLAR_MASS;
                 let radius = 1.8 * Units::SOLAR_RADIUS;
-                let lum = 25.0 * 3.828e26;
+                let lum = 25.0 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::A => {
                 let temp = 7500.0 + subclass as f64 * 250.0;
                 let mass = 1.4 * Units::SOLAR_MASS;
                 let radius = 1.4 * Units::SOLAR_RADIUS;
-                let lum = 5.0 * 3.828e26;
+                let lum = 5.0 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::F => {
                 let temp = 6000.0 + subclass as f64 * 150.0;
                 let mass = 1.04 * Units::SOLAR_MASS;
                 let radius = 1.15 * Units::SOLAR_RADIUS;
-                let lum = 1.5 * 3.828e26;
+                let lum = 1.5 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::G => {
                 let temp = 5200.0 + subclass as f64 * 80.0;
                 let mass = (0.8 + subclass as f64 * 0.02) * Units::SOLAR_MASS;
                 let radius = (0.96 + subclass as f64 * 0.004) * Units::SOLAR_RADIUS;
-                let lum = (0.6 + subclass as f64 * 0.04) * 3.828e26;
+                let lum = (0.6 + subclass as f64 * 0.04) * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::K => {
                 let temp = 3700.0 + subclass as f64 * 150.0;
                 let mass = 0.45 * Units::SOLAR_MASS;
                 let radius = 0.7 * Units::SOLAR_RADIUS;
-                let lum = 0.08 * 3.828e26;
+                let lum = 0.08 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
             SpectralClass::M => {
                 let temp = 2400.0 + subclass as f64 * 130.0;
                 let mass = 0.08 * Units::SOLAR_MASS;
                 let radius = 0.2 * Units::SOLAR_RADIUS;
-                let lum = 0.001 * 3.828e26;
+                let lum = 0.001 * Units::SOLAR_LUMINOSITY;
                 (mass, radius, temp, lum)
             }
-            _ => {
+            SpectralClass::L => {
+                // Warmest brown dwarfs and the coolest red dwarfs, ~2500 K at L0
+                let temp = 2500.0 - subclass as f64 * 120.0;
+                let mass = 0.07 * Units::SOLAR_MASS;
+                let radius = 0.1 * Units::SOLAR_RADIUS;
+                let lum = 1e-4 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::T => {
+                // Methane dwarfs, ~1300 K at T0 down to ~500 K
+                let temp = 1300.0 - subclass as f64 * 85.0;
+                let mass = 0.05 * Units::SOLAR_MASS;
+                let radius = 0.1 * Units::SOLAR_RADIUS;
+                let lum = 1e-5 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::Y => {
+                // Coolest brown dwarfs, room temperature and below
+                let temp = 500.0 - subclass as f64 * 40.0;
+                let mass = 0.02 * Units::SOLAR_MASS;
+                let radius = 0.1 * Units::SOLAR_RADIUS;
+                let lum = 1e-7 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::C => {
+                // Carbon stars are cool asymptotic giant branch stars
+                let temp = 3500.0 - subclass as f64 * 100.0;
+                let mass = 1.5 * Units::SOLAR_MASS;
+                let radius = 250.0 * Units::SOLAR_RADIUS;
+                let lum = 5000.0 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::S => {
+                // Zirconium-oxide giants between M giants and carbon stars
+                let temp = 3600.0 - subclass as f64 * 100.0;
+                let mass = 1.5 * Units::SOLAR_MASS;
+                let radius = 200.0 * Units::SOLAR_RADIUS;
+                let lum = 4000.0 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::W => {
+                // Wolf-Rayet stars, stripped helium cores; earlier subclasses are hotter
+                let temp = 150000.0 - subclass as f64 * 13000.0;
+                let mass = 15.0 * Units::SOLAR_MASS;
+                let radius = 2.0 * Units::SOLAR_RADIUS;
+                let lum = 200000.0 * Units::SOLAR_LUMINOSITY;
+                (mass, radius, temp, lum)
+            }
+            SpectralClass::Unknown => {
                 // Default to Sun-like
-                (Units::SOLAR_MASS, Units::SOLAR_RADIUS, 5778.0, 3.828e26)
+                (Units::SOLAR_MASS, Units::SOLAR_RADIUS, 5778.0, Units::SOLAR_LUMINOSITY)
             }
         };
 
-        // Adjust for luminosity class
+        // Adjust for luminosity class; brown dwarf, carbon, S and Wolf-Rayet defaults
+        // already describe the star as it is, so only O through M are scaled
+        let unscaled = matches!(
+            class,
+            SpectralClass::L | SpectralClass::T | SpectralClass::Y
+                | SpectralClass::C | SpectralClass::S | SpectralClass::W
+        );
         let (mass_mult, radius_mult, lum_mult) = match luminosity {
+            _ if unscaled => (1.0, 1.0, 1.0),
             LuminosityClass::Ia | LuminosityClass::Ib => (25.0, 200.0, 10000.0),
             LuminosityClass::II => (10.0, 50.0, 1000.0),
             LuminosityClass::III => (3.0, 10.0, 100.0),
@@ -146,7 +232,7 @@ impl StarPhysical {
             LuminosityClass::VII => (0.6, 0.01, 0.0001),
         };
 
-        Self {
+        let mut star = Self {
             mass_kg: mass * mass_mult,
             radius_m: radius * radius_mult,
             luminosity_w: lum * lum_mult,
@@ -158,7 +244,55 @@ impl StarPhysical {
             metallicity: None,
             magnetic_field_t: None,
             rotation_velocity_mps: None,
+        };
+        star.age_years = star.estimate_age();
+        star
+    }
+
+    // Main-sequence lifetime of a one solar mass star
+    pub const SOLAR_MAIN_SEQUENCE_YEARS: f64 = 1.0e10;
+    // No star is older than the universe, so long-lived dwarfs are capped here
+    pub const MAX_STELLAR_AGE_YEARS: f64 = 1.3e10;
+    // Typical age of a white dwarf in the galactic disc
+    pub const WHITE_DWARF_AGE_YEARS: f64 = 5.0e9;
+
+    // Rough age in years from the main-sequence lifetime t ≈ 10 Gyr · M^-2.5,
+    // taking dwarfs as halfway through it and giants as just past it.
+    // None for brown dwarfs, which never reach the main sequence.
+    pub fn estimate_age(&self) -> Option<f64> {
+        let mass = self.mass_solar();
+        if mass <= 0.0 || matches!(self.spectral_class, SpectralClass::L | SpectralClass::T | SpectralClass::Y) {
+            return None;
         }
+
+        let lifetime = Self::SOLAR_MAIN_SEQUENCE_YEARS * mass.powf(-2.5);
+        let evolved = matches!(self.spectral_class, SpectralClass::C | SpectralClass::S | SpectralClass::W);
+        let fraction_of_lifetime = match self.luminosity_class {
+            _ if evolved => 1.1,
+            LuminosityClass::VII => return Some(Self::WHITE_DWARF_AGE_YEARS),
+            LuminosityClass::V | LuminosityClass::VI => 0.5,
+            LuminosityClass::IV => 0.9,
+            LuminosityClass::III | LuminosityClass::II | LuminosityClass::Ia | LuminosityClass::Ib => 1.1,
+        };
+        Some((lifetime * fraction_of_lifetime).min(Self::MAX_STELLAR_AGE_YEARS))
+    }
+
+    pub fn mass_solar(&self) -> f64 {
+        self.mass_kg / Units::SOLAR_MASS
+    }
+
+    pub fn radius_solar(&self) -> f64 {
+        self.radius_m / Units::SOLAR_RADIUS
+    }
+
+    pub fn luminosity_solar(&self) -> f64 {
+        self.luminosity_w / Units::SOLAR_LUMINOSITY
+    }
+
+    // Absolute visual magnitude from luminosity, taking the Sun's M_V = 4.83 and ignoring
+    // bolometric corrections
+    pub fn absolute_magnitude(&self) -> f64 {
+        4.83 - 2.5 * self.luminosity_solar().log10()
     }
 }
 
@@ -184,13 +318,33 @@ pub enum LuminosityClass {
     VII, // White dwarf
 }
 
+// Peculiarity and emission markers that may follow the luminosity class
+// (peculiar, emission, metallic, nebulous, N III/He II emission, unspecified extra)
+pub const SPECTRAL_PECULIARITY_CODES: [char; 6] = ['p', 'e', 'm', 'n', 'f', '+'];
+
+// Components of a spectral type string such as "B9IIIpe"
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpectralTypeParts {
+    pub class: SpectralClass,
+    pub subclass: u8,
+    pub luminosity: LuminosityClass,
+    pub peculiarities: Vec<char>,
+}
+
 // Parse spectral type string into components
-fn parse_spectral_type(spectral: &str) -> (SpectralClass, u8, LuminosityClass) {
+// Peculiarity codes are collected separately and uncertainty marks (':') are ignored,
+// so neither is mistaken for part of the luminosity class.
+pub fn parse_spectral_type(spectral: &str) -> SpectralTypeParts {
     if spectral.is_empty() {
-        return (SpectralClass::Unknown, 0, LuminosityClass::V);
+        return SpectralTypeParts {
+            class: SpectralClass::Unknown,
+            subclass: 0,
+            luminosity: LuminosityClass::V,
+            peculiarities: Vec::new(),
+        };
     }
 
-    let mut chars = spectral.chars();
+    let mut chars = spectral.chars().peekable();
 
     // Parse spectral class
     let class = match chars.next() {
@@ -210,16 +364,26 @@ fn parse_spectral_type(spectral: &str) -> (SpectralClass, u8, LuminosityClass) {
         _ => SpectralClass::Unknown,
     };
 
+    // Wolf-Rayet subtypes (WN, WC, WO) put a letter before the subclass
+    if class == SpectralClass::W {
+        chars.next_if(|c| matches!(c, 'N' | 'C' | 'O'));
+    }
+
     // Parse subclass (0-9)
     let subclass = chars
-        .next()
+        .next_if(|c| c.is_ascii_digit())
         .and_then(|c| c.to_digit(10))
         .map(|d| d as u8)
         .unwrap_or(5);
 
-    // Parse luminosity class
-    let rest: String = chars.collect();
-    let luminosity = match rest.as_str() {
+    // Parse luminosity class, then whatever markers follow it
+    let mut luminosity_code = String::new();
+    while let Some(c) = chars.next_if(|c| matches!(c, 'I' | 'V' | 'a' | 'b' | 'A' | 'B')) {
+        luminosity_code.push(c);
+    }
+    let peculiarities: Vec<char> = chars.filter(|c| SPECTRAL_PECULIARITY_CODES.contains(c)).collect();
+
+    let luminosity = match luminosity_code.as_str() {
         "Ia" | "IA" => LuminosityClass::Ia,
         "Ib" | "IB" => LuminosityClass::Ib,
         "II" => LuminosityClass::II,
@@ -231,7 +395,7 @@ fn parse_spectral_type(spectral: &str) -> (SpectralClass, u8, LuminosityClass) {
         _ => LuminosityClass::V,
     };
 
-    (class, subclass, luminosity)
+    SpectralTypeParts { class, subclass, luminosity, peculiarities }
 }
 
 // Planetary body physical properties
@@ -281,6 +445,73 @@ impl Default for PlanetPhysical {
     }
 }
 
+impl PlanetPhysical {
+    // Surface temperature under a star of `luminosity_w` at `orbital_radius_m`: the
+    // equilibrium temperature for the planet's Bond albedo (0.3 when unknown), raised by
+    // the atmosphere's greenhouse effect when it has one
+    pub fn surface_temperature_at(&self, luminosity_w: f64, orbital_radius_m: f64) -> f64 {
+        let equilibrium_k = equilibrium_temperature_k(luminosity_w, orbital_radius_m, self.bond_albedo.unwrap_or(0.3));
+        match &self.atmosphere {
+            Some(atmosphere) => atmosphere.surface_temperature_k(equilibrium_k),
+            None => equilibrium_k,
+        }
+    }
+
+    // Habitability from 0 to 1 for people living on the surface at `surface_temperature_k`.
+    // Liquid-water temperatures, breathable air and near-Earth gravity score best; gas and
+    // ice giants score zero, and runaway greenhouse atmospheres are penalized on top of
+    // the heat they cause.
+    pub fn assess_habitability(&self, surface_temperature_k: f64) -> f64 {
+        if matches!(self.composition, PlanetaryComposition::GasGiant | PlanetaryComposition::IceGiant) {
+            return 0.0;
+        }
+
+        // Full marks from freezing to body temperature, none below 223 K or above boiling
+        let temperature = match surface_temperature_k {
+            t if (273.0..=310.0).contains(&t) => 1.0,
+            t if t < 273.0 => ((t - 223.0) / 50.0).max(0.0),
+            t => ((373.0 - t) / 63.0).max(0.0),
+        };
+
+        let (air, greenhouse) = match &self.atmosphere {
+            Some(atmosphere) => {
+                let air = if atmosphere.assess_breathability() {
+                    1.0
+                } else if (10_000.0..=500_000.0).contains(&atmosphere.surface_pressure_pa) {
+                    0.5
+                } else {
+                    0.1
+                };
+                let greenhouse = (MAX_BENIGN_GREENHOUSE_FACTOR / atmosphere.greenhouse_factor).min(1.0);
+                (air, greenhouse)
+            }
+            None => (0.0, 1.0),
+        };
+
+        let gravity_g = self.gravity_mps2 / 9.81;
+        let gravity = if (0.5..=1.5).contains(&gravity_g) { 1.0 } else { 0.5 };
+
+        temperature * air * greenhouse * gravity
+    }
+}
+
+// Stefan-Boltzmann constant, W m^-2 K^-4
+const STEFAN_BOLTZMANN: f64 = 5.670_374e-8;
+
+// Infrared optical depth that lifts Earth from its 255 K equilibrium to 288 K;
+// `greenhouse_factor` is measured in multiples of it
+const EARTH_GREENHOUSE_DEPTH: f64 = 0.84;
+
+// Greenhouse factors above this are on their way to runaway and cost habitability
+const MAX_BENIGN_GREENHOUSE_FACTOR: f64 = 3.0;
+
+// Blackbody temperature of a fast-rotating planet at `orbital_radius_m` from a star of
+// `luminosity_w`: T = (L (1 - A) / (16 π σ d²))^(1/4)
+pub fn equilibrium_temperature_k(luminosity_w: f64, orbital_radius_m: f64, bond_albedo: f64) -> f64 {
+    let absorbed = luminosity_w * (1.0 - bond_albedo.clamp(0.0, 1.0));
+    (absorbed / (16.0 * std::f64::consts::PI * STEFAN_BOLTZMANN * orbital_radius_m.powi(2))).powf(0.25)
+}
+
 // Planetary composition types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum PlanetaryComposition {
@@ -296,6 +527,20 @@ pub enum PlanetaryComposition {
     Custom(String),
 }
 
+// Oxygen partial pressure people can live on: hypoxia below, oxygen toxicity above
+const BREATHABLE_O2_PA: std::ops::RangeInclusive<f64> = 16_000.0..=50_000.0;
+
+// Highest partial pressure of each toxic gas that is safe to breathe indefinitely
+const TOXIC_GAS_LIMITS_PA: [(&str, f64); 7] = [
+    ("CO2", 1_000.0),
+    ("CO", 5.0),
+    ("SO2", 0.5),
+    ("H2S", 1.0),
+    ("NH3", 3.0),
+    ("Cl2", 0.1),
+    ("HCN", 0.5),
+];
+
 // Atmospheric composition and properties
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Atmosphere {
@@ -332,8 +577,9 @@ impl Atmosphere {
             ],
             greenhouse_factor: 1.0,
             transparency: 0.8,
-            breathable: true,
+            breathable: false,
         }
+        .with_assessed_breathability()
     }
 
     pub fn mars_like() -> Self {
@@ -358,6 +604,59 @@ impl Atmosphere {
             transparency: 0.9,
             breathable: false,
         }
+        .with_assessed_breathability()
+    }
+
+    pub fn venus_like() -> Self {
+        Self {
+            surface_pressure_pa: 9.2e6,
+            scale_height_m: 15900.0,
+            composition: vec![
+                AtmosphericComponent {
+                    gas: "CO2".to_string(),
+                    percentage: 96.5,
+                },
+                AtmosphericComponent {
+                    gas: "N2".to_string(),
+                    percentage: 3.5,
+                },
+            ],
+            greenhouse_factor: 170.0,
+            transparency: 0.1,
+            breathable: false,
+        }
+        .with_assessed_breathability()
+    }
+
+    // Surface temperature under a gray greenhouse, T = T_eq (1 + 0.75 τ)^(1/4), with the
+    // infrared optical depth τ scaled from Earth's by `greenhouse_factor`
+    pub fn surface_temperature_k(&self, equilibrium_k: f64) -> f64 {
+        let depth = EARTH_GREENHOUSE_DEPTH * self.greenhouse_factor.max(0.0);
+        equilibrium_k * (1.0 + 0.75 * depth).powf(0.25)
+    }
+
+    // Partial pressure of a gas in pascals, zero when absent
+    pub fn partial_pressure_pa(&self, gas: &str) -> f64 {
+        self.composition
+            .iter()
+            .filter(|c| c.gas.eq_ignore_ascii_case(gas))
+            .map(|c| c.percentage / 100.0 * self.surface_pressure_pa)
+            .sum()
+    }
+
+    // Whether people could breathe this air unaided: enough oxygen pressure without
+    // oxygen toxicity, and every toxic gas (CO2, CO, SO2, ...) under its exposure limit
+    pub fn assess_breathability(&self) -> bool {
+        BREATHABLE_O2_PA.contains(&self.partial_pressure_pa("O2"))
+            && TOXIC_GAS_LIMITS_PA
+                .iter()
+                .all(|&(gas, limit_pa)| self.partial_pressure_pa(gas) <= limit_pa)
+    }
+
+    // Same atmosphere with `breathable` derived from its composition
+    pub fn with_assessed_breathability(mut self) -> Self {
+        self.breathable = self.assess_breathability();
+        self
     }
 }
 
@@ -460,4 +759,139 @@ pub struct GenericPhysical {
     pub density_kgm3: Option<f64>,
     pub temperature_k: Option<f64>,
     pub custom_properties: std::collections::HashMap<String, serde_json::Value>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solar_unit_accessors() {
+        // The G-class table puts G2V a little under the Sun in mass and luminosity
+        let g2v = StarPhysical::from_spectral_type("G2V");
+        assert!((g2v.mass_solar() - 1.0).abs() < 0.2, "mass {}", g2v.mass_solar());
+        assert!((g2v.radius_solar() - 1.0).abs() < 0.05, "radius {}", g2v.radius_solar());
+        assert!((g2v.luminosity_solar() - 1.0).abs() < 0.35, "luminosity {}", g2v.luminosity_solar());
+
+        // Unclassified stars fall back to exactly solar values
+        let sun = StarPhysical::from_spectral_type("");
+        assert_eq!((sun.mass_solar(), sun.radius_solar(), sun.luminosity_solar()), (1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_surface_gravity_and_escape_velocity() {
+        // Stored values win; zeroed ones are derived from mass and radius
+        assert_eq!(Physical::new_planet().surface_gravity_mps2(), Some(9.81));
+        let earth = Physical::Planet(PlanetPhysical {
+            gravity_mps2: 0.0,
+            escape_velocity_mps: 0.0,
+            ..Default::default()
+        });
+        let g = earth.surface_gravity_mps2().unwrap();
+        assert!((g - 9.82).abs() < 0.05, "Earth gravity {}", g);
+        let v_esc = earth.escape_velocity_mps().unwrap();
+        assert!((v_esc - 11_186.0).abs() < 50.0, "Earth escape velocity {}", v_esc);
+
+        // The Sun's photosphere pulls about 274 m/s²
+        let sun = Physical::new_star("");
+        let g_sun = sun.surface_gravity_mps2().unwrap();
+        assert!((g_sun - 274.0).abs() < 1.0, "solar gravity {}", g_sun);
+
+        assert_eq!(Physical::new_station().surface_gravity_mps2(), None);
+    }
+
+    #[test]
+    fn test_peculiarity_suffixes_do_not_change_luminosity() {
+        let a0p = parse_spectral_type("A0p");
+        assert_eq!((a0p.class, a0p.subclass, a0p.luminosity), (SpectralClass::A, 0, LuminosityClass::V));
+        assert_eq!(a0p.peculiarities, vec!['p']);
+
+        let b2e = parse_spectral_type("B2e");
+        assert_eq!((b2e.class, b2e.subclass, b2e.luminosity), (SpectralClass::B, 2, LuminosityClass::V));
+        assert_eq!(b2e.peculiarities, vec!['e']);
+
+        let giant = parse_spectral_type("K0III:");
+        assert_eq!((giant.class, giant.subclass, giant.luminosity), (SpectralClass::K, 0, LuminosityClass::III));
+        assert!(giant.peculiarities.is_empty());
+
+        let g2v = parse_spectral_type("G2V+");
+        assert_eq!((g2v.luminosity, g2v.peculiarities), (LuminosityClass::V, vec!['+']));
+        assert_eq!(parse_spectral_type("B9IIIpe").peculiarities, vec!['p', 'e']);
+    }
+
+    #[test]
+    fn test_brown_dwarfs_and_wolf_rayet_are_not_sun_like() {
+        let t5 = StarPhysical::from_spectral_type("T5");
+        assert_eq!(t5.spectral_class, SpectralClass::T);
+        assert!(t5.temperature_k < 1500.0, "T5 at {} K", t5.temperature_k);
+        assert!(t5.mass_solar() < 0.08);
+
+        let wn5 = StarPhysical::from_spectral_type("WN5");
+        assert_eq!(wn5.spectral_class, SpectralClass::W);
+        assert!(wn5.temperature_k > 20000.0, "WN5 at {} K", wn5.temperature_k);
+        assert!(wn5.luminosity_solar() > 10000.0);
+
+        // Earlier Wolf-Rayet subclasses are hotter
+        assert!(StarPhysical::from_spectral_type("WC4").temperature_k > wn5.temperature_k);
+
+        let carbon = StarPhysical::from_spectral_type("C5");
+        assert!(carbon.temperature_k < 4000.0 && carbon.radius_solar() > 100.0);
+    }
+
+    #[test]
+    fn test_age_estimate_follows_mass() {
+        let o_star = StarPhysical::from_spectral_type("O5V");
+        let o_age = o_star.age_years.unwrap();
+        assert!(o_age < 1.0e7, "O5V aged {} years", o_age);
+
+        let k_dwarf = StarPhysical::from_spectral_type("K5V");
+        let k_age = k_dwarf.age_years.unwrap();
+        assert!(k_age > 1.0e10, "K5V aged {} years", k_age);
+        assert!(k_age <= StarPhysical::MAX_STELLAR_AGE_YEARS);
+
+        // Sun-like dwarfs land at several billion years
+        let g_age = StarPhysical::from_spectral_type("G2V").age_years.unwrap();
+        assert!((1.0e9..1.0e10).contains(&g_age), "G2V aged {} years", g_age);
+        assert_eq!(StarPhysical::from_spectral_type("T5").age_years, None);
+    }
+
+    #[test]
+    fn test_breathability_from_composition() {
+        assert!(Atmosphere::earth_like().assess_breathability());
+        assert!(Atmosphere::earth_like().breathable);
+        assert!(!Atmosphere::mars_like().assess_breathability());
+        assert!(!Atmosphere::mars_like().breathable);
+
+        // Plenty of oxygen does not make up for half the air being CO2
+        let mut smothering = Atmosphere::earth_like();
+        smothering.composition = vec![
+            AtmosphericComponent { gas: "CO2".to_string(), percentage: 50.0 },
+            AtmosphericComponent { gas: "O2".to_string(), percentage: 25.0 },
+            AtmosphericComponent { gas: "N2".to_string(), percentage: 25.0 },
+        ];
+        assert!(!smothering.assess_breathability());
+
+        // Earth's mix at a mountaintop-on-Everest pressure is too thin
+        let mut thin = Atmosphere::earth_like();
+        thin.surface_pressure_pa = 33_000.0;
+        assert!(!thin.assess_breathability());
+    }
+
+    #[test]
+    fn test_greenhouse_warming_and_habitability() {
+        let mut earth = PlanetPhysical::default();
+        let earth_k = earth.surface_temperature_at(Units::SOLAR_LUMINOSITY, Units::AU);
+        assert!((earth_k - 288.0).abs() < 3.0, "Earth at {} K", earth_k);
+        assert!(earth.assess_habitability(earth_k) > 0.9);
+
+        // Venus: thick CO2 and bright clouds, yet far hotter than its ~230 K equilibrium
+        earth.atmosphere = Some(Atmosphere::venus_like());
+        earth.bond_albedo = Some(0.77);
+        let venus = earth;
+        let venus_k = venus.surface_temperature_at(Units::SOLAR_LUMINOSITY, 0.723 * Units::AU);
+        let equilibrium_k = equilibrium_temperature_k(Units::SOLAR_LUMINOSITY, 0.723 * Units::AU, 0.77);
+        assert!(equilibrium_k < 250.0);
+        assert!(venus_k > 400.0, "Venus at {} K", venus_k);
+        assert!(venus.assess_habitability(venus_k) < 0.01);
+    }
+}
@@ -0,0 +1,220 @@
+// Political influence zones computed in memory, without PostGIS
+
+use crate::stellar_forge::containers::{Galaxy, PoliticalRegion};
+use crate::stellar_forge::core::{Id, Vec3};
+use serde::{Deserialize, Serialize};
+
+// One system's sphere of influence
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct InfluenceSphere {
+    pub center: Vec3,
+    pub radius: f64,
+}
+
+impl InfluenceSphere {
+    pub fn contains(&self, point: Vec3) -> bool {
+        (point - self.center).norm() <= self.radius
+    }
+
+    pub fn overlaps(&self, other: &InfluenceSphere) -> bool {
+        (self.center - other.center).norm() <= self.radius + other.radius
+    }
+}
+
+// Union of influence spheres, the in-memory counterpart of political.generate_influence_zone
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct InfluenceZone {
+    pub spheres: Vec<InfluenceSphere>,
+}
+
+impl InfluenceZone {
+    pub fn is_empty(&self) -> bool {
+        self.spheres.is_empty()
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        self.spheres.iter().any(|s| s.contains(point))
+    }
+
+    // Axis-aligned bounding box as (min, max)
+    pub fn bounding_box(&self) -> Option<(Vec3, Vec3)> {
+        let first = self.spheres.first()?;
+        let reach = |s: &InfluenceSphere| Vec3::repeat(s.radius);
+        let init = (first.center - reach(first), first.center + reach(first));
+        Some(self.spheres.iter().fold(init, |(min, max), s| {
+            (min.inf(&(s.center - reach(s))), max.sup(&(s.center + reach(s))))
+        }))
+    }
+
+    // Groups of sphere indices that touch each other, each group one contiguous territory
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..self.spheres.len()).collect();
+
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..self.spheres.len() {
+            for j in (i + 1)..self.spheres.len() {
+                if self.spheres[i].overlaps(&self.spheres[j]) {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[a] = b;
+                }
+            }
+        }
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_of_root = std::collections::HashMap::new();
+        for i in 0..self.spheres.len() {
+            let r = root(&mut parent, i);
+            let group = *group_of_root.entry(r).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(i);
+        }
+        groups
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.components().len() <= 1
+    }
+
+    // Border of the zone in the x-y plane: points on each sphere's equator that no
+    // other sphere covers, suitable for drawing top-down territory outlines
+    pub fn border_points_xy(&self, samples_per_sphere: usize) -> Vec<Vec3> {
+        let mut border = Vec::new();
+        for (i, sphere) in self.spheres.iter().enumerate() {
+            for k in 0..samples_per_sphere {
+                let angle = std::f64::consts::TAU * k as f64 / samples_per_sphere as f64;
+                let point = sphere.center + Vec3::new(angle.cos(), angle.sin(), 0.0) * sphere.radius;
+                let covered = self.spheres.iter().enumerate().any(|(j, other)| {
+                    j != i && (point - other.center).norm() < other.radius
+                });
+                if !covered {
+                    border.push(point);
+                }
+            }
+        }
+        border
+    }
+}
+
+// Union of spheres of `base_radius` around each controlled system.
+// Radius and positions share whatever unit the caller uses.
+pub fn compute_influence_zone(systems: &[Vec3], base_radius: f64) -> InfluenceZone {
+    let weighted: Vec<(Vec3, f64)> = systems.iter().map(|&p| (p, 1.0)).collect();
+    compute_weighted_influence_zone(&weighted, base_radius)
+}
+
+// Same as compute_influence_zone, with each radius scaled by a control strength as the SQL version does
+pub fn compute_weighted_influence_zone(systems: &[(Vec3, f64)], base_radius: f64) -> InfluenceZone {
+    InfluenceZone {
+        spheres: systems
+            .iter()
+            .filter(|(_, strength)| *strength > 0.0)
+            .map(|&(center, strength)| InfluenceSphere { center, radius: base_radius * strength })
+            .collect(),
+    }
+}
+
+// Influence zone of a region's member systems; members missing from the galaxy are skipped
+pub fn region_influence_zone(galaxy: &Galaxy, region: &PoliticalRegion, base_radius_m: f64) -> InfluenceZone {
+    let positions: Vec<Vec3> = region
+        .member_system_ids
+        .iter()
+        .filter_map(|id| galaxy.find_system(*id))
+        .map(|system| system.galactic_position())
+        .collect();
+    compute_influence_zone(&positions, base_radius_m)
+}
+
+// Systems inside more than one region's influence zone, as (id, name, claim count),
+// most contested first. Offline counterpart of SpatialQueries::find_contested_systems.
+pub fn find_contested_systems(
+    galaxy: &Galaxy,
+    regions: &[PoliticalRegion],
+    base_radius_m: f64,
+) -> Vec<(Id, String, usize)> {
+    let zones: Vec<InfluenceZone> = regions
+        .iter()
+        .map(|region| region_influence_zone(galaxy, region, base_radius_m))
+        .collect();
+
+    let mut contested: Vec<(Id, String, usize)> = galaxy
+        .star_systems
+        .iter()
+        .map(|system| {
+            let position = system.galactic_position();
+            let claims = zones.iter().filter(|zone| zone.contains(position)).count();
+            (system.id, system.name.clone(), claims)
+        })
+        .filter(|(_, _, claims)| *claims > 1)
+        .collect();
+
+    contested.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(&b.1)));
+    contested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stellar_forge::builders::SystemBuilder;
+    use crate::stellar_forge::core::Units;
+
+    #[test]
+    fn test_clustered_systems_form_one_zone() {
+        let systems = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(6.0, 0.0, 0.0)];
+        let zone = compute_influence_zone(&systems, 5.0);
+
+        assert!(zone.is_connected());
+        assert!(zone.contains(Vec3::new(3.0, 0.0, 0.0)));
+        assert!(!zone.contains(Vec3::new(12.0, 0.0, 0.0)));
+
+        let (min, max) = zone.bounding_box().unwrap();
+        assert_eq!((min.x, max.x), (-5.0, 11.0));
+
+        // Distant system adds a second territory
+        let spread = compute_influence_zone(&[systems[0], systems[1], Vec3::new(50.0, 0.0, 0.0)], 5.0);
+        assert_eq!(spread.components(), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn test_border_skips_covered_points() {
+        let zone = compute_influence_zone(&[Vec3::zeros(), Vec3::new(6.0, 0.0, 0.0)], 5.0);
+        let border = zone.border_points_xy(36);
+
+        assert!(!border.is_empty());
+        assert!(border.len() < 72);
+        for point in &border {
+            let interior = zone.spheres.iter().any(|s| (point - s.center).norm() < s.radius - 1e-9);
+            assert!(!interior, "border point {:?} lies inside the zone", point);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_regions_share_contested_system() {
+        let mut galaxy = Galaxy::new("Test");
+        let mut ids = Vec::new();
+        for (name, x_ly) in [("Alpha", 0.0), ("Border", 8.0), ("Gamma", 16.0)] {
+            let system = SystemBuilder::new(name).at_position(x_ly, 0.0, 0.0).with_star("G2V").build();
+            ids.push(system.id);
+            galaxy.add_star_system(system).unwrap();
+        }
+
+        let mut federation = PoliticalRegion::new("Federation", "Republic");
+        federation.add_system(ids[0]);
+        let mut empire = PoliticalRegion::new("Empire", "Monarchy");
+        empire.add_system(ids[2]);
+
+        let contested = find_contested_systems(&galaxy, &[federation, empire], 10.0 * Units::LIGHT_YEAR);
+        assert_eq!(contested.len(), 1);
+        assert_eq!(contested[0].0, ids[1]);
+        assert_eq!(contested[0].1, "Border");
+        assert_eq!(contested[0].2, 2);
+    }
+}
@@ -4,7 +4,7 @@ use crate::stellar_forge::core::{Id, State, Vec3, CoordinateError};
 use crate::stellar_forge::bodies::{StellarBody, BodyKind, SpatialParent};
 use crate::stellar_forge::containers::{Galaxy, StarSystem, PoliticalRegion, Fleet};
 use crate::stellar_forge::frames::{Frame, FrameHierarchy, FrameKind};
-use crate::stellar_forge::motion::{MotionModel, OrbitalElements};
+use crate::stellar_forge::motion::MotionModel;
 use crate::stellar_forge::physical::Physical;
 use crate::stellar_forge::associations::{Association, AssociationManager, Tag};
 use std::collections::HashMap;
@@ -232,9 +232,9 @@ impl StellarForgeService {
 
     // Initialize with galactic frame
     pub fn initialize(&mut self) -> Result<(), ServiceError> {
-        let galactic_frame = Frame::new_galactic("Galactic");
+        let galactic_frame = Frame::new_galactic_iau("Galactic");
         self.frame_hierarchy.add_frame(galactic_frame)
-            .map_err(|e| ServiceError::CoordinateError(e))?;
+            .map_err(ServiceError::CoordinateError)?;
         Ok(())
     }
 
@@ -243,9 +243,8 @@ impl StellarForgeService {
         let system_id = system.id;
 
         // Create barycenter frame for the system
-        let galactic_frame_id = self.frame_hierarchy.frames
-            .values()
-            .find(|f| f.kind == FrameKind::GalacticIAU)
+        let galactic_frame_id = self.frame_hierarchy
+            .find_frame_by_kind(FrameKind::GalacticIAU)
             .map(|f| f.id)
             .ok_or_else(|| ServiceError::InvalidOperation("No galactic frame".into()))?;
 
@@ -257,7 +256,7 @@ impl StellarForgeService {
         );
 
         self.frame_hierarchy.add_frame(barycenter_frame)
-            .map_err(|e| ServiceError::CoordinateError(e))?;
+            .map_err(ServiceError::CoordinateError)?;
 
         // Index all bodies in the system
         for star in &system.stars {
@@ -351,7 +350,7 @@ impl FrameService for StellarForgeService {
     fn add_frame(&mut self, frame: Frame) -> Result<Id, ServiceError> {
         let frame_id = frame.id;
         self.frame_hierarchy.add_frame(frame)
-            .map_err(|e| ServiceError::CoordinateError(e))?;
+            .map_err(ServiceError::CoordinateError)?;
         Ok(frame_id)
     }
 
@@ -367,9 +366,8 @@ impl FrameService for StellarForgeService {
 
     fn to_galactic(&self, frame_id: Id, state: State, epoch: OffsetDateTime)
         -> Result<State, CoordinateError> {
-        let galactic_frame = self.frame_hierarchy.frames
-            .values()
-            .find(|f| f.kind == FrameKind::GalacticIAU)
+        let galactic_frame = self.frame_hierarchy
+            .find_frame_by_kind(FrameKind::GalacticIAU)
             .ok_or(CoordinateError::FrameNotFound(frame_id))?;
 
         self.frame_hierarchy.transform_state(state, frame_id, galactic_frame.id, epoch)
@@ -385,6 +383,12 @@ pub struct PropagationService {
     cache: HashMap<(Id, i64), State>,  // (body_id, epoch_seconds) -> State
 }
 
+impl Default for PropagationService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PropagationService {
     pub fn new() -> Self {
         Self {
@@ -1,14 +1,19 @@
 // Storage and persistence layer for StellarForge
 
-use crate::stellar_forge::core::{Id, State, Vec3};
+use crate::stellar_forge::core::{Id, Vec3, Units, ContainerError};
 use crate::stellar_forge::bodies::{StellarBody, BodyKind};
-use crate::stellar_forge::containers::{Galaxy, StarSystem, PoliticalRegion, Fleet};
-use crate::stellar_forge::frames::Frame;
-use crate::stellar_forge::associations::{Association, Tag};
+use crate::stellar_forge::containers::{Galaxy, StarSystem, MergePolicy, MergeSummary};
+use crate::stellar_forge::motion::MotionModel;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::Path;
 use time::OffsetDateTime;
 
+// Schema version written into new datasets. Bump the major version for changes that old
+// readers would misparse and teach `FileStorage::migrate` to upgrade the previous one.
+pub const DATASET_VERSION: &str = "1.0.0";
+
 // Main storage format for complete datasets
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StellarForgeDataset {
@@ -29,7 +34,7 @@ pub struct DatasetMetadata {
     pub statistics: DatasetStatistics,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DatasetStatistics {
     pub total_systems: usize,
     pub total_stars: usize,
@@ -38,6 +43,8 @@ pub struct DatasetStatistics {
     pub total_stations: usize,
     pub habitable_worlds: usize,
     pub populated_worlds: usize,
+    #[serde(default)]
+    pub total_population: f64,
 }
 
 impl StellarForgeDataset {
@@ -45,7 +52,7 @@ impl StellarForgeDataset {
         let stats = Self::calculate_statistics(&galaxy);
 
         Self {
-            version: "1.0.0".to_string(),
+            version: DATASET_VERSION.to_string(),
             created_utc: OffsetDateTime::now_utc(),
             last_modified_utc: OffsetDateTime::now_utc(),
             galaxy,
@@ -67,8 +74,9 @@ impl StellarForgeDataset {
             total_planets: 0,
             total_moons: 0,
             total_stations: 0,
-            habitable_worlds: 0,
+            habitable_worlds: galaxy.habitable_worlds().len(),
             populated_worlds: 0,
+            total_population: galaxy.total_population(),
         };
 
         for system in &galaxy.star_systems {
@@ -82,18 +90,10 @@ impl StellarForgeDataset {
                     .filter(|c| c.kind == BodyKind::Moon)
                     .count();
 
-                // Check habitability
-                if let Some(physical) = &planet.physical {
-                    if let crate::stellar_forge::physical::Physical::Planet(p) = physical {
-                        if let Some(hab) = p.habitability_score {
-                            if hab > 0.5 {
-                                stats.habitable_worlds += 1;
-                            }
-                        }
-                        if let Some(pop) = p.population {
-                            if pop > 0.0 {
-                                stats.populated_worlds += 1;
-                            }
+                if let Some(crate::stellar_forge::physical::Physical::Planet(p)) = &planet.physical {
+                    if let Some(pop) = p.population {
+                        if pop > 0.0 {
+                            stats.populated_worlds += 1;
                         }
                     }
                 }
@@ -107,6 +107,217 @@ impl StellarForgeDataset {
         self.metadata.statistics = Self::calculate_statistics(&self.galaxy);
         self.last_modified_utc = OffsetDateTime::now_utc();
     }
+
+    // Write one system to its own JSON file, for editing outside the dataset
+    pub fn export_system_named(&self, system_name: &str, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let system = self.galaxy.find_system_by_name(system_name).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("no system named '{}' in {}", system_name, self.metadata.name),
+            )
+        })?;
+        FileStorage::export_system(system, path)
+    }
+
+    // Put a system back into the galaxy; one with the same id is replaced, so an
+    // exported and edited system overwrites its original. Returns true on replacement.
+    pub fn import_system(&mut self, system: StarSystem) -> Result<bool, ContainerError> {
+        let replaced = self.galaxy.remove_system(system.id).is_ok();
+        self.galaxy.add_star_system(system)?;
+        self.update_statistics();
+        Ok(replaced)
+    }
+
+    // Sanity-check a loaded dataset, returning one message per problem found:
+    // hierarchy violations, stale statistics, negative masses, non-finite positions
+    // and impossible orbits
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for system in &self.galaxy.star_systems {
+            if !system.galactic_position().iter().all(|v| v.is_finite()) {
+                problems.push(format!("{}: galactic position is not finite", system.name));
+            }
+            for violation in system.validate_hierarchy() {
+                problems.push(format!("{}: {}", system.name, violation));
+            }
+
+            let mut bodies: Vec<&StellarBody> = Vec::new();
+            for body in system.stars.iter()
+                .chain(&system.planets)
+                .chain(&system.belts)
+                .chain(&system.stations)
+                .chain(&system.other_bodies)
+            {
+                bodies.push(body);
+                bodies.extend(body.descendants());
+            }
+            for body in bodies {
+                Self::validate_body(&system.name, body, &mut problems);
+            }
+        }
+        for body in &self.galaxy.rogue_objects {
+            Self::validate_body("rogue objects", body, &mut problems);
+        }
+
+        let recomputed = Self::calculate_statistics(&self.galaxy);
+        if recomputed != self.metadata.statistics {
+            problems.push(format!(
+                "stored statistics {:?} do not match recomputed {:?}",
+                self.metadata.statistics, recomputed
+            ));
+        }
+
+        problems
+    }
+
+    fn validate_body(context: &str, body: &StellarBody, problems: &mut Vec<String>) {
+        let state_finite = body.state.position_m.iter()
+            .chain(body.state.velocity_mps.iter())
+            .all(|v| v.is_finite());
+        if !state_finite {
+            problems.push(format!("{}: {} has a non-finite position or velocity", context, body.name));
+        }
+
+        if let Some(mass) = body.physical.as_ref().and_then(|p| p.mass_kg()) {
+            if mass.is_nan() || mass < 0.0 {
+                problems.push(format!("{}: {} has invalid mass {} kg", context, body.name, mass));
+            }
+        }
+
+        // validate_orbit knows which kinds may fly hyperbolic orbits (comets)
+        if let Err(err) = body.validate_orbit() {
+            problems.push(format!("{}: {} has an invalid orbit: {}", context, body.name, err));
+        }
+        if let Some(MotionModel::Keplerian(elements)) = &body.motion {
+            if elements.eccentricity < 0.0 {
+                problems.push(format!("{}: {} orbit has eccentricity {}", context, body.name, elements.eccentricity));
+            }
+            if elements.gravitational_param_m3s2 <= 0.0 {
+                problems.push(format!("{}: {} orbits a non-positive mass", context, body.name));
+            }
+        }
+    }
+}
+
+// Overview of a dataset for the Stats command
+#[derive(Clone, Debug)]
+pub struct DatasetSummary {
+    pub name: String,
+    pub statistics: DatasetStatistics,
+    pub spectral_histogram: BTreeMap<String, usize>,  // First letter of the spectral type, "?" if none
+    pub nearest_system: Option<(String, f64)>,        // Name and distance from Sol in ly
+    pub farthest_system: Option<(String, f64)>,
+    pub most_populous_world: Option<(String, f64)>,
+    pub bounding_box_ly: Option<(Vec3, Vec3)>,
+}
+
+// Count of stars per spectral class letter
+pub fn spectral_histogram(galaxy: &Galaxy) -> BTreeMap<String, usize> {
+    let mut histogram = BTreeMap::new();
+    for star in galaxy.star_systems.iter().flat_map(|s| &s.stars) {
+        let class = match &star.physical {
+            Some(crate::stellar_forge::physical::Physical::Star(s)) => s
+                .spectral_type
+                .trim()
+                .chars()
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string()),
+            _ => None,
+        };
+        *histogram.entry(class.unwrap_or_else(|| "?".to_string())).or_insert(0) += 1;
+    }
+    histogram
+}
+
+impl StellarForgeDataset {
+    // Distances are from the system named Sol if there is one, else the galactic origin (the Sun)
+    pub fn summary(&self) -> DatasetSummary {
+        let galaxy = &self.galaxy;
+        let sol = galaxy
+            .star_systems
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case("sol"));
+        let origin = sol.map(|s| s.galactic_position()).unwrap_or_else(Vec3::zeros);
+
+        let distances: Vec<(String, f64)> = galaxy
+            .star_systems
+            .iter()
+            .filter(|s| sol.is_none_or(|sol| sol.id != s.id))
+            .map(|s| (s.name.clone(), (s.galactic_position() - origin).norm() / Units::LIGHT_YEAR))
+            .collect();
+        let nearest_system = distances.iter().min_by(|a, b| a.1.total_cmp(&b.1)).cloned();
+        let farthest_system = distances.iter().max_by(|a, b| a.1.total_cmp(&b.1)).cloned();
+
+        let most_populous_world = galaxy
+            .star_systems
+            .iter()
+            .flat_map(|s| s.planets.iter().flat_map(|p| std::iter::once(p).chain(p.descendants())))
+            .filter_map(|body| match &body.physical {
+                Some(crate::stellar_forge::physical::Physical::Planet(p))
+                | Some(crate::stellar_forge::physical::Physical::Moon(p)) => {
+                    p.population.filter(|pop| *pop > 0.0).map(|pop| (body.name.clone(), pop))
+                }
+                _ => None,
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let bounding_box_ly = galaxy.star_systems.iter().map(|s| s.galactic_position() / Units::LIGHT_YEAR).fold(
+            None,
+            |bounds: Option<(Vec3, Vec3)>, p| match bounds {
+                None => Some((p, p)),
+                Some((min, max)) => Some((min.inf(&p), max.sup(&p))),
+            },
+        );
+
+        DatasetSummary {
+            name: self.metadata.name.clone(),
+            statistics: self.metadata.statistics.clone(),
+            spectral_histogram: spectral_histogram(galaxy),
+            nearest_system,
+            farthest_system,
+            most_populous_world,
+            bounding_box_ly,
+        }
+    }
+}
+
+impl fmt::Display for DatasetSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.statistics;
+        writeln!(f, "Dataset: {}", self.name)?;
+        writeln!(f, "  Systems: {}", stats.total_systems)?;
+        writeln!(f, "  Stars: {}", stats.total_stars)?;
+        writeln!(f, "  Planets: {}", stats.total_planets)?;
+        writeln!(f, "  Moons: {}", stats.total_moons)?;
+        writeln!(f, "  Stations: {}", stats.total_stations)?;
+        writeln!(f, "  Habitable worlds: {}", stats.habitable_worlds)?;
+        writeln!(f, "  Populated worlds: {}", stats.populated_worlds)?;
+        writeln!(f, "  Total population: {:.0}", stats.total_population)?;
+
+        writeln!(f, "Spectral classes:")?;
+        for (class, count) in &self.spectral_histogram {
+            writeln!(f, "  {}: {}", class, count)?;
+        }
+
+        if let Some((name, distance)) = &self.nearest_system {
+            writeln!(f, "Nearest system: {} ({:.2} ly)", name, distance)?;
+        }
+        if let Some((name, distance)) = &self.farthest_system {
+            writeln!(f, "Farthest system: {} ({:.2} ly)", name, distance)?;
+        }
+        if let Some((name, population)) = &self.most_populous_world {
+            writeln!(f, "Most populous world: {} ({:.0})", name, population)?;
+        }
+        if let Some((min, max)) = &self.bounding_box_ly {
+            writeln!(
+                f,
+                "Bounding box (ly): ({:.1}, {:.1}, {:.1}) to ({:.1}, {:.1}, {:.1})",
+                min.x, min.y, min.z, max.x, max.y, max.z
+            )?;
+        }
+        Ok(())
+    }
 }
 
 // File I/O operations
@@ -120,13 +331,60 @@ impl FileStorage {
         Ok(())
     }
 
-    // Load from JSON file
+    // Load from JSON file, migrating older schema versions and rejecting unknown ones
     pub fn load_json(path: impl AsRef<Path>) -> std::io::Result<StellarForgeDataset> {
         let json = std::fs::read_to_string(path)?;
-        let dataset = serde_json::from_str(&json)?;
+        let raw: serde_json::Value = serde_json::from_str(&json)?;
+        let dataset = serde_json::from_value(Self::migrate(raw)?)?;
         Ok(dataset)
     }
 
+    // Upgrade a raw dataset to the current major version. Each older major version with a
+    // known upgrade gets an arm that rewrites the JSON (and its version) and recurses; any
+    // other version is an InvalidData error.
+    pub fn migrate(dataset: serde_json::Value) -> std::io::Result<serde_json::Value> {
+        let current = major_version(DATASET_VERSION).unwrap_or_default();
+        let version = dataset
+            .get("version")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| invalid_data("dataset has no version field".to_string()))?;
+        let major = major_version(version)
+            .ok_or_else(|| invalid_data(format!("dataset version '{}' is not of the form MAJOR.MINOR.PATCH", version)))?;
+
+        match major {
+            m if m == current => Ok(dataset),
+            m if m > current => Err(invalid_data(format!(
+                "dataset version {} is newer than the supported {}; upgrade SolarViewer to read it",
+                version, DATASET_VERSION
+            ))),
+            _ => Err(invalid_data(format!(
+                "dataset version {} has no migration to {}",
+                version, DATASET_VERSION
+            ))),
+        }
+    }
+
+    // Load several datasets and merge their galaxies into the first, in order, returning
+    // the merged dataset with the combined summary of every merge
+    pub fn merge_json<P: AsRef<Path>>(
+        inputs: &[P],
+        policy: MergePolicy,
+    ) -> std::io::Result<(StellarForgeDataset, MergeSummary)> {
+        let mut paths = inputs.iter();
+        let first = paths.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "no datasets to merge")
+        })?;
+
+        let mut merged = Self::load_json(first)?;
+        let mut summary = MergeSummary::default();
+        for path in paths {
+            let dataset = Self::load_json(path)?;
+            summary += merged.galaxy.merge(dataset.galaxy, policy);
+        }
+        merged.update_statistics();
+        Ok((merged, summary))
+    }
+
     // Save to binary format (using bincode)
     #[cfg(feature = "binary")]
     pub fn save_binary(dataset: &StellarForgeDataset, path: impl AsRef<Path>) -> std::io::Result<()> {
@@ -160,6 +418,175 @@ impl FileStorage {
     }
 }
 
+fn major_version(version: &str) -> Option<u64> {
+    version.split('.').next()?.trim().parse().ok()
+}
+
+fn invalid_data(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+// Plain SQLite dump of a dataset: the systems, stars, planets and moons tables of the Postgres
+// schema with x/y/z columns (light-years) in place of PostGIS geometry. Existing tables in the
+// file are replaced.
+pub fn export_sqlite(dataset: &StellarForgeDataset, path: &str) -> rusqlite::Result<()> {
+    use crate::stellar_forge::physical::Physical;
+    use rusqlite::params;
+
+    let mut conn = rusqlite::Connection::open(path)?;
+    conn.execute_batch(
+        "DROP TABLE IF EXISTS moons;
+         DROP TABLE IF EXISTS planets;
+         DROP TABLE IF EXISTS stars;
+         DROP TABLE IF EXISTS systems;
+         CREATE TABLE systems (
+             id TEXT PRIMARY KEY,
+             name TEXT NOT NULL,
+             system_type TEXT NOT NULL,
+             x REAL NOT NULL,
+             y REAL NOT NULL,
+             z REAL NOT NULL,
+             galactic_longitude REAL,
+             galactic_latitude REAL,
+             distance_from_sol_ly REAL,
+             total_mass_solar REAL,
+             habitable_zone_inner_au REAL,
+             habitable_zone_outer_au REAL
+         );
+         CREATE TABLE stars (
+             id TEXT PRIMARY KEY,
+             system_id TEXT NOT NULL REFERENCES systems(id),
+             name TEXT NOT NULL,
+             spectral_type TEXT,
+             mass_solar REAL,
+             radius_solar REAL,
+             luminosity_solar REAL,
+             temperature_k REAL
+         );
+         CREATE TABLE planets (
+             id TEXT PRIMARY KEY,
+             system_id TEXT NOT NULL REFERENCES systems(id),
+             name TEXT NOT NULL,
+             orbital_radius_au REAL,
+             eccentricity REAL,
+             inclination_deg REAL,
+             orbital_period_days REAL,
+             mass_earth REAL,
+             radius_earth REAL,
+             habitability_score REAL,
+             population REAL
+         );
+         CREATE TABLE moons (
+             id TEXT PRIMARY KEY,
+             planet_id TEXT NOT NULL REFERENCES planets(id),
+             name TEXT NOT NULL,
+             orbital_radius_km REAL,
+             eccentricity REAL,
+             orbital_period_days REAL,
+             mass_kg REAL,
+             radius_m REAL
+         );
+         CREATE INDEX idx_stars_system ON stars(system_id);
+         CREATE INDEX idx_planets_system ON planets(system_id);
+         CREATE INDEX idx_moons_planet ON moons(planet_id);",
+    )?;
+
+    let orbit = |body: &StellarBody| match &body.motion {
+        Some(MotionModel::Keplerian(e)) => (
+            Some(e.semi_major_axis_m),
+            Some(e.eccentricity),
+            Some(e.inclination_rad.to_degrees()),
+            Some(e.orbital_period() / 86_400.0),
+        ),
+        _ => (None, None, None, None),
+    };
+
+    let tx = conn.transaction()?;
+    for system in &dataset.galaxy.star_systems {
+        let pos = system.galactic_position() / Units::LIGHT_YEAR;
+        let hz = system.habitable_zone();
+        tx.execute(
+            "INSERT INTO systems VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                system.id.to_string(),
+                system.name,
+                format!("{:?}", system.system_type),
+                pos.x,
+                pos.y,
+                pos.z,
+                system.galactic_coordinates.longitude_rad.to_degrees(),
+                system.galactic_coordinates.latitude_rad.to_degrees(),
+                system.galactic_coordinates.distance_m / Units::LIGHT_YEAR,
+                system.total_mass() / Units::SOLAR_MASS,
+                hz.map(|(inner, _)| inner / Units::AU),
+                hz.map(|(_, outer)| outer / Units::AU),
+            ],
+        )?;
+
+        for star in &system.stars {
+            let physical = match &star.physical {
+                Some(Physical::Star(s)) => Some(s),
+                _ => None,
+            };
+            tx.execute(
+                "INSERT INTO stars VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    star.id.to_string(),
+                    system.id.to_string(),
+                    star.name,
+                    physical.map(|s| s.spectral_type.clone()),
+                    physical.map(|s| s.mass_solar()),
+                    physical.map(|s| s.radius_solar()),
+                    physical.map(|s| s.luminosity_solar()),
+                    physical.map(|s| s.temperature_k),
+                ],
+            )?;
+        }
+
+        for planet in &system.planets {
+            let physical = match &planet.physical {
+                Some(Physical::Planet(p)) => Some(p),
+                _ => None,
+            };
+            let (a, e, i, period) = orbit(planet);
+            tx.execute(
+                "INSERT INTO planets VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    planet.id.to_string(),
+                    system.id.to_string(),
+                    planet.name,
+                    a.map(|a| a / Units::AU),
+                    e,
+                    i,
+                    period,
+                    physical.map(|p| p.mass_kg / Units::EARTH_MASS),
+                    physical.map(|p| p.radius_m / Units::EARTH_RADIUS),
+                    physical.and_then(|p| p.habitability_score),
+                    physical.and_then(|p| p.population),
+                ],
+            )?;
+
+            for moon in planet.children.iter().filter(|c| c.kind == BodyKind::Moon) {
+                let (a, e, _, period) = orbit(moon);
+                tx.execute(
+                    "INSERT INTO moons VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        moon.id.to_string(),
+                        planet.id.to_string(),
+                        moon.name,
+                        a.map(|a| a / 1000.0),
+                        e,
+                        period,
+                        moon.physical.as_ref().and_then(|p| p.mass_kg()),
+                        moon.physical.as_ref().and_then(|p| p.radius_m()),
+                    ],
+                )?;
+            }
+        }
+    }
+    tx.commit()
+}
+
 // Database repository traits
 pub trait Repository<T> {
     type Error;
@@ -171,6 +598,18 @@ pub trait Repository<T> {
     fn get_all(&self) -> Result<Vec<T>, Self::Error>;
 }
 
+// Async counterpart of Repository for database-backed stores
+#[async_trait::async_trait]
+pub trait AsyncRepository<T: Send + Sync> {
+    type Error;
+
+    async fn insert(&mut self, entity: &T) -> Result<Id, Self::Error>;
+    async fn update(&mut self, entity: &T) -> Result<(), Self::Error>;
+    async fn delete(&mut self, id: Id) -> Result<(), Self::Error>;
+    async fn get(&self, id: Id) -> Result<Option<T>, Self::Error>;
+    async fn get_all(&self) -> Result<Vec<T>, Self::Error>;
+}
+
 // PostgreSQL repository implementation
 #[cfg(feature = "postgres")]
 pub mod postgres {
@@ -185,7 +624,11 @@ pub mod postgres {
     impl PostgresRepository {
         pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
             let pool = Pool::<Postgres>::connect(database_url).await?;
-            Ok(Self { pool })
+            Ok(Self::from_pool(pool))
+        }
+
+        pub fn from_pool(pool: Pool<Postgres>) -> Self {
+            Self { pool }
         }
 
         pub async fn init_schema(&self) -> Result<(), sqlx::Error> {
@@ -284,7 +727,7 @@ pub mod postgres {
     }
 
     #[async_trait]
-    impl Repository<StellarBody> for PostgresRepository {
+    impl AsyncRepository<StellarBody> for PostgresRepository {
         type Error = sqlx::Error;
 
         async fn insert(&mut self, body: &StellarBody) -> Result<Id, Self::Error> {
@@ -315,7 +758,7 @@ pub mod postgres {
             .bind(serde_json::to_value(&body.spatial_parent).unwrap())
             .bind(body.frame_id)
             .bind(body.epoch)
-            .bind(serde_json::to_value(&body.state).unwrap())
+            .bind(serde_json::to_value(body.state).unwrap())
             .bind(serde_json::to_value(&body.motion).unwrap())
             .bind(serde_json::to_value(&body.physical).unwrap())
             .bind(body.rotation_period_hours)
@@ -350,7 +793,7 @@ pub mod postgres {
             )
             .bind(body.id)
             .bind(&body.name)
-            .bind(serde_json::to_value(&body.state).unwrap())
+            .bind(serde_json::to_value(body.state).unwrap())
             .bind(serde_json::to_value(&body.motion).unwrap())
             .bind(serde_json::to_value(&body.physical).unwrap())
             .bind(&body.tags)
@@ -371,15 +814,68 @@ pub mod postgres {
         }
 
         async fn get(&self, id: Id) -> Result<Option<StellarBody>, Self::Error> {
-            // This would require proper deserialization from the database
-            // For now, returning a placeholder
-            Ok(None)
+            let row = sqlx::query(&format!("SELECT {} FROM bodies WHERE id = $1", BODY_COLUMNS))
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+            row.as_ref().map(body_from_row).transpose()
         }
 
         async fn get_all(&self) -> Result<Vec<StellarBody>, Self::Error> {
-            // This would query and deserialize all bodies
-            Ok(Vec::new())
+            let rows = sqlx::query(&format!("SELECT {} FROM bodies ORDER BY name", BODY_COLUMNS))
+                .fetch_all(&self.pool)
+                .await?;
+            rows.iter().map(body_from_row).collect()
+        }
+    }
+
+    const BODY_COLUMNS: &str = "id, name, kind, spatial_parent, frame_id, epoch, state, motion, physical, \
+        rotation_period_hours, axial_tilt_rad, retrograde_rotation, tags, associations, \
+        visible, color, render_distance, metadata";
+
+    // Rows are flat, so children come back empty and are rebuilt from spatial_parent by the caller
+    fn body_from_row(row: &sqlx::postgres::PgRow) -> Result<StellarBody, sqlx::Error> {
+        fn json<T: serde::de::DeserializeOwned>(row: &sqlx::postgres::PgRow, column: &str) -> Result<T, sqlx::Error> {
+            let value: Option<serde_json::Value> = row.try_get(column)?;
+            serde_json::from_value(value.unwrap_or(serde_json::Value::Null))
+                .map_err(|e| sqlx::Error::ColumnDecode { index: column.to_string(), source: Box::new(e) })
+        }
+
+        let kind: String = row.try_get("kind")?;
+        let color: Option<Vec<f32>> = row.try_get("color")?;
+
+        Ok(StellarBody {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            kind: parse_body_kind(&kind).ok_or_else(|| sqlx::Error::ColumnDecode {
+                index: "kind".to_string(),
+                source: format!("unknown body kind '{}'", kind).into(),
+            })?,
+            spatial_parent: json(row, "spatial_parent")?,
+            frame_id: row.try_get("frame_id")?,
+            children: Vec::new(),
+            epoch: row.try_get("epoch")?,
+            state: json(row, "state")?,
+            motion: json(row, "motion")?,
+            physical: json(row, "physical")?,
+            rotation_period_hours: row.try_get("rotation_period_hours")?,
+            axial_tilt_rad: row.try_get("axial_tilt_rad")?,
+            retrograde_rotation: row.try_get("retrograde_rotation")?,
+            tags: row.try_get("tags")?,
+            associations: json(row, "associations")?,
+            visible: row.try_get("visible")?,
+            color: color.and_then(|c| <[f32; 3]>::try_from(c).ok()),
+            render_distance: row.try_get("render_distance")?,
+            metadata: json(row, "metadata")?,
+        })
+    }
+
+    // Inverse of the Debug formatting used for the kind column, e.g. "Planet" or "Custom(7)"
+    fn parse_body_kind(kind: &str) -> Option<BodyKind> {
+        if let Some(id) = kind.strip_prefix("Custom(").and_then(|rest| rest.strip_suffix(')')) {
+            return id.parse().ok().map(BodyKind::Custom);
         }
+        serde_json::from_value(serde_json::Value::String(kind.to_string())).ok()
     }
 
     // Spatial queries using PostGIS
@@ -444,27 +940,26 @@ impl ImportExport {
         let mut file = std::fs::File::create(path)?;
 
         // Header
-        writeln!(file, "System,Star,Spectral,X_LY,Y_LY,Z_LY,Planets,Habitable")?;
+        writeln!(file, "System,Star,Spectral,X_LY,Y_LY,Z_LY,Planets,Habitable,Mass_Solar,Radius_Solar,Luminosity_Solar")?;
 
         for system in &galaxy.star_systems {
             let pos = system.galactic_position() / crate::stellar_forge::core::Units::LIGHT_YEAR;
 
             for star in &system.stars {
-                let spectral = if let Some(physical) = &star.physical {
-                    if let crate::stellar_forge::physical::Physical::Star(s) = physical {
-                        s.spectral_type.clone()
-                    } else {
-                        "Unknown".to_string()
-                    }
-                } else {
-                    "Unknown".to_string()
+                let star_physical = match &star.physical {
+                    Some(crate::stellar_forge::physical::Physical::Star(s)) => Some(s),
+                    _ => None,
                 };
+                let spectral = star_physical
+                    .map(|s| s.spectral_type.clone())
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let solar = |value: Option<f64>| value.map(|v| format!("{:.3}", v)).unwrap_or_default();
 
                 let habitable = system.habitable_zone().is_some();
 
                 writeln!(
                     file,
-                    "{},{},{},{:.2},{:.2},{:.2},{},{}",
+                    "{},{},{},{:.2},{:.2},{:.2},{},{},{},{},{}",
                     system.name,
                     star.name,
                     spectral,
@@ -472,7 +967,118 @@ impl ImportExport {
                     pos.y,
                     pos.z,
                     system.planets.len(),
-                    habitable
+                    habitable,
+                    solar(star_physical.map(|s| s.mass_solar())),
+                    solar(star_physical.map(|s| s.radius_solar())),
+                    solar(star_physical.map(|s| s.luminosity_solar()))
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Export every body in every system, one row each, with nested bodies following their parent
+    pub fn export_bodies_to_csv(galaxy: &Galaxy, path: impl AsRef<Path>) -> std::io::Result<()> {
+        use std::io::Write;
+        use crate::stellar_forge::physical::Physical;
+
+        let mut file = std::fs::File::create(path)?;
+        writeln!(file, "System,Body,Kind,Parent,Orbital_Radius_AU,Mass_kg,Habitability")?;
+
+        let quote = |text: &str| format!("\"{}\"", text.replace('"', "\"\""));
+
+        for system in &galaxy.star_systems {
+            let parent_name = |body: &StellarBody| match body.spatial_parent {
+                crate::stellar_forge::bodies::SpatialParent::Body(id) if id == system.id => system.name.clone(),
+                crate::stellar_forge::bodies::SpatialParent::Body(id) => system
+                    .find_body_recursive(id)
+                    .map(|parent| parent.name.clone())
+                    .unwrap_or_default(),
+                crate::stellar_forge::bodies::SpatialParent::Frame(_) => system.name.clone(),
+            };
+
+            let top_level = system.stars.iter()
+                .chain(&system.planets)
+                .chain(&system.belts)
+                .chain(&system.stations)
+                .chain(&system.other_bodies);
+
+            for top in top_level {
+                for body in std::iter::once(top).chain(top.descendants()) {
+                    let orbital_radius_m = match (&body.motion, &body.physical) {
+                        (Some(MotionModel::Keplerian(elements)), _) => elements.semi_major_axis_m,
+                        (_, Some(physical @ Physical::Belt(_))) => physical.radius_m().unwrap_or(0.0),
+                        _ => body.state.position_m.norm(),
+                    };
+                    let mass = body.physical.as_ref()
+                        .and_then(|p| p.mass_kg())
+                        .map(|m| format!("{:.4e}", m))
+                        .unwrap_or_default();
+                    let habitability = match &body.physical {
+                        Some(Physical::Planet(p)) | Some(Physical::Moon(p)) => {
+                            p.habitability_score.map(|h| format!("{:.2}", h)).unwrap_or_default()
+                        }
+                        _ => String::new(),
+                    };
+
+                    writeln!(
+                        file,
+                        "{},{},{:?},{},{:.6},{},{}",
+                        quote(&system.name),
+                        quote(&body.name),
+                        body.kind,
+                        quote(&parent_name(body)),
+                        orbital_radius_m / Units::AU,
+                        mass,
+                        habitability
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Write every star as seen from the Sun as a Stellarium script: running it from the
+    // script console adds each star as a custom object at its J2000 RA/Dec, with the
+    // apparent magnitude in a trailing comment. Stars less than 1 AU from the Sun are skipped.
+    pub fn export_stellarium_script(galaxy: &Galaxy, path: impl AsRef<Path>) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let mut file = std::fs::File::create(path)?;
+        writeln!(file, "// {} custom objects for Stellarium, J2000 RA/Dec from the Sun", galaxy.name)?;
+        writeln!(file, "// Run from the script console (F12)")?;
+
+        let sexagesimal = |value: f64| {
+            let total_s = (value.abs() * 3600.0 * 10.0).round() / 10.0;
+            let (whole, minutes) = ((total_s / 3600.0).floor(), ((total_s % 3600.0) / 60.0).floor());
+            (whole, minutes, total_s % 60.0)
+        };
+
+        for system in &galaxy.star_systems {
+            if system.galactic_coordinates.distance_m < Units::AU {
+                continue;
+            }
+            let equatorial = system.equatorial_coordinates();
+            let distance_pc = equatorial.distance_m / Units::PARSEC;
+            let (ra_h, ra_m, ra_s) = sexagesimal(equatorial.ra_hours().rem_euclid(24.0));
+            let dec_sign = if equatorial.dec_degrees() < 0.0 { '-' } else { '+' };
+            let (dec_d, dec_m, dec_s) = sexagesimal(equatorial.dec_degrees());
+
+            for star in &system.stars {
+                let Some(crate::stellar_forge::physical::Physical::Star(physical)) = &star.physical else {
+                    continue;
+                };
+                let magnitude = physical.absolute_magnitude() + 5.0 * (distance_pc / 10.0).log10();
+
+                writeln!(
+                    file,
+                    "CustomObjectMgr.addCustomObject(\"{}\", \"{:02}h{:02}m{:04.1}s\", \"{}{:02}d{:02}m{:04.1}s\", true); // V {:.2}",
+                    star.name.replace('"', "'"),
+                    ra_h, ra_m, ra_s,
+                    dec_sign, dec_d, dec_m, dec_s,
+                    magnitude
                 )?;
             }
         }
@@ -506,7 +1112,7 @@ impl Migration {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stellar_forge::builders::{SystemBuilder, GalaxyBuilder};
+    use crate::stellar_forge::builders::{SystemBuilder, GalaxyBuilder, MoonBuilder, PlanetBuilder};
 
     #[test]
     fn test_dataset_creation() {
@@ -523,6 +1129,7 @@ mod tests {
         assert_eq!(dataset.version, "1.0.0");
         assert_eq!(dataset.metadata.statistics.total_systems, 1);
         assert_eq!(dataset.metadata.statistics.total_stars, 1);
+        assert_eq!(dataset.metadata.statistics.total_population, 0.0);
     }
 
     #[test]
@@ -535,4 +1142,319 @@ mod tests {
 
         assert_eq!(parsed.galaxy.name, "Test");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_load_json_rejects_newer_major_version() {
+        let mut dataset = StellarForgeDataset::new(Galaxy::new("Future"));
+        let path = std::env::temp_dir().join("solarviewer_future_version.json");
+
+        FileStorage::save_json(&dataset, &path).unwrap();
+        assert_eq!(FileStorage::load_json(&path).unwrap().version, DATASET_VERSION);
+
+        dataset.version = "2.0.0".to_string();
+        FileStorage::save_json(&dataset, &path).unwrap();
+        let err = FileStorage::load_json(&path).unwrap_err();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("dataset version 2.0.0 is newer than the supported 1.0.0"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_reports_corrupted_dataset() {
+        let galaxy = GalaxyBuilder::new("Test Galaxy")
+            .with_system(
+                SystemBuilder::new("Test System")
+                    .with_star("G2V")
+                    .with_planet(PlanetBuilder::terrestrial("Terra", 1.0).with_moon(MoonBuilder::new("Luna")))
+            )
+            .build();
+        let mut dataset = StellarForgeDataset::new(galaxy);
+        assert_eq!(dataset.validate(), Vec::<String>::new());
+
+        let system = &mut dataset.galaxy.star_systems[0];
+        if let Some(crate::stellar_forge::physical::Physical::Planet(p)) = &mut system.planets[0].physical {
+            p.mass_kg = -1.0;
+        }
+        system.planets[0].children[0].spatial_parent = crate::stellar_forge::bodies::SpatialParent::Body(Id::new_v4());
+        if let Some(MotionModel::Keplerian(elements)) = &mut system.planets[0].motion {
+            elements.eccentricity = 1.5;
+        }
+        dataset.metadata.statistics.total_stars = 7;
+
+        let path = std::env::temp_dir().join("solarviewer_corrupted_dataset.json");
+        FileStorage::save_json(&dataset, &path).unwrap();
+        let loaded = FileStorage::load_json(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let problems = loaded.validate();
+        assert_eq!(problems.len(), 4, "{:#?}", problems);
+        assert!(problems.iter().any(|p| p.contains("Terra has invalid mass")));
+        assert!(problems.iter().any(|p| p.contains("Luna references missing parent")));
+        assert!(problems.iter().any(|p| p.contains("Terra has an invalid orbit")));
+        assert!(problems.iter().any(|p| p.contains("stored statistics")));
+    }
+
+    #[test]
+    fn test_validate_accepts_hyperbolic_comet() {
+        let mut galaxy = GalaxyBuilder::new("Test Galaxy")
+            .with_system(
+                SystemBuilder::new("Test System")
+                    .with_star("G2V")
+                    .with_planet(PlanetBuilder::terrestrial("Terra", 1.0))
+            )
+            .build();
+
+        // An interstellar visitor on an open orbit, e > 1
+        let system = &mut galaxy.star_systems[0];
+        let mut comet = system.planets[0].clone();
+        comet.id = Id::new_v4();
+        comet.name = "Oumuamua".to_string();
+        comet.kind = BodyKind::Comet;
+        if let Some(MotionModel::Keplerian(elements)) = &mut comet.motion {
+            elements.eccentricity = 1.2;
+        }
+        let mut planet = comet.clone();
+        planet.id = Id::new_v4();
+        planet.name = "Runaway".to_string();
+        planet.kind = BodyKind::Planet;
+        system.other_bodies.push(comet);
+        system.other_bodies.push(planet);
+
+        let problems = StellarForgeDataset::new(galaxy).validate();
+        assert_eq!(problems.len(), 1, "{:#?}", problems);
+        assert!(problems[0].contains("Runaway has an invalid orbit"));
+    }
+
+    #[test]
+    fn test_summary_counts() {
+        let galaxy = GalaxyBuilder::new("Local")
+            .with_system(SystemBuilder::new("Sol").with_star("G2V").with_planet(PlanetBuilder::terrestrial("Earth", 1.0)))
+            .with_system(SystemBuilder::new("Near").at_position(4.0, 0.0, 0.0).with_star("M5V"))
+            .with_system(SystemBuilder::new("Far").at_position(0.0, 12.0, -3.0).with_binary_stars("K1V", "M3V", 20.0))
+            .build();
+        let mut dataset = StellarForgeDataset::new(galaxy);
+        dataset.metadata.name = "Local".into();
+
+        let summary = dataset.summary();
+        assert_eq!(summary.spectral_histogram.get("M"), Some(&2));
+        assert_eq!(summary.nearest_system.as_ref().map(|s| s.0.as_str()), Some("Near"));
+        assert_eq!(summary.farthest_system.as_ref().map(|s| s.0.as_str()), Some("Far"));
+
+        let printed = summary.to_string();
+        assert!(printed.contains("  Systems: 3\n"));
+        assert!(printed.contains("  Stars: 4\n"));
+        assert!(printed.contains("  Planets: 1\n"));
+        assert!(printed.contains("  G: 1\n  K: 1\n  M: 2\n"));
+    }
+
+    #[test]
+    fn test_merge_json_files() {
+        let dir = std::env::temp_dir();
+        let write = |file: &str, names: &[&str]| {
+            let mut galaxy = Galaxy::new("Part");
+            for name in names {
+                galaxy.add_star_system(SystemBuilder::new(*name).with_star("G2V").build()).unwrap();
+            }
+            let path = dir.join(file);
+            FileStorage::save_json(&StellarForgeDataset::new(galaxy), &path).unwrap();
+            path
+        };
+        let a = write("solarviewer_merge_a.json", &["Alpha", "Beta", "Gamma"]);
+        let b = write("solarviewer_merge_b.json", &["Gamma", "Delta"]);
+
+        let (merged, summary) = FileStorage::merge_json(&[&a, &b], MergePolicy::KeepExisting).unwrap();
+        std::fs::remove_file(&a).ok();
+        std::fs::remove_file(&b).ok();
+
+        // Five systems in, one duplicate dropped
+        assert_eq!(merged.galaxy.star_systems.len(), 4);
+        assert_eq!(merged.metadata.statistics.total_systems, 4);
+        assert_eq!(merged.metadata.statistics.total_stars, 4);
+        assert_eq!(summary, MergeSummary { added: 1, skipped: 1, ..Default::default() });
+    }
+
+    #[test]
+    fn test_export_and_reimport_system() {
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(SystemBuilder::new("Alpha").with_star("K1V").build()).unwrap();
+        galaxy.add_star_system(
+            SystemBuilder::new("Beta")
+                .with_star("G2V")
+                .with_planet(PlanetBuilder::terrestrial("Beta b", 1.0))
+                .with_planet(PlanetBuilder::gas_giant("Beta c", 5.0))
+                .build(),
+        ).unwrap();
+        let mut dataset = StellarForgeDataset::new(galaxy);
+
+        let path = std::env::temp_dir().join("solarviewer_export_system_test.json");
+        assert_eq!(
+            dataset.export_system_named("Gamma", &path).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+        dataset.export_system_named("beta", &path).unwrap();
+        let mut system = FileStorage::import_system(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let original = dataset.galaxy.find_system_by_name("Beta").unwrap().clone();
+        assert_eq!(system.id, original.id);
+        let names = |s: &StarSystem| s.planets.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&system), names(&original));
+
+        system.planets.pop();
+        assert!(dataset.import_system(system).unwrap());
+        assert_eq!(dataset.galaxy.star_systems.len(), 2);
+        assert_eq!(dataset.galaxy.find_system_by_name("Beta").unwrap().planets.len(), 1);
+        assert_eq!(dataset.metadata.statistics.total_planets, 1);
+    }
+
+    #[test]
+    fn test_export_bodies_covers_hierarchy() {
+        let mut galaxy = Galaxy::new("Local");
+        let sol = crate::stellar_forge::builders::create_sol_like_system().build();
+        let moons: usize = sol.planets.iter().map(|p| p.children.len()).sum();
+        let expected = sol.stars.len() + sol.planets.len() + moons + sol.belts.len();
+        galaxy.add_star_system(sol).unwrap();
+
+        let path = std::env::temp_dir().join("solarviewer_bodies_export_test.csv");
+        ImportExport::export_bodies_to_csv(&galaxy, &path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let rows: Vec<&str> = csv.lines().skip(1).collect();
+        assert!(moons > 0);
+        assert_eq!(rows.len(), expected);
+
+        let moon = rows.iter().find(|r| r.contains("\"Moon\",Moon")).unwrap();
+        assert!(moon.contains(",\"Earth\","), "{}", moon);
+        let earth = rows.iter().find(|r| r.starts_with("\"Sol\",\"Earth\"")).unwrap();
+        assert!(earth.contains(",\"Sol\",1.000000,"), "{}", earth);
+    }
+
+    #[test]
+    fn test_export_stellarium_script_places_sirius() {
+        use crate::stellar_forge::builders::SystemBuilder;
+        use crate::stellar_forge::physical::Physical;
+
+        let mut galaxy = Galaxy::new("Local");
+        galaxy.add_star_system(crate::stellar_forge::builders::create_sol_like_system().build()).unwrap();
+        let mut sirius = SystemBuilder::new("Sirius").with_star("A1V").at_galactic(227.23, -8.89, 2.64).build();
+        if let Some(Physical::Star(star)) = &mut sirius.stars[0].physical {
+            star.luminosity_w = 25.4 * Units::SOLAR_LUMINOSITY;
+        }
+        galaxy.add_star_system(sirius).unwrap();
+
+        let path = std::env::temp_dir().join("solarviewer_stellarium_export_test.ssc");
+        ImportExport::export_stellarium_script(&galaxy, &path).unwrap();
+        let script = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // The Sun itself isn't placed on its own sky
+        let objects: Vec<&str> = script.lines().filter(|l| l.starts_with("CustomObjectMgr")).collect();
+        assert_eq!(objects.len(), 1, "{}", script);
+
+        // Sirius sits at RA 06h45m09s, Dec -16°43', shining at V = -1.46
+        let line = objects[0];
+        assert!(line.contains("\"Sirius A\", \"06h45m"), "{}", line);
+        assert!(line.contains("\"-16d4"), "{}", line);
+        let magnitude: f64 = line.rsplit("// V ").next().unwrap().parse().unwrap();
+        assert!((magnitude + 1.46).abs() < 0.15, "{}", line);
+    }
+
+    #[test]
+    fn test_export_sqlite_counts() {
+        let mut galaxy = Galaxy::new("Local");
+        galaxy.add_star_system(crate::stellar_forge::builders::create_sol_like_system().build()).unwrap();
+        galaxy.add_star_system(
+            SystemBuilder::new("Alpha")
+                .at_position(4.3, 0.0, 0.0)
+                .with_binary_stars("G2V", "K1V", 23.0)
+                .build(),
+        ).unwrap();
+        let dataset = StellarForgeDataset::new(galaxy);
+        let stats = &dataset.metadata.statistics;
+
+        let path = std::env::temp_dir().join("solarviewer_sqlite_export_test.db");
+        let path = path.to_str().unwrap();
+        export_sqlite(&dataset, path).unwrap();
+        // A second export replaces the first rather than failing or duplicating rows
+        export_sqlite(&dataset, path).unwrap();
+
+        let conn = rusqlite::Connection::open(path).unwrap();
+        let count = |table: &str| -> usize {
+            conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get::<_, i64>(0)).unwrap() as usize
+        };
+        assert_eq!(count("systems"), stats.total_systems);
+        assert_eq!(count("stars"), stats.total_stars);
+        assert_eq!(count("planets"), stats.total_planets);
+        assert_eq!(count("moons"), stats.total_moons);
+
+        let earth_au: f64 = conn
+            .query_row("SELECT orbital_radius_au FROM planets WHERE name = 'Earth'", [], |row| row.get(0))
+            .unwrap();
+        assert!((earth_au - 1.0).abs() < 1e-9);
+        let moons_of_mars: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM moons JOIN planets ON moons.planet_id = planets.id WHERE planets.name = 'Mars'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(moons_of_mars, 2);
+        drop(conn);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    async fn test_postgres_repository_is_async_repository() {
+        use crate::stellar_forge::storage::postgres::PostgresRepository;
+
+        fn assert_repository<R: AsyncRepository<StellarBody, Error = sqlx::Error>>(_: &R) {}
+
+        // Lazy pools don't connect until first use, so no server is needed
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy(crate::stellar_forge::database::DEFAULT_DATABASE_URL)
+            .unwrap();
+        let repository = PostgresRepository::from_pool(pool);
+        assert_repository(&repository);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL with PostGIS
+    async fn test_postgres_body_round_trip() {
+        use crate::stellar_forge::database::resolve_database_url;
+        use crate::stellar_forge::storage::postgres::PostgresRepository;
+
+        let mut repository = PostgresRepository::new(&resolve_database_url(None)).await.unwrap();
+        repository.init_schema().await.unwrap();
+
+        let system = SystemBuilder::new("Round Trip")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Terra", 1.0))
+            .build();
+        let mut body = system.planets[0].clone();
+        // TIMESTAMPTZ keeps microseconds
+        body.epoch = body.epoch.replace_nanosecond(body.epoch.microsecond() * 1000).unwrap();
+        body.color = Some([0.2, 0.4, 0.8]);
+        body.metadata.insert("survey".to_string(), serde_json::json!({ "complete": true }));
+
+        let pool = sqlx::PgPool::connect(&resolve_database_url(None)).await.unwrap();
+        sqlx::query("INSERT INTO frames (id, kind, name, epoch) VALUES ($1, 'Barycentric', 'Round Trip', $2) ON CONFLICT DO NOTHING")
+            .bind(body.frame_id)
+            .bind(body.epoch)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        repository.insert(&body).await.unwrap();
+        let fetched = repository.get(body.id).await.unwrap().expect("body was inserted");
+        let listed = repository.get_all().await.unwrap();
+        repository.delete(body.id).await.unwrap();
+
+        assert_eq!(serde_json::to_value(&fetched).unwrap(), serde_json::to_value(&body).unwrap());
+        assert!(listed.iter().any(|b| b.id == body.id));
+        assert!(repository.get(body.id).await.unwrap().is_none());
+    }
+}
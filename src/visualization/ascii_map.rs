@@ -0,0 +1,156 @@
+/// Terminal star map - a top-down character grid for quick inspection over SSH
+use crate::extraction::Star;
+
+use super::spectral::{get_spectral_colors, SpectralType};
+
+/// Glyph used for the center star
+pub const CENTER_GLYPH: char = '@';
+
+/// One character per spectral class, so the map reads without color
+pub fn spectral_glyph(spectral_type: SpectralType) -> char {
+    match spectral_type {
+        SpectralType::O => 'O',
+        SpectralType::B => 'B',
+        SpectralType::A => 'A',
+        SpectralType::F => 'F',
+        SpectralType::G => 'G',
+        SpectralType::K => 'K',
+        SpectralType::M => 'M',
+        SpectralType::L => 'L',
+        SpectralType::T => 'T',
+        SpectralType::Y => 'Y',
+        SpectralType::C => 'C',
+        SpectralType::S => 'S',
+        SpectralType::W => 'W',
+        SpectralType::WhiteDwarf => 'w',
+        SpectralType::Unknown => '*',
+    }
+}
+
+/// Render the stars within `radius_ly` of `center` as a `width` x `height` grid, colored with
+/// ANSI 24-bit escapes in each class's core color
+/// The view looks down the z axis with +y up. Stars sharing a cell show the most luminous
+/// one, and the center star is always drawn as `@` in the middle of the grid.
+pub fn render_ascii_map(stars: &[Star], center: &Star, radius_ly: f64, width: usize, height: usize) -> String {
+    render_grid(stars, center, radius_ly, width, height, true)
+}
+
+/// Same as `render_ascii_map` without color escapes, for logs and dumb terminals
+pub fn render_ascii_map_plain(stars: &[Star], center: &Star, radius_ly: f64, width: usize, height: usize) -> String {
+    render_grid(stars, center, radius_ly, width, height, false)
+}
+
+struct Cell {
+    glyph: char,
+    spectral: SpectralType,
+    luminosity: f64,
+}
+
+fn render_grid(stars: &[Star], center: &Star, radius_ly: f64, width: usize, height: usize, color: bool) -> String {
+    let mut grid: Vec<Option<Cell>> = (0..width * height).map(|_| None).collect();
+
+    if width > 0 && height > 0 && radius_ly > 0.0 {
+        let to_cell = |offset: f64, cells: usize| {
+            let fraction = (offset / radius_ly + 1.0) / 2.0;
+            (fraction * (cells - 1) as f64).round() as usize
+        };
+
+        let cell_index = |star: &Star| {
+            let col = to_cell(star.x - center.x, width);
+            let row = height - 1 - to_cell(star.y - center.y, height);
+            row * width + col
+        };
+
+        for star in stars.iter().filter(|s| s.id != center.id && s.distance_to(center) <= radius_ly) {
+            let spectral = star.spectral_type.parse().unwrap_or(SpectralType::Unknown);
+            let cell = &mut grid[cell_index(star)];
+            if cell.as_ref().is_none_or(|c| star.luminosity_solar > c.luminosity) {
+                *cell = Some(Cell { glyph: spectral_glyph(spectral), spectral, luminosity: star.luminosity_solar });
+            }
+        }
+
+        let spectral = center.spectral_type.parse().unwrap_or(SpectralType::Unknown);
+        grid[cell_index(center)] = Some(Cell { glyph: CENTER_GLYPH, spectral, luminosity: f64::INFINITY });
+    }
+
+    let mut out = String::with_capacity(width * height + height);
+    for row in grid.chunks(width.max(1)).take(height) {
+        for cell in row {
+            match cell {
+                Some(cell) if color => {
+                    let (core, _) = get_spectral_colors(cell.spectral);
+                    out.push_str(&format!("\x1b[38;2;{};{};{}m{}\x1b[0m", core.r, core.g, core.b, cell.glyph));
+                }
+                Some(cell) => out.push(cell.glyph),
+                None => out.push(' '),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(id: i32, spectral_type: &str, x: f64, y: f64) -> Star {
+        Star {
+            id,
+            name: format!("Star {}", id),
+            spectral_type: spectral_type.to_string(),
+            radius_solar: 1.0,
+            mass_solar: 1.0,
+            luminosity_solar: 1.0,
+            temperature_k: 5778.0,
+            x,
+            y,
+            z: 0.0,
+            system_name: None,
+            system_x: x,
+            system_y: y,
+            system_z: 0.0,
+            catalog_id: None,
+            hip: None,
+            hd: None,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        }
+    }
+
+    #[test]
+    fn test_center_star_at_grid_center() {
+        let stars = vec![
+            star(1, "G2V", 10.0, 20.0),
+            star(2, "M5V", 20.0, 20.0),  // Right edge
+            star(3, "K1V", 10.0, 30.0),  // Top edge
+            star(4, "A0V", 100.0, 20.0), // Outside the radius
+        ];
+        let center = &stars[0];
+
+        let plain = render_ascii_map_plain(&stars, center, 10.0, 41, 21);
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 21);
+        assert!(lines.iter().all(|line| line.chars().count() == 41));
+
+        assert_eq!(lines[10].chars().nth(20), Some('@'));
+        assert_eq!(lines[10].chars().nth(40), Some('M'));
+        assert_eq!(lines[0].chars().nth(20), Some('K'));
+        assert!(!plain.contains('A'));
+
+        // Color escapes wrap the glyphs without moving them
+        let colored = render_ascii_map(&stars, center, 10.0, 41, 21);
+        assert!(colored.contains("\x1b[38;2;"));
+        let mut stripped = String::new();
+        let mut chars = colored.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                stripped.push(c);
+            }
+        }
+        assert_eq!(stripped, plain);
+    }
+}
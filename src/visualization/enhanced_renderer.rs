@@ -1,13 +1,15 @@
 /// Enhanced star map renderer with spectral colors and labels
-use image::{ImageBuffer, Rgb, RgbImage};
-use imageproc::drawing::{draw_filled_circle_mut, draw_line_segment_mut};
-use std::path::Path;
+use image::{GenericImage, ImageBuffer, Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_circle_mut, draw_hollow_circle_mut, draw_line_segment_mut, Canvas};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use anyhow::Result;
 
 use super::projection::Point2D;
 use super::spectral::{SpectralType, get_spectral_colors};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct StarDataEnhanced {
     pub name: String,
     pub x: f64,
@@ -15,6 +17,60 @@ pub struct StarDataEnhanced {
     pub z: f64,
     pub spectral_type: SpectralType,
     pub luminosity: f64,
+    /// Core color to draw instead of the spectral color
+    pub color: Option<Rgb<u8>>,
+    pub marker: Option<StarMarker>,
+}
+
+/// Symbol framing a star on the map to call it out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarMarker {
+    /// Gold square, e.g. for a capital
+    Capital,
+    /// Magenta diamond, e.g. for an anomaly
+    Anomaly,
+    /// Cyan triangle, e.g. for an outpost
+    Outpost,
+}
+
+impl StarMarker {
+    fn color(self) -> Rgb<u8> {
+        match self {
+            StarMarker::Capital => Rgb([255, 215, 0]),
+            StarMarker::Anomaly => Rgb([255, 60, 200]),
+            StarMarker::Outpost => Rgb([120, 220, 255]),
+        }
+    }
+
+    /// Corners of the closed outline around a star of `radius` pixels at the origin
+    fn outline(self, radius: f32) -> Vec<(f32, f32)> {
+        let r = radius + 18.0;
+        match self {
+            StarMarker::Capital => vec![(-r, -r), (r, -r), (r, r), (-r, r)],
+            StarMarker::Anomaly => {
+                let r = r * std::f32::consts::SQRT_2;
+                vec![(0.0, -r), (r, 0.0), (0.0, r), (-r, 0.0)]
+            }
+            StarMarker::Outpost => {
+                // Equilateral triangle whose inscribed circle clears the star
+                let (r, h) = (2.0 * r, r * 3.0_f32.sqrt());
+                vec![(0.0, -r), (h, r / 2.0), (-h, r / 2.0)]
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for StarMarker {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "capital" | "square" => Ok(StarMarker::Capital),
+            "anomaly" | "diamond" => Ok(StarMarker::Anomaly),
+            "outpost" | "triangle" => Ok(StarMarker::Outpost),
+            other => Err(format!("Unknown marker '{}' (expected capital, anomaly or outpost)", other)),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -24,10 +80,29 @@ pub struct StarConnection {
     pub distance_ly: f64,
 }
 
+/// Evenly spaced horizontal and vertical lines in pixel space
+#[derive(Debug, Clone, Copy)]
+pub struct GridLines {
+    pub spacing_px: f64,
+    /// A point that lines pass through, so the grid can be anchored to a world coordinate
+    pub offset_px: (f64, f64),
+    pub color: Rgb<u8>,
+}
+
+/// Title text drawn across the top of the map
+pub struct Caption {
+    pub text: String,
+    pub font: fontdue::Font,
+    pub color: Rgb<u8>,
+}
+
 pub struct EnhancedStarMapRenderer {
     width: u32,
     height: u32,
     background: Rgb<u8>,
+    grid: Option<GridLines>,
+    caption: Option<Caption>,
+    highlighted: HashSet<usize>,
 }
 
 impl Default for EnhancedStarMapRenderer {
@@ -36,6 +111,9 @@ impl Default for EnhancedStarMapRenderer {
             width: 5000,
             height: 5000,
             background: Rgb([0, 0, 0]), // Black
+            grid: None,
+            caption: None,
+            highlighted: HashSet::new(),
         }
     }
 }
@@ -49,6 +127,29 @@ impl EnhancedStarMapRenderer {
         }
     }
 
+    pub fn with_background(mut self, background: Rgb<u8>) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Draw grid lines under the connections and stars
+    /// Spacings under two pixels would only flood the image, so they draw nothing.
+    pub fn with_grid(mut self, grid: GridLines) -> Self {
+        self.grid = Some(grid);
+        self
+    }
+
+    pub fn with_caption(mut self, caption: Caption) -> Self {
+        self.caption = Some(caption);
+        self
+    }
+
+    /// Ring the stars at these indices, e.g. systems matching a highlight tag
+    pub fn with_highlighted(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.highlighted = indices.into_iter().collect();
+        self
+    }
+
     /// Convert our Color type to image::Rgb
     fn color_to_rgb(color: super::spectral::Color) -> Rgb<u8> {
         Rgb([color.r, color.g, color.b])
@@ -64,6 +165,78 @@ impl EnhancedStarMapRenderer {
     ) -> Result<()> {
         // Create image buffer with background color
         let mut img: RgbImage = ImageBuffer::from_pixel(self.width, self.height, self.background);
+        self.draw(&mut img, stars, star_positions_2d, connections, center_star_idx);
+
+        // Save image
+        img.save(&output_path)?;
+        Ok(())
+    }
+
+    /// Render the map as `tile_size` square tiles (edge tiles may be smaller) in `output_dir`
+    /// Only one tile is held in memory at a time. Every tile is drawn in full-image
+    /// coordinates, so lines and stars crossing tile edges line up exactly.
+    pub fn render_tiles<P: AsRef<Path>>(
+        &self,
+        stars: &[StarDataEnhanced],
+        star_positions_2d: &[Point2D],
+        connections: &[StarConnection],
+        center_star_idx: Option<usize>,
+        tile_size: u32,
+        output_dir: P,
+    ) -> Result<TileSet> {
+        if tile_size == 0 {
+            anyhow::bail!("Tile size must be positive");
+        }
+
+        let output_dir = output_dir.as_ref();
+        fs::create_dir_all(output_dir)?;
+
+        let columns = self.width.div_ceil(tile_size);
+        let rows = self.height.div_ceil(tile_size);
+        let mut paths = Vec::with_capacity((columns * rows) as usize);
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let origin = (column * tile_size, row * tile_size);
+                let tile_width = tile_size.min(self.width - origin.0);
+                let tile_height = tile_size.min(self.height - origin.1);
+
+                let mut canvas = TileCanvas {
+                    tile: ImageBuffer::from_pixel(tile_width, tile_height, self.background),
+                    origin,
+                    full_size: (self.width, self.height),
+                    background: self.background,
+                };
+                self.draw(&mut canvas, stars, star_positions_2d, connections, center_star_idx);
+
+                let path = output_dir.join(format!("tile_{}_{}.png", row, column));
+                canvas.tile.save(&path)?;
+                paths.push(path);
+            }
+        }
+
+        Ok(TileSet {
+            width: self.width,
+            height: self.height,
+            tile_size,
+            columns,
+            rows,
+            paths,
+        })
+    }
+
+    /// Draw the map onto any canvas laid out in full-image pixel coordinates
+    fn draw<C: Canvas<Pixel = Rgb<u8>>>(
+        &self,
+        img: &mut C,
+        stars: &[StarDataEnhanced],
+        star_positions_2d: &[Point2D],
+        connections: &[StarConnection],
+        center_star_idx: Option<usize>,
+    ) {
+        if let Some(grid) = &self.grid {
+            self.draw_grid(img, grid);
+        }
 
         // Draw connections (lines between nearby stars)
         for conn in connections {
@@ -84,7 +257,7 @@ impl EnhancedStarMapRenderer {
 
                 // Draw line
                 draw_line_segment_mut(
-                    &mut img,
+                    img,
                     (p1.x as f32, p1.y as f32),
                     (p2.x as f32, p2.y as f32),
                     line_color,
@@ -93,7 +266,7 @@ impl EnhancedStarMapRenderer {
                 // Draw line again with smaller offset for thickness effect
                 if width > 1 {
                     draw_line_segment_mut(
-                        &mut img,
+                        img,
                         (p1.x as f32 + 0.5, p1.y as f32 + 0.5),
                         (p2.x as f32 + 0.5, p2.y as f32 + 0.5),
                         line_color,
@@ -112,9 +285,9 @@ impl EnhancedStarMapRenderer {
             let px = pos.x as i32;
             let py = pos.y as i32;
 
-            // Get spectral color
+            // Get spectral color, unless the star has its own
             let (core_color, _glow_color) = get_spectral_colors(star.spectral_type);
-            let star_rgb = Self::color_to_rgb(core_color);
+            let star_rgb = star.color.unwrap_or_else(|| Self::color_to_rgb(core_color));
 
             // Calculate star size based on luminosity
             let size_factor = if star.luminosity > 0.0 {
@@ -142,7 +315,7 @@ impl EnhancedStarMapRenderer {
             // Draw outer glow
             let glow_radius = (radius as f64 * 0.7) as i32;
             if glow_radius > 0 {
-                draw_filled_circle_mut(&mut img, (px, py), glow_radius, glow_color);
+                draw_filled_circle_mut(img, (px, py), glow_radius, glow_color);
             }
 
             // Draw star core
@@ -152,19 +325,92 @@ impl EnhancedStarMapRenderer {
                 star_rgb
             };
 
-            draw_filled_circle_mut(&mut img, (px, py), radius, final_color);
+            draw_filled_circle_mut(img, (px, py), radius, final_color);
 
             // Draw highlight
             let highlight_radius = (radius as f64 * 0.25) as i32;
             if highlight_radius > 0 {
                 let highlight = Rgb([255, 255, 255]);
-                draw_filled_circle_mut(&mut img, (px - 5, py - 5), highlight_radius, highlight);
+                draw_filled_circle_mut(img, (px - 5, py - 5), highlight_radius, highlight);
+            }
+
+            // Ring highlighted stars just outside their disc
+            if self.highlighted.contains(&idx) {
+                let ring = Rgb([80, 255, 160]);
+                for r in (radius + 8)..=(radius + 11) {
+                    draw_hollow_circle_mut(img, (px, py), r, ring);
+                }
+            }
+
+            if let Some(marker) = star.marker {
+                let corners = marker.outline(radius as f32);
+                for (i, &(x1, y1)) in corners.iter().enumerate() {
+                    let (x2, y2) = corners[(i + 1) % corners.len()];
+                    for offset in [0.0, 1.0] {
+                        draw_line_segment_mut(
+                            img,
+                            (pos.x as f32 + x1 + offset, pos.y as f32 + y1 + offset),
+                            (pos.x as f32 + x2 + offset, pos.y as f32 + y2 + offset),
+                            marker.color(),
+                        );
+                    }
+                }
             }
         }
 
-        // Save image
-        img.save(&output_path)?;
-        Ok(())
+        if let Some(caption) = &self.caption {
+            self.draw_caption(img, caption);
+        }
+    }
+
+    fn draw_grid<C: Canvas<Pixel = Rgb<u8>>>(&self, img: &mut C, grid: &GridLines) {
+        if grid.spacing_px.is_nan() || grid.spacing_px < 2.0 {
+            return;
+        }
+        let (width, height) = (self.width as f32, self.height as f32);
+
+        // First line at or left of/above the image edge, then every spacing_px
+        let mut x = grid.offset_px.0 - (grid.offset_px.0 / grid.spacing_px).floor() * grid.spacing_px;
+        while x < self.width as f64 {
+            draw_line_segment_mut(img, (x as f32, 0.0), (x as f32, height), grid.color);
+            x += grid.spacing_px;
+        }
+        let mut y = grid.offset_px.1 - (grid.offset_px.1 / grid.spacing_px).floor() * grid.spacing_px;
+        while y < self.height as f64 {
+            draw_line_segment_mut(img, (0.0, y as f32), (width, y as f32), grid.color);
+            y += grid.spacing_px;
+        }
+    }
+
+    /// Title centered along the top edge, sized to the image
+    fn draw_caption<C: Canvas<Pixel = Rgb<u8>>>(&self, img: &mut C, caption: &Caption) {
+        let px = (self.height as f32 / 40.0).max(12.0);
+        let glyphs: Vec<(fontdue::Metrics, Vec<u8>)> =
+            caption.text.chars().map(|c| caption.font.rasterize(c, px)).collect();
+        let text_width: f32 = glyphs.iter().map(|(metrics, _)| metrics.advance_width).sum();
+        let baseline = (px * 1.5) as i32;
+
+        let mut pen_x = (self.width as f32 - text_width) / 2.0;
+        for (metrics, coverage) in &glyphs {
+            let left = pen_x as i32 + metrics.xmin;
+            let top = baseline - metrics.ymin - metrics.height as i32;
+            for (i, &alpha) in coverage.iter().enumerate() {
+                let x = left + (i % metrics.width) as i32;
+                let y = top + (i / metrics.width) as i32;
+                if alpha == 0 || x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+                    continue;
+                }
+                let under = img.get_pixel(x as u32, y as u32);
+                let blend = |fg: u8, bg: u8| ((fg as u32 * alpha as u32 + bg as u32 * (255 - alpha as u32)) / 255) as u8;
+                let color = Rgb([
+                    blend(caption.color[0], under[0]),
+                    blend(caption.color[1], under[1]),
+                    blend(caption.color[2], under[2]),
+                ]);
+                img.draw_pixel(x as u32, y as u32, color);
+            }
+            pen_x += metrics.advance_width;
+        }
     }
 
     /// Helper method to calculate which connections to draw
@@ -198,6 +444,64 @@ impl EnhancedStarMapRenderer {
     }
 }
 
+/// Tiles written by `render_tiles`, row-major
+#[derive(Debug, Clone)]
+pub struct TileSet {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub paths: Vec<PathBuf>,
+}
+
+impl TileSet {
+    /// Assemble the tiles into one image
+    /// This holds the full image in memory, so it only suits sizes a single render could handle.
+    pub fn stitch<P: AsRef<Path>>(&self, output_path: P) -> Result<()> {
+        let mut img: RgbImage = ImageBuffer::new(self.width, self.height);
+        for (index, path) in self.paths.iter().enumerate() {
+            let row = index as u32 / self.columns;
+            let column = index as u32 % self.columns;
+            let tile = image::open(path)?.to_rgb8();
+            img.copy_from(&tile, column * self.tile_size, row * self.tile_size)?;
+        }
+        img.save(output_path)?;
+        Ok(())
+    }
+}
+
+/// One tile of a larger image; pixels outside the tile are dropped
+struct TileCanvas {
+    tile: RgbImage,
+    origin: (u32, u32),
+    full_size: (u32, u32),
+    background: Rgb<u8>,
+}
+
+impl Canvas for TileCanvas {
+    type Pixel = Rgb<u8>;
+
+    fn dimensions(&self) -> (u32, u32) {
+        self.full_size
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Rgb<u8> {
+        x.checked_sub(self.origin.0)
+            .zip(y.checked_sub(self.origin.1))
+            .and_then(|(tx, ty)| self.tile.get_pixel_checked(tx, ty).copied())
+            .unwrap_or(self.background)
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Rgb<u8>) {
+        if let (Some(tx), Some(ty)) = (x.checked_sub(self.origin.0), y.checked_sub(self.origin.1)) {
+            if tx < self.tile.width() && ty < self.tile.height() {
+                self.tile.put_pixel(tx, ty, color);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,4 +512,103 @@ mod tests {
         assert_eq!(renderer.width, 5000);
         assert_eq!(renderer.height, 5000);
     }
+
+    #[test]
+    fn test_grid_lines_at_spacing() {
+        let background = Rgb([10, 10, 30]);
+        let grid_color = Rgb([60, 60, 90]);
+        let renderer = EnhancedStarMapRenderer::new(100, 80)
+            .with_background(background)
+            .with_grid(GridLines { spacing_px: 20.0, offset_px: (45.0, 50.0), color: grid_color });
+
+        let mut img: RgbImage = ImageBuffer::from_pixel(100, 80, background);
+        renderer.draw(&mut img, &[], &[], &[], None);
+
+        // Vertical lines at x = 5, 25, 45, ...; horizontal lines at y = 10, 30, 50, 70
+        for x in [5, 25, 45, 65, 85] {
+            assert_eq!(*img.get_pixel(x, 3), grid_color, "no vertical line at x = {}", x);
+        }
+        for y in [10, 30, 50, 70] {
+            assert_eq!(*img.get_pixel(2, y), grid_color, "no horizontal line at y = {}", y);
+        }
+        assert_eq!(*img.get_pixel(15, 20), background);
+        assert_eq!(*img.get_pixel(36, 41), background);
+    }
+
+    #[test]
+    fn test_tiled_render_matches_single_image() {
+        let stars: Vec<StarDataEnhanced> = [(0.0, 0.0), (3.0, 1.0), (1.0, 4.0), (4.5, 4.0)]
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| StarDataEnhanced {
+                name: format!("Star {}", i),
+                x,
+                y,
+                z: 0.0,
+                spectral_type: SpectralType::K,
+                luminosity: 0.3,
+                ..Default::default()
+            })
+            .collect();
+        // Stars and connections straddle the tile seams at x = 100 and y = 100
+        let points = vec![
+            Point2D { x: 95.0, y: 30.0 },
+            Point2D { x: 170.0, y: 104.0 },
+            Point2D { x: 30.0, y: 160.0 },
+            Point2D { x: 103.0, y: 97.0 },
+        ];
+        let connections = EnhancedStarMapRenderer::find_connections(&stars, 6.0);
+        let renderer = EnhancedStarMapRenderer::new(200, 200);
+
+        let dir = std::env::temp_dir().join("solarviewer_tiled_render_test");
+        fs::remove_dir_all(&dir).ok();
+        let single = dir.join("single.png");
+        let stitched = dir.join("stitched.png");
+
+        let tiles = renderer
+            .render_tiles(&stars, &points, &connections, Some(0), 100, dir.join("tiles"))
+            .unwrap();
+        assert_eq!((tiles.columns, tiles.rows, tiles.paths.len()), (2, 2, 4));
+        tiles.stitch(&stitched).unwrap();
+        renderer.render_to_file(&stars, &points, &connections, Some(0), &single).unwrap();
+
+        let expected = image::open(&single).unwrap().to_rgb8();
+        let actual = image::open(&stitched).unwrap().to_rgb8();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(expected.dimensions(), actual.dimensions());
+        assert!(expected.pixels().eq(actual.pixels()), "stitched tiles differ from single render");
+    }
+
+    #[test]
+    fn test_color_override_and_marker() {
+        let star = |x: f64, color: Option<Rgb<u8>>, marker: Option<StarMarker>| StarDataEnhanced {
+            name: format!("Star {}", x),
+            x,
+            y: 0.0,
+            z: 0.0,
+            spectral_type: SpectralType::B,
+            luminosity: 1.0,
+            color,
+            marker,
+        };
+        let red = Rgb([255, 0, 0]);
+        let stars = vec![star(0.0, Some(red), Some(StarMarker::Capital)), star(10.0, None, None)];
+        let points = vec![Point2D { x: 100.0, y: 100.0 }, Point2D { x: 300.0, y: 100.0 }];
+        let renderer = EnhancedStarMapRenderer::new(400, 200);
+
+        let mut img: RgbImage = ImageBuffer::from_pixel(400, 200, Rgb([0, 0, 0]));
+        renderer.draw(&mut img, &stars, &points, &[], None);
+
+        // Inside the 40 px disc, clear of the white glint
+        assert_eq!(*img.get_pixel(100 + 25, 100), red);
+        let (blue, _) = get_spectral_colors(SpectralType::B);
+        assert_eq!(*img.get_pixel(300 + 25, 100), EnhancedStarMapRenderer::color_to_rgb(blue));
+
+        // The capital's square frame sits 18 px outside the disc
+        assert_eq!(*img.get_pixel(100 + 58, 100), StarMarker::Capital.color());
+        assert_eq!(*img.get_pixel(300 + 58, 100), Rgb([0, 0, 0]));
+        assert_eq!("Anomaly".parse::<StarMarker>(), Ok(StarMarker::Anomaly));
+        assert!("comet".parse::<StarMarker>().is_err());
+    }
 }
@@ -0,0 +1,160 @@
+/// Jump-network export for graph tools - GraphViz DOT or GraphML
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::Result;
+
+use super::enhanced_renderer::{StarConnection, StarDataEnhanced};
+use super::spectral::get_spectral_colors;
+
+/// Output format for `export_connection_graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Dot,
+    GraphMl,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "dot" | "gv" | "graphviz" => Ok(GraphFormat::Dot),
+            "graphml" | "xml" => Ok(GraphFormat::GraphMl),
+            other => Err(format!("Unknown graph format '{}' (expected dot or graphml)", other)),
+        }
+    }
+}
+
+/// Write the stars and their connections as an undirected graph
+/// Nodes carry the star name and its spectral core color; edges carry the distance in
+/// light-years. Node ids are the star indices, matching `StarConnection` indices.
+pub fn export_connection_graph<P: AsRef<Path>>(
+    stars: &[StarDataEnhanced],
+    connections: &[StarConnection],
+    format: GraphFormat,
+    output_path: P,
+) -> Result<()> {
+    if let Some(c) = connections.iter().find(|c| c.from_idx >= stars.len() || c.to_idx >= stars.len()) {
+        anyhow::bail!(
+            "Connection {} -> {} refers past the {} stars given",
+            c.from_idx,
+            c.to_idx,
+            stars.len()
+        );
+    }
+
+    let text = match format {
+        GraphFormat::Dot => to_dot(stars, connections),
+        GraphFormat::GraphMl => to_graphml(stars, connections),
+    };
+    fs::write(output_path, text)?;
+    Ok(())
+}
+
+fn hex_color(star: &StarDataEnhanced) -> String {
+    let (core, _) = get_spectral_colors(star.spectral_type);
+    format!("#{:02x}{:02x}{:02x}", core.r, core.g, core.b)
+}
+
+fn to_dot(stars: &[StarDataEnhanced], connections: &[StarConnection]) -> String {
+    let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut out = String::from("graph jump_network {\n");
+    out.push_str("  node [style=filled, fontcolor=white];\n");
+    for (i, star) in stars.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "  n{} [label=\"{}\", fillcolor=\"{}\", spectral=\"{:?}\"];",
+            i,
+            quote(&star.name),
+            hex_color(star),
+            star.spectral_type
+        );
+    }
+    for c in connections {
+        let _ = writeln!(
+            out,
+            "  n{} -- n{} [label=\"{:.2} ly\", distance={:.4}];",
+            c.from_idx, c.to_idx, c.distance_ly, c.distance_ly
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn to_graphml(stars: &[StarDataEnhanced], connections: &[StarConnection]) -> String {
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n",
+        "  <key id=\"spectral\" for=\"node\" attr.name=\"spectral\" attr.type=\"string\"/>\n",
+        "  <key id=\"color\" for=\"node\" attr.name=\"color\" attr.type=\"string\"/>\n",
+        "  <key id=\"distance_ly\" for=\"edge\" attr.name=\"distance_ly\" attr.type=\"double\"/>\n",
+        "  <graph id=\"jump_network\" edgedefault=\"undirected\">\n",
+    ));
+    for (i, star) in stars.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <node id=\"n{}\"><data key=\"name\">{}</data><data key=\"spectral\">{:?}</data><data key=\"color\">{}</data></node>",
+            i,
+            escape(&star.name),
+            star.spectral_type,
+            hex_color(star)
+        );
+    }
+    for (i, c) in connections.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"><data key=\"distance_ly\">{}</data></edge>",
+            i, c.from_idx, c.to_idx, c.distance_ly
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualization::enhanced_renderer::EnhancedStarMapRenderer;
+    use crate::visualization::spectral::SpectralType;
+
+    #[test]
+    fn test_dot_has_a_node_per_star_and_edge_per_connection() {
+        let stars: Vec<StarDataEnhanced> = [0.0, 2.0, 4.0, 20.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| StarDataEnhanced {
+                name: format!("Star \"{}\"", i),
+                x,
+                y: 0.0,
+                z: 0.0,
+                spectral_type: SpectralType::M,
+                luminosity: 0.01,
+                ..Default::default()
+            })
+            .collect();
+        let connections = EnhancedStarMapRenderer::find_connections(&stars, 2.5);
+        assert_eq!(connections.len(), 2);
+
+        let path = std::env::temp_dir().join("solarviewer_jump_network_test.dot");
+        export_connection_graph(&stars, &connections, GraphFormat::Dot, &path).unwrap();
+        let dot = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        let nodes = dot.lines().filter(|l| l.contains("fillcolor=") && !l.contains(" -- ")).count();
+        let edges = dot.lines().filter(|l| l.contains(" -- ")).count();
+        assert_eq!(nodes, stars.len());
+        assert_eq!(edges, connections.len());
+        assert!(dot.contains(r#"label="Star \"3\"""#));
+        assert!(dot.contains("label=\"2.00 ly\""));
+        assert_eq!("GraphML".parse::<GraphFormat>(), Ok(GraphFormat::GraphMl));
+    }
+}
@@ -0,0 +1,207 @@
+/// Interactive HTML star map export - a single self-contained file with pan, zoom and tooltips
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use serde_json::json;
+
+use super::enhanced_renderer::{StarConnection, StarDataEnhanced};
+use super::projection::Point2D;
+use super::spectral::get_spectral_colors;
+
+/// Write an HTML page that draws the map on a canvas
+/// Star data is embedded as JSON, so the file opens straight from disk with no server
+/// or external scripts. Tooltip distances are measured from the coordinate origin.
+pub fn export_interactive_html<P: AsRef<Path>>(
+    stars: &[StarDataEnhanced],
+    points_2d: &[Point2D],
+    connections: &[StarConnection],
+    output_path: P,
+) -> Result<()> {
+    if stars.len() != points_2d.len() {
+        anyhow::bail!(
+            "Got {} stars but {} projected points",
+            stars.len(),
+            points_2d.len()
+        );
+    }
+
+    let star_records: Vec<serde_json::Value> = stars
+        .iter()
+        .zip(points_2d)
+        .map(|(star, point)| {
+            let (core, _) = get_spectral_colors(star.spectral_type);
+            let distance_ly = (star.x * star.x + star.y * star.y + star.z * star.z).sqrt();
+            json!({
+                "name": star.name,
+                "spectral": format!("{:?}", star.spectral_type),
+                "distance_ly": distance_ly,
+                "luminosity": star.luminosity,
+                "x": point.x,
+                "y": point.y,
+                "color": format!("rgb({},{},{})", core.r, core.g, core.b),
+            })
+        })
+        .collect();
+
+    let connection_records: Vec<serde_json::Value> = connections
+        .iter()
+        .map(|c| json!({ "from": c.from_idx, "to": c.to_idx, "distance_ly": c.distance_ly }))
+        .collect();
+
+    // `</` would end the script block early if a star name contained it
+    let stars_json = serde_json::to_string(&star_records)?.replace("</", "<\\/");
+    let connections_json = serde_json::to_string(&connection_records)?.replace("</", "<\\/");
+
+    let html = TEMPLATE
+        .replace("__STARS__", &stars_json)
+        .replace("__CONNECTIONS__", &connections_json);
+
+    fs::write(output_path, html)?;
+    Ok(())
+}
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Star Map</title>
+<style>
+  html, body { margin: 0; height: 100%; background: #000; overflow: hidden; font-family: sans-serif; }
+  canvas { display: block; cursor: grab; }
+  #tooltip { position: absolute; display: none; padding: 4px 8px; background: rgba(20,20,40,0.9);
+             color: #eee; border: 1px solid #446; font-size: 12px; pointer-events: none; }
+</style>
+</head>
+<body>
+<canvas id="map"></canvas>
+<div id="tooltip"></div>
+<script id="star-data" type="application/json">__STARS__</script>
+<script id="connection-data" type="application/json">__CONNECTIONS__</script>
+<script>
+const stars = JSON.parse(document.getElementById('star-data').textContent);
+const connections = JSON.parse(document.getElementById('connection-data').textContent);
+const canvas = document.getElementById('map');
+const ctx = canvas.getContext('2d');
+const tooltip = document.getElementById('tooltip');
+let view = { x: 0, y: 0, k: 1 };
+let drag = null;
+
+function fit() {
+  canvas.width = window.innerWidth;
+  canvas.height = window.innerHeight;
+  if (stars.length === 0) return;
+  const xs = stars.map(s => s.x), ys = stars.map(s => s.y);
+  const minX = Math.min(...xs), maxX = Math.max(...xs);
+  const minY = Math.min(...ys), maxY = Math.max(...ys);
+  const w = Math.max(maxX - minX, 1), h = Math.max(maxY - minY, 1);
+  view.k = 0.9 * Math.min(canvas.width / w, canvas.height / h);
+  view.x = canvas.width / 2 - (minX + maxX) / 2 * view.k;
+  view.y = canvas.height / 2 - (minY + maxY) / 2 * view.k;
+}
+
+function screen(s) { return [s.x * view.k + view.x, s.y * view.k + view.y]; }
+
+function radius(s) { return 3 + 2 * Math.min(Math.cbrt(Math.max(s.luminosity, 0)), 3); }
+
+function draw() {
+  ctx.clearRect(0, 0, canvas.width, canvas.height);
+  ctx.strokeStyle = 'rgba(80,120,160,0.6)';
+  ctx.lineWidth = 1;
+  for (const c of connections) {
+    const [x1, y1] = screen(stars[c.from]);
+    const [x2, y2] = screen(stars[c.to]);
+    ctx.beginPath(); ctx.moveTo(x1, y1); ctx.lineTo(x2, y2); ctx.stroke();
+  }
+  for (const s of stars) {
+    const [x, y] = screen(s);
+    ctx.fillStyle = s.color;
+    ctx.beginPath(); ctx.arc(x, y, radius(s), 0, 2 * Math.PI); ctx.fill();
+  }
+}
+
+function starAt(mx, my) {
+  for (const s of stars) {
+    const [x, y] = screen(s);
+    const r = radius(s) + 3;
+    if ((x - mx) ** 2 + (y - my) ** 2 <= r * r) return s;
+  }
+  return null;
+}
+
+canvas.addEventListener('wheel', e => {
+  e.preventDefault();
+  const factor = e.deltaY < 0 ? 1.2 : 1 / 1.2;
+  view.x = e.clientX - (e.clientX - view.x) * factor;
+  view.y = e.clientY - (e.clientY - view.y) * factor;
+  view.k *= factor;
+  draw();
+}, { passive: false });
+
+canvas.addEventListener('mousedown', e => { drag = { x: e.clientX, y: e.clientY }; canvas.style.cursor = 'grabbing'; });
+window.addEventListener('mouseup', () => { drag = null; canvas.style.cursor = 'grab'; });
+canvas.addEventListener('mousemove', e => {
+  if (drag) {
+    view.x += e.clientX - drag.x;
+    view.y += e.clientY - drag.y;
+    drag = { x: e.clientX, y: e.clientY };
+    draw();
+    return;
+  }
+  const s = starAt(e.clientX, e.clientY);
+  if (s) {
+    tooltip.style.display = 'block';
+    tooltip.style.left = (e.clientX + 12) + 'px';
+    tooltip.style.top = (e.clientY + 12) + 'px';
+    tooltip.textContent = s.name + ' | ' + s.spectral + ' | ' + s.distance_ly.toFixed(2) + ' ly';
+  } else {
+    tooltip.style.display = 'none';
+  }
+});
+
+window.addEventListener('resize', () => { fit(); draw(); });
+fit();
+draw();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualization::enhanced_renderer::EnhancedStarMapRenderer;
+    use crate::visualization::spectral::SpectralType;
+
+    #[test]
+    fn test_html_embeds_every_star() {
+        let stars: Vec<StarDataEnhanced> = (0..3)
+            .map(|i| StarDataEnhanced {
+                name: format!("Star {}", i),
+                x: i as f64,
+                y: 0.0,
+                z: 0.0,
+                spectral_type: SpectralType::G,
+                luminosity: 1.0,
+                ..Default::default()
+            })
+            .collect();
+        let points: Vec<Point2D> = (0..3)
+            .map(|i| Point2D { x: 100.0 * i as f64, y: 50.0 })
+            .collect();
+        let connections = EnhancedStarMapRenderer::find_connections(&stars, 1.5);
+
+        let path = std::env::temp_dir().join("solarviewer_interactive_map_test.html");
+        export_interactive_html(&stars, &points, &connections, &path).unwrap();
+        let html = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+
+        let start = html.find(r#"<script id="star-data" type="application/json">"#).unwrap();
+        let json_start = html[start..].find('>').unwrap() + start + 1;
+        let json_end = html[json_start..].find("</script>").unwrap() + json_start;
+        let embedded: Vec<serde_json::Value> =
+            serde_json::from_str(&html[json_start..json_end]).unwrap();
+
+        assert_eq!(embedded.len(), 3);
+        assert_eq!(embedded[1]["name"], "Star 1");
+    }
+}
@@ -2,27 +2,292 @@ pub mod renderer;
 pub mod projection;
 pub mod spectral;
 pub mod enhanced_renderer;
+pub mod html_export;
+pub mod system_renderer;
+pub mod ascii_map;
+pub mod graph_export;
 
 pub use renderer::StarMapRenderer;
-pub use projection::ProjectionEngine;
-pub use spectral::SpectralType;
-pub use enhanced_renderer::EnhancedStarMapRenderer;
+pub use projection::{Orientation, ProjectionEngine, ViewAxis};
+pub use spectral::{Color, SpectralType};
+pub use enhanced_renderer::{Caption, EnhancedStarMapRenderer, GridLines, StarMarker, TileSet};
+pub use html_export::export_interactive_html;
+pub use system_renderer::SystemTopDownRenderer;
+pub use ascii_map::{render_ascii_map, render_ascii_map_plain};
+pub use graph_export::{export_connection_graph, GraphFormat};
 
+use std::collections::{HashMap, HashSet};
 use anyhow::Result;
-use crate::extraction::StarReader;
-use projection::Point3D;
-use spectral::get_spectral_colors;
+use image::Rgb;
 
-/// Render a star map centered on a specific star
-pub fn render_star_map(
+use crate::extraction::{Star, StarReader};
+use crate::stellar_forge::containers::Galaxy;
+use projection::{OrthographicView, Point3D};
+
+/// Fonts tried, in order, for map titles
+const CAPTION_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/TTF/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
+
+/// Presentation options for rendered star maps
+#[derive(Debug, Clone)]
+pub struct MapStyle {
+    pub background: Color,
+    /// Cartesian grid spacing in light-years, with lines through the origin; None for no grid
+    pub grid_spacing_ly: Option<f64>,
+    pub title: Option<String>,
+    /// View axis and rotation of the projection
+    pub orientation: Orientation,
+    /// Systems to ring on the map
+    pub highlight: Option<TagHighlight>,
+    /// Colors and markers for individual stars
+    pub overrides: Option<StarOverrides>,
+}
+
+/// Systems of a StellarForge galaxy holding a body with a given tag
+/// Map stars are matched to them by system name, or by star name for single stars.
+#[derive(Debug, Clone)]
+pub struct TagHighlight {
+    pub tag: String,
+    /// Lowercased system names
+    pub systems: HashSet<String>,
+}
+
+impl TagHighlight {
+    pub fn from_galaxy(galaxy: &Galaxy, tag: &str) -> Self {
+        let systems = galaxy
+            .star_systems
+            .iter()
+            .filter(|system| !system.bodies_with_tag(tag).is_empty())
+            .map(|system| system.name.to_lowercase())
+            .collect();
+        Self { tag: tag.to_string(), systems }
+    }
+
+    pub fn matches(&self, star: &Star) -> bool {
+        star.system_name
+            .as_deref()
+            .into_iter()
+            .chain(std::iter::once(star.name.as_str()))
+            .any(|name| self.systems.contains(&name.to_lowercase()))
+    }
+}
+
+/// A star's own color and marker, from `StellarBody::color` and its "marker" metadata
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StarOverride {
+    pub color: Option<Rgb<u8>>,
+    pub marker: Option<StarMarker>,
+}
+
+/// Per-star colors and markers from the stars of a StellarForge galaxy
+/// Map stars are matched by star name; a single map star (one without a system) may also
+/// match the name of a system, taking its primary's override.
+#[derive(Debug, Clone, Default)]
+pub struct StarOverrides {
+    /// Keyed by lowercased star name
+    pub stars: HashMap<String, StarOverride>,
+    /// Primary star overrides keyed by lowercased system name
+    pub systems: HashMap<String, StarOverride>,
+}
+
+impl StarOverrides {
+    pub fn from_galaxy(galaxy: &Galaxy) -> Self {
+        let (mut stars, mut systems) = (HashMap::new(), HashMap::new());
+        for system in &galaxy.star_systems {
+            for (i, body) in system.stars.iter().enumerate() {
+                let marker = match body.metadata.get("marker").and_then(|m| m.as_str()) {
+                    Some(name) => match name.parse::<StarMarker>() {
+                        Ok(marker) => Some(marker),
+                        Err(e) => {
+                            eprintln!("Warning: {} on {}; drawing it without a marker", e, body.name);
+                            None
+                        }
+                    },
+                    None => None,
+                };
+                let color = body.color.map(|rgb| Rgb(rgb.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)));
+                if color.is_none() && marker.is_none() {
+                    continue;
+                }
+
+                let star_override = StarOverride { color, marker };
+                if i == 0 {
+                    systems.insert(system.name.to_lowercase(), star_override);
+                }
+                stars.insert(body.name.to_lowercase(), star_override);
+            }
+        }
+        Self { stars, systems }
+    }
+
+    pub fn get(&self, star: &Star) -> Option<&StarOverride> {
+        let name = star.name.to_lowercase();
+        match self.stars.get(&name) {
+            Some(star_override) => Some(star_override),
+            None if star.system_name.is_none() => self.systems.get(&name),
+            None => None,
+        }
+    }
+}
+
+impl Default for MapStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::rgb(0, 0, 0),
+            grid_spacing_ly: None,
+            title: None,
+            orientation: Orientation::default(),
+            highlight: None,
+            overrides: None,
+        }
+    }
+}
+
+impl MapStyle {
+    /// Renderer configured with this style for a map laid out by `view`
+    /// A title is skipped with a warning when no system font can be found.
+    fn renderer(&self, view: &OrthographicView) -> Result<EnhancedStarMapRenderer> {
+        let background = Rgb([self.background.r, self.background.g, self.background.b]);
+        let mut renderer = EnhancedStarMapRenderer::new(view.width, view.height).with_background(background);
+
+        if let Some(spacing_ly) = self.grid_spacing_ly {
+            if spacing_ly.is_nan() || spacing_ly <= 0.0 {
+                anyhow::bail!("Grid spacing must be positive, got {}", spacing_ly);
+            }
+            let origin = view.to_pixel(0.0, 0.0);
+            // Dim lines that stay visible against the background
+            let color = Rgb(background.0.map(|c| c.saturating_add(40)));
+            renderer = renderer.with_grid(GridLines {
+                spacing_px: spacing_ly * view.pixels_per_ly,
+                offset_px: (origin.x, origin.y),
+                color,
+            });
+        }
+
+        if let Some(title) = self.title.as_ref().filter(|t| !t.trim().is_empty()) {
+            match load_caption_font() {
+                Some(font) => {
+                    renderer = renderer.with_caption(Caption {
+                        text: title.clone(),
+                        font,
+                        color: Rgb([200, 220, 255]),
+                    });
+                }
+                None => eprintln!("Warning: No font found for the map title; rendering without it"),
+            }
+        }
+
+        Ok(renderer)
+    }
+}
+
+fn load_caption_font() -> Option<fontdue::Font> {
+    CAPTION_FONTS.iter().find_map(|path| {
+        let bytes = std::fs::read(path).ok()?;
+        fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default()).ok()
+    })
+}
+
+/// Find the star to center a map on
+/// `center_id` disambiguates stars sharing a name, or stands in for a blank name; it matches
+/// the database id, the containing system's name, the catalog designation, or "HIP n"/"HD n".
+/// Several stars matching is an error that lists the candidates rather than a silent pick.
+pub fn find_center_star<'a>(
+    stars: &'a [Star],
+    center_star_name: &str,
+    center_id: Option<&str>,
+) -> Result<&'a Star> {
+    let name = center_star_name.trim();
+    let center_id = center_id.map(str::trim).filter(|id| !id.is_empty());
+
+    if name.is_empty() && center_id.is_none() {
+        anyhow::bail!("No center star given: pass a star name or a system/catalog ID");
+    }
+
+    let candidates: Vec<&Star> = stars
+        .iter()
+        .filter(|s| name.is_empty() || s.display_name().eq_ignore_ascii_case(name))
+        .filter(|s| center_id.is_none_or(|id| matches_center_id(s, id)))
+        .collect();
+
+    match candidates.as_slice() {
+        [] => match center_id {
+            Some(id) if name.is_empty() => anyhow::bail!("No star with ID '{}' found in database", id),
+            Some(id) => anyhow::bail!("Star '{}' with ID '{}' not found in database", name, id),
+            None => anyhow::bail!("Star '{}' not found in database", name),
+        },
+        [star] => Ok(star),
+        _ => {
+            let listing: Vec<String> = candidates
+                .iter()
+                .map(|s| {
+                    let system = s.system_name.as_deref().map(|n| format!(" in {}", n)).unwrap_or_default();
+                    format!("  id {}{} at ({:.2}, {:.2}, {:.2})", s.id, system, s.x, s.y, s.z)
+                })
+                .collect();
+            anyhow::bail!(
+                "{} stars match '{}'; pick one with a system/catalog ID:\n{}",
+                candidates.len(),
+                if name.is_empty() { center_id.unwrap_or_default() } else { name },
+                listing.join("\n")
+            )
+        }
+    }
+}
+
+fn matches_center_id(star: &Star, id: &str) -> bool {
+    let upper = id.to_uppercase();
+    let catalog_number = |prefix: &str| {
+        upper.strip_prefix(prefix).and_then(|digits| digits.trim().parse::<u32>().ok())
+    };
+
+    star.id.to_string() == id
+        || star.system_name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(id))
+        || star.catalog_id.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(id))
+        || star.hip.is_some_and(|n| catalog_number("HIP") == Some(n))
+        || star.hd.is_some_and(|n| catalog_number("HD") == Some(n))
+}
+
+/// Stars no farther than `radius_ly` from `center`, including the center itself
+pub fn stars_within_radius(stars: &[Star], center: &Star, radius_ly: f64) -> Vec<Star> {
+    stars
+        .iter()
+        .filter(|star| star.distance_to(center) <= radius_ly)
+        .cloned()
+        .collect()
+}
+
+/// Stars, projected positions and connections for a map, ready to draw
+struct PreparedMap {
+    stars: Vec<enhanced_renderer::StarDataEnhanced>,
+    points_2d: Vec<projection::Point2D>,
+    connections: Vec<enhanced_renderer::StarConnection>,
+    center_star_idx: Option<usize>,
+    /// Indices of stars matching the style's highlight
+    highlighted: Vec<usize>,
+    connection_distance_ly: f64,
+    view: OrthographicView,
+}
+
+/// Read, select and project the stars around the center star
+#[allow(clippy::too_many_arguments)]
+fn prepare_star_map(
     db_path: &str,
     center_star_name: &str,
+    center_id: Option<&str>,
     mut search_radius_ly: f64,
-    output_path: &str,
     width: u32,
     height: u32,
     mut connection_distance_ly: f64,
-) -> Result<()> {
+    orientation: Orientation,
+    highlight: Option<&TagHighlight>,
+    overrides: Option<&StarOverrides>,
+) -> Result<PreparedMap> {
     // Use sensible defaults for enhanced visualization
     if search_radius_ly <= 0.0 {
         search_radius_ly = 25.0;
@@ -36,38 +301,30 @@ pub fn render_star_map(
     let all_stars = reader.read_all_stars()?;
 
     // Find the center star
-    let center_star = all_stars
-        .iter()
-        .find(|s| s.name.eq_ignore_ascii_case(center_star_name))
-        .ok_or_else(|| anyhow::anyhow!("Star '{}' not found in database", center_star_name))?;
+    let center_star = find_center_star(&all_stars, center_star_name, center_id)?;
+    let center_label = center_star.display_name();
 
     // Find all stars within search radius
-    let mut nearby_stars = Vec::new();
-    for star in &all_stars {
-        let dx = star.x - center_star.x;
-        let dy = star.y - center_star.y;
-        let dz = star.z - center_star.z;
-        let dist = (dx * dx + dy * dy + dz * dz).sqrt();
-
-        if dist <= search_radius_ly {
-            nearby_stars.push(star.clone());
-        }
-    }
+    let nearby_stars = stars_within_radius(&all_stars, center_star, search_radius_ly);
 
-    println!("Found {} stars within {} ly of {}", nearby_stars.len(), search_radius_ly, center_star_name);
+    println!("Found {} stars within {} ly of {}", nearby_stars.len(), search_radius_ly, center_label);
 
     // Convert to StarData for rendering with spectral types
     let render_stars: Vec<enhanced_renderer::StarDataEnhanced> = nearby_stars
         .iter()
         .map(|s| {
             let spectral = s.spectral_type.parse::<SpectralType>().unwrap_or(SpectralType::Unknown);
+            let star_override = overrides.and_then(|o| o.get(s)).copied().unwrap_or_default();
             enhanced_renderer::StarDataEnhanced {
-                name: s.name.clone(),
+                // Blank names fall back to the catalog designation
+                name: s.display_name(),
                 x: s.x,
                 y: s.y,
                 z: s.z,
                 spectral_type: spectral,
                 luminosity: s.luminosity_solar,
+                color: star_override.color,
+                marker: star_override.marker,
             }
         })
         .collect();
@@ -78,7 +335,8 @@ pub fn render_star_map(
         .map(|s| Point3D { x: s.x, y: s.y, z: s.z })
         .collect();
 
-    let projection_engine = ProjectionEngine::new(width, height, 300);
+    let projection_engine = ProjectionEngine::new(width, height, 300).with_orientation(orientation);
+    let view = projection_engine.fit_orthographic(&points_3d);
     let mut points_2d = projection_engine.project_orthographic(&points_3d);
 
     // Resolve overlaps
@@ -87,17 +345,247 @@ pub fn render_star_map(
     // Find connections using enhanced renderer
     let connections = EnhancedStarMapRenderer::find_connections(&render_stars, connection_distance_ly);
 
-    // Render to PNG using enhanced renderer
-    let renderer = EnhancedStarMapRenderer::new(width, height);
-    let center_star_idx = render_stars
+    let center_star_idx = nearby_stars
         .iter()
-        .position(|s| s.name.eq_ignore_ascii_case(center_star_name));
+        .position(|s| s.id == center_star.id);
+
+    let highlighted: Vec<usize> = match highlight {
+        Some(highlight) => nearby_stars
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| highlight.matches(s))
+            .map(|(i, _)| i)
+            .collect(),
+        None => Vec::new(),
+    };
+    if let Some(highlight) = highlight {
+        println!("Highlighting {} stars in systems tagged '{}'", highlighted.len(), highlight.tag);
+    }
 
-    renderer.render_to_file(&render_stars, &points_2d, &connections, center_star_idx, output_path)?;
+    Ok(PreparedMap {
+        stars: render_stars,
+        points_2d,
+        connections,
+        center_star_idx,
+        highlighted,
+        connection_distance_ly,
+        view,
+    })
+}
+
+/// Render a star map centered on a specific star
+#[allow(clippy::too_many_arguments)]
+pub fn render_star_map(
+    db_path: &str,
+    center_star_name: &str,
+    center_id: Option<&str>,
+    search_radius_ly: f64,
+    output_path: &str,
+    width: u32,
+    height: u32,
+    connection_distance_ly: f64,
+    style: &MapStyle,
+) -> Result<()> {
+    let map = prepare_star_map(
+        db_path, center_star_name, center_id, search_radius_ly, width, height, connection_distance_ly,
+        style.orientation, style.highlight.as_ref(), style.overrides.as_ref(),
+    )?;
+
+    // Render to PNG using enhanced renderer
+    let renderer = style.renderer(&map.view)?.with_highlighted(map.highlighted.iter().copied());
+    renderer.render_to_file(&map.stars, &map.points_2d, &map.connections, map.center_star_idx, output_path)?;
 
     println!("Map rendered to: {}", output_path);
-    println!("  Stars plotted: {}", render_stars.len());
-    println!("  Connections (<{} ly): {}", connection_distance_ly, connections.len());
+    println!("  Stars plotted: {}", map.stars.len());
+    println!("  Connections (<{} ly): {}", map.connection_distance_ly, map.connections.len());
 
     Ok(())
 }
+
+/// Render a star map as PNG tiles in `tile_dir`, for sizes too big to hold in memory
+/// With `stitch_to` set the tiles are also assembled into one image, which needs the full
+/// buffer again, so leave it unset for truly huge maps.
+#[allow(clippy::too_many_arguments)]
+pub fn render_star_map_tiled(
+    db_path: &str,
+    center_star_name: &str,
+    center_id: Option<&str>,
+    search_radius_ly: f64,
+    tile_dir: &str,
+    width: u32,
+    height: u32,
+    connection_distance_ly: f64,
+    tile_size: u32,
+    stitch_to: Option<&str>,
+    style: &MapStyle,
+) -> Result<enhanced_renderer::TileSet> {
+    let map = prepare_star_map(
+        db_path, center_star_name, center_id, search_radius_ly, width, height, connection_distance_ly,
+        style.orientation, style.highlight.as_ref(), style.overrides.as_ref(),
+    )?;
+
+    let renderer = style.renderer(&map.view)?.with_highlighted(map.highlighted.iter().copied());
+    let tiles = renderer.render_tiles(
+        &map.stars, &map.points_2d, &map.connections, map.center_star_idx, tile_size, tile_dir,
+    )?;
+    println!("Map rendered to {} tiles ({}x{}) in: {}", tiles.paths.len(), tiles.columns, tiles.rows, tile_dir);
+
+    if let Some(output_path) = stitch_to {
+        tiles.stitch(output_path)?;
+        println!("Tiles stitched to: {}", output_path);
+    }
+
+    Ok(tiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn star(id: i32, name: &str, system_name: Option<&str>, x: f64) -> Star {
+        Star {
+            id,
+            name: name.to_string(),
+            spectral_type: "G2V".to_string(),
+            radius_solar: 1.0,
+            mass_solar: 1.0,
+            luminosity_solar: 1.0,
+            temperature_k: 5778.0,
+            x,
+            y: 0.0,
+            z: 0.0,
+            system_name: system_name.map(str::to_string),
+            system_x: x,
+            system_y: 0.0,
+            system_z: 0.0,
+            catalog_id: None,
+            hip: None,
+            hd: None,
+            parallax_mas: None,
+            apparent_magnitude: None,
+            velocity_kms: None,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_names_need_disambiguator() {
+        let stars = vec![
+            star(1, "Kepler", Some("Alpha"), 1.0),
+            star(2, "Kepler", Some("Beta"), 2.0),
+            star(3, "Sol", None, 0.0),
+        ];
+
+        let err = find_center_star(&stars, "kepler", None).unwrap_err().to_string();
+        assert!(err.contains("2 stars match"));
+        assert!(err.contains("id 1 in Alpha at (1.00, 0.00, 0.00)"));
+        assert!(err.contains("id 2 in Beta at (2.00, 0.00, 0.00)"));
+
+        assert_eq!(find_center_star(&stars, "Kepler", Some("Beta")).unwrap().id, 2);
+        assert_eq!(find_center_star(&stars, "Kepler", Some("1")).unwrap().id, 1);
+        assert_eq!(find_center_star(&stars, "Sol", None).unwrap().id, 3);
+    }
+
+    #[test]
+    fn test_blank_names_use_catalog_ids() {
+        let mut proxima = star(7, "", None, 4.2);
+        proxima.catalog_id = Some("Gliese 551".to_string());
+        proxima.hip = Some(70890);
+        let stars = vec![proxima, star(8, "", None, 9.0), star(3, "Sol", None, 0.0)];
+
+        assert!(find_center_star(&stars, "", None).is_err());
+        assert_eq!(find_center_star(&stars, "Gliese 551", None).unwrap().id, 7);
+        assert_eq!(find_center_star(&stars, "", Some("HIP 70890")).unwrap().id, 7);
+        assert_eq!(find_center_star(&stars, "", Some("8")).unwrap().id, 8);
+        assert!(find_center_star(&stars, "", Some("HD 1")).is_err());
+    }
+
+    #[test]
+    fn test_highlight_tag_rings_tagged_systems() {
+        use crate::stellar_forge::builders::SystemBuilder;
+        use crate::stellar_forge::core::Taggable;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(SystemBuilder::new("Colony").with_star("G2V").build()).unwrap();
+        galaxy.add_star_system(SystemBuilder::new("Wild").with_star("G2V").build()).unwrap();
+        galaxy.star_systems[0].stars[0].add_tag("colonized");
+
+        let highlight = TagHighlight::from_galaxy(&galaxy, "colonized");
+        let stars = [star(1, "Colony A", Some("Colony"), 0.0), star(2, "Wild", None, 10.0)];
+        let highlighted: Vec<usize> = (0..stars.len()).filter(|&i| highlight.matches(&stars[i])).collect();
+        assert_eq!(highlighted, vec![0]);
+
+        let render_stars: Vec<enhanced_renderer::StarDataEnhanced> = stars
+            .iter()
+            .map(|s| enhanced_renderer::StarDataEnhanced {
+                name: s.name.clone(),
+                x: s.x,
+                y: s.y,
+                z: s.z,
+                spectral_type: SpectralType::G,
+                luminosity: 1.0,
+                ..Default::default()
+            })
+            .collect();
+        let points = vec![projection::Point2D { x: 100.0, y: 100.0 }, projection::Point2D { x: 300.0, y: 100.0 }];
+        let view = OrthographicView { center_x: 0.0, center_y: 0.0, pixels_per_ly: 20.0, width: 400, height: 200 };
+        let style = MapStyle { highlight: Some(highlight), ..MapStyle::default() };
+
+        let path = std::env::temp_dir().join("solarviewer_highlight_tag.png");
+        style
+            .renderer(&view)
+            .unwrap()
+            .with_highlighted(highlighted)
+            .render_to_file(&render_stars, &points, &[], None, &path)
+            .unwrap();
+        let img = image::open(&path).unwrap().to_rgb8();
+        std::fs::remove_file(&path).ok();
+
+        // Just outside each 40 px disc: a ring on the tagged star, background on the other
+        assert_ne!(*img.get_pixel(100 + 49, 100), Rgb([0, 0, 0]));
+        assert_eq!(*img.get_pixel(300 + 49, 100), Rgb([0, 0, 0]));
+    }
+
+    #[test]
+    fn test_radius_extract_matches_filter() {
+        let stars: Vec<Star> = (0..30)
+            .map(|i| star(i, &format!("Star {}", i), None, i as f64 - 5.0))
+            .chain(std::iter::once(star(100, "Sol", None, 0.0)))
+            .collect();
+        let center = find_center_star(&stars, "Sol", None).unwrap();
+        let bubble = stars_within_radius(&stars, center, 10.0);
+
+        // -5..=10 from the line of stars, plus Sol itself
+        assert_eq!(bubble.len(), 17);
+        assert!(bubble.iter().all(|s| s.distance_to(center) <= 10.0));
+
+        let path = std::env::temp_dir().join("solarviewer_radius_extract.csv");
+        crate::extraction::export_stars_to_csv(&bubble, path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(csv.lines().count() - 1, bubble.len());
+    }
+
+    #[test]
+    fn test_star_overrides_match_by_star_or_system_name() {
+        use crate::stellar_forge::builders::SystemBuilder;
+
+        let mut galaxy = Galaxy::new("Test");
+        galaxy.add_star_system(SystemBuilder::new("Throne").with_star("G2V").with_star("M4V").build()).unwrap();
+        galaxy.add_star_system(SystemBuilder::new("Plain").with_star("G2V").build()).unwrap();
+        galaxy.star_systems[0].stars[1].name = "Throne B".to_string();
+        let capital = &mut galaxy.star_systems[0].stars[0];
+        capital.color = Some([1.0, 0.0, 0.0]);
+        capital.metadata.insert("marker".to_string(), serde_json::json!("capital"));
+
+        let overrides = StarOverrides::from_galaxy(&galaxy);
+        let expected = StarOverride { color: Some(Rgb([255, 0, 0])), marker: Some(StarMarker::Capital) };
+        assert_eq!(overrides.get(&star(1, "Throne A", None, 0.0)), Some(&expected));
+        assert_eq!(overrides.get(&star(2, "Throne", None, 0.0)), Some(&expected));
+        assert_eq!(overrides.get(&star(4, "Plain A", Some("Plain"), 0.0)), None);
+
+        // The companion of a binary keeps its own look, whatever the map calls its system
+        assert_eq!(overrides.get(&star(5, "Throne B", Some("Throne"), 0.0)), None);
+        assert_eq!(overrides.get(&star(6, "Throne", Some("Throne"), 0.0)), None);
+    }
+}
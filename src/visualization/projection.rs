@@ -1,5 +1,12 @@
 /// Projection engine for converting 3D stellar coordinates to 2D
 /// Uses orthographic projection with overlap resolution
+use std::str::FromStr;
+
+use time::OffsetDateTime;
+
+use crate::stellar_forge::bodies::{BodyKind, StellarBody};
+use crate::stellar_forge::containers::StarSystem;
+use crate::stellar_forge::core::{Id, Units, Vec3};
 
 #[derive(Debug, Clone)]
 pub struct Point2D {
@@ -14,35 +21,147 @@ pub struct Point3D {
     pub z: f64,
 }
 
+/// A system body placed on the top-down view
+#[derive(Debug, Clone)]
+pub struct ProjectedBody {
+    pub id: Id,
+    pub name: String,
+    pub kind: BodyKind,
+    pub point: Point2D,
+}
+
+/// Top-down view of a single system's orbital plane
+#[derive(Debug, Clone)]
+pub struct SystemProjection {
+    pub bodies: Vec<ProjectedBody>,
+    /// Scale used for the view, so overlays (orbits, zones) line up with the bodies
+    pub pixels_per_au: f64,
+}
+
+/// World axis a map looks along; the other two axes form the image plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ViewAxis {
+    /// Top-down onto the x-y plane
+    #[default]
+    Z,
+    /// Onto the x-z plane
+    Y,
+    /// Onto the y-z plane
+    X,
+}
+
+impl FromStr for ViewAxis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "z" => Ok(ViewAxis::Z),
+            "y" => Ok(ViewAxis::Y),
+            "x" => Ok(ViewAxis::X),
+            other => Err(format!("Unknown view axis '{}' (expected x, y or z)", other)),
+        }
+    }
+}
+
+/// How world coordinates are laid onto the image plane before fitting
+/// The plane's first axis is drawn to the right and its second axis down the image, so
+/// `rotation_deg` turns the map clockwise on screen: 90 degrees puts the first axis where
+/// the second one was.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Orientation {
+    pub view_axis: ViewAxis,
+    pub rotation_deg: f64,
+}
+
+impl Orientation {
+    /// Plane coordinates of a world point, in light-years
+    pub fn to_plane(&self, point: &Point3D) -> (f64, f64) {
+        let (u, v) = match self.view_axis {
+            ViewAxis::Z => (point.x, point.y),
+            ViewAxis::Y => (point.x, point.z),
+            ViewAxis::X => (point.y, point.z),
+        };
+        let (sin, cos) = self.rotation_deg.to_radians().sin_cos();
+        (u * cos - v * sin, u * sin + v * cos)
+    }
+}
+
+/// Mapping from light-year plane coordinates to pixels chosen by `ProjectionEngine::fit_orthographic`
+#[derive(Debug, Clone, Copy)]
+pub struct OrthographicView {
+    /// World point drawn at the image center
+    pub center_x: f64,
+    pub center_y: f64,
+    pub pixels_per_ly: f64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl OrthographicView {
+    /// Pixel position of a point on the (oriented) image plane, in light-years
+    pub fn to_pixel(&self, x: f64, y: f64) -> Point2D {
+        Point2D {
+            x: (x - self.center_x) * self.pixels_per_ly + (self.width as f64) / 2.0,
+            y: (y - self.center_y) * self.pixels_per_ly + (self.height as f64) / 2.0,
+        }
+    }
+}
+
 pub struct ProjectionEngine {
     width: u32,
     height: u32,
     margin: u32,
+    orientation: Orientation,
 }
 
 impl ProjectionEngine {
     pub fn new(width: u32, height: u32, margin: u32) -> Self {
-        Self { width, height, margin }
+        Self { width, height, margin, orientation: Orientation::default() }
     }
 
-    /// Project 3D points to 2D using orthographic projection (drop Z)
+    /// Look along another axis or turn the map, without touching the star data
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Project 3D points to 2D using orthographic projection (drop the view axis, Z by default)
     /// Scales to fit within the output dimensions
     pub fn project_orthographic(&self, points_3d: &[Point3D]) -> Vec<Point2D> {
+        let view = self.fit_orthographic(points_3d);
+        points_3d
+            .iter()
+            .map(|p| {
+                let (u, v) = self.orientation.to_plane(p);
+                view.to_pixel(u, v)
+            })
+            .collect()
+    }
+
+    /// Pixel mapping `project_orthographic` uses for these points, so overlays such as
+    /// grids can be drawn in light-years
+    pub fn fit_orthographic(&self, points_3d: &[Point3D]) -> OrthographicView {
+        let mut view = OrthographicView {
+            center_x: 0.0,
+            center_y: 0.0,
+            pixels_per_ly: 1.0,
+            width: self.width,
+            height: self.height,
+        };
         if points_3d.is_empty() {
-            return Vec::new();
+            return view;
         }
 
-        // Find bounding box in 3D space (using only X, Y)
-        let mut min_x = points_3d[0].x;
-        let mut max_x = points_3d[0].x;
-        let mut min_y = points_3d[0].y;
-        let mut max_y = points_3d[0].y;
-
-        for point in points_3d {
-            if point.x < min_x { min_x = point.x; }
-            if point.x > max_x { max_x = point.x; }
-            if point.y < min_y { min_y = point.y; }
-            if point.y > max_y { max_y = point.y; }
+        // Find bounding box on the image plane
+        let plane: Vec<(f64, f64)> = points_3d.iter().map(|p| self.orientation.to_plane(p)).collect();
+        let (mut min_x, mut min_y) = plane[0];
+        let (mut max_x, mut max_y) = plane[0];
+
+        for &(x, y) in &plane {
+            if x < min_x { min_x = x; }
+            if x > max_x { max_x = x; }
+            if y < min_y { min_y = y; }
+            if y > max_y { max_y = y; }
         }
 
         let mut range_x = max_x - min_x;
@@ -52,28 +171,72 @@ impl ProjectionEngine {
         range_x *= 1.1;
         range_y *= 1.1;
 
-        let center_x = (min_x + max_x) / 2.0;
-        let center_y = (min_y + max_y) / 2.0;
+        view.center_x = (min_x + max_x) / 2.0;
+        view.center_y = (min_y + max_y) / 2.0;
 
         // Calculate scale to fit in available space
         let available_width = (self.width - 2 * self.margin) as f64;
         let available_height = (self.height - 2 * self.margin) as f64;
 
-        let scale = if range_x > 0.0 && range_y > 0.0 {
-            (available_width / range_x).min(available_height / range_y)
+        if range_x > 0.0 && range_y > 0.0 {
+            view.pixels_per_ly = (available_width / range_x).min(available_height / range_y);
+        }
+        view
+    }
+
+    /// Project a system's bodies onto the x-y plane of its frame at `epoch`
+    /// Positions are propagated from each body's motion model and measured from
+    /// the barycenter at `epoch`, which lands at the image center. Belts are skipped since
+    /// they have no single position; moons stay with their planets.
+    pub fn project_system_topdown(&self, system: &StarSystem, epoch: OffsetDateTime) -> SystemProjection {
+        let bodies: Vec<&StellarBody> = system.stars.iter()
+            .chain(system.planets.iter())
+            .chain(system.stations.iter())
+            .chain(system.other_bodies.iter())
+            .collect();
+
+        let barycenter = system.barycenter_at(epoch);
+        let offsets_au: Vec<Vec3> = bodies
+            .iter()
+            .map(|body| {
+                let state = match &body.motion {
+                    Some(motion) => motion.propagate(body.state, body.epoch, epoch),
+                    None => body.state,
+                };
+                (state.position_m - barycenter) / Units::AU
+            })
+            .collect();
+
+        let max_radius_au = offsets_au
+            .iter()
+            .map(|p| p.x.hypot(p.y))
+            .fold(0.0, f64::max);
+
+        let available_width = (self.width - 2 * self.margin) as f64;
+        let available_height = (self.height - 2 * self.margin) as f64;
+
+        // Same 10% padding as the orthographic view
+        let pixels_per_au = if max_radius_au > 0.0 {
+            available_width.min(available_height) / (2.0 * max_radius_au * 1.1)
         } else {
             1.0
         };
 
-        // Project each point
-        points_3d
+        let bodies = bodies
             .iter()
-            .map(|p| {
-                let px = (p.x - center_x) * scale + (self.width as f64) / 2.0;
-                let py = (p.y - center_y) * scale + (self.height as f64) / 2.0;
-                Point2D { x: px, y: py }
+            .zip(offsets_au.iter())
+            .map(|(body, offset)| ProjectedBody {
+                id: body.id,
+                name: body.name.clone(),
+                kind: body.kind,
+                point: Point2D {
+                    x: offset.x * pixels_per_au + (self.width as f64) / 2.0,
+                    y: offset.y * pixels_per_au + (self.height as f64) / 2.0,
+                },
             })
-            .collect()
+            .collect();
+
+        SystemProjection { bodies, pixels_per_au }
     }
 
     /// Resolve overlapping stars by applying repulsive forces
@@ -130,4 +293,58 @@ mod tests {
         assert!(projected[0].x > 0.0);
         assert!(projected[0].y > 0.0);
     }
+
+    #[test]
+    fn test_quarter_turn_puts_x_where_y_was() {
+        let points = vec![
+            Point3D { x: 5.0, y: 0.0, z: 0.0 },
+            Point3D { x: 0.0, y: 5.0, z: 0.0 },
+            Point3D { x: -5.0, y: 0.0, z: 0.0 },
+            Point3D { x: 0.0, y: -5.0, z: 0.0 },
+        ];
+        let upright = ProjectionEngine::new(1000, 1000, 100).project_orthographic(&points);
+        let turned = ProjectionEngine::new(1000, 1000, 100)
+            .with_orientation(Orientation { view_axis: ViewAxis::Z, rotation_deg: 90.0 })
+            .project_orthographic(&points);
+
+        assert!((turned[0].x - upright[1].x).abs() < 1e-9);
+        assert!((turned[0].y - upright[1].y).abs() < 1e-9);
+        // +x moves from right of center to below it: clockwise as seen on the image
+        assert!(upright[0].x > 500.0 && turned[0].y > 500.0);
+        assert!((turned[0].x - 500.0).abs() < 1e-9);
+
+        // Looking along y puts z on the plane's second axis
+        let side = Orientation { view_axis: ViewAxis::Y, rotation_deg: 0.0 };
+        assert_eq!(side.to_plane(&Point3D { x: 1.0, y: 2.0, z: 3.0 }), (1.0, 3.0));
+    }
+
+    #[test]
+    fn test_system_topdown_matches_semi_major_axes() {
+        use crate::stellar_forge::builders::create_sol_like_system;
+        use crate::stellar_forge::motion::MotionModel;
+
+        let sol = create_sol_like_system().build();
+        let epoch = sol.planets[0].epoch;
+
+        let engine = ProjectionEngine::new(2000, 2000, 100);
+        let projection = engine.project_system_topdown(&sol, epoch);
+        assert_eq!(projection.bodies.len(), sol.stars.len() + sol.planets.len());
+
+        for planet in &sol.planets {
+            let projected = projection.bodies.iter().find(|b| b.id == planet.id).unwrap();
+            let radius_au = (projected.point.x - 1000.0).hypot(projected.point.y - 1000.0)
+                / projection.pixels_per_au;
+
+            let Some(MotionModel::Keplerian(elements)) = &planet.motion else {
+                panic!("{} should have a Keplerian orbit", planet.name);
+            };
+            let a_au = elements.semi_major_axis_m / Units::AU;
+            let e = elements.eccentricity;
+            assert!(
+                radius_au >= a_au * (1.0 - e) - 1e-6 && radius_au <= a_au * (1.0 + e) + 1e-6,
+                "{} projected at {} AU, expected about {} AU",
+                planet.name, radius_au, a_au
+            );
+        }
+    }
 }
@@ -30,7 +30,6 @@ pub struct StarMapRenderer {
     background: Rgb<u8>,
     star_color: Rgb<u8>,
     line_color: Rgb<u8>,
-    text_color: Rgb<u8>,
     base_star_radius: u32,
 }
 
@@ -42,7 +41,6 @@ impl Default for StarMapRenderer {
             background: Rgb([0, 0, 0]),       // Black
             star_color: Rgb([255, 255, 255]), // White
             line_color: Rgb([100, 150, 255]), // Light blue
-            text_color: Rgb([200, 220, 255]), // Light blue for text
             base_star_radius: 40,
         }
     }
@@ -119,11 +117,6 @@ impl StarMapRenderer {
             draw_filled_circle_mut(&mut img, (px, py), radius, star_color);
         }
 
-        // Draw title
-        let title = format!("Star Field - {} stars", stars.len());
-        // Note: Text rendering requires font loading which may fail, so we skip it for now
-        // In a full implementation, we'd load a system font here
-
         // Save image
         img.save(&output_path)?;
         Ok(())
@@ -163,6 +156,7 @@ impl StarMapRenderer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::visualization::spectral::SpectralType;
 
     #[test]
     fn test_renderer_creation() {
@@ -180,6 +174,7 @@ mod tests {
                 y: 0.0,
                 z: 0.0,
                 spectral_type: "G3V".to_string(),
+                spectral_enum: SpectralType::G,
                 luminosity: 1.0,
             },
             StarData {
@@ -188,6 +183,7 @@ mod tests {
                 y: 0.0,
                 z: 0.0,
                 spectral_type: "M4V".to_string(),
+                spectral_enum: SpectralType::M,
                 luminosity: 0.1,
             },
             StarData {
@@ -196,6 +192,7 @@ mod tests {
                 y: 0.0,
                 z: 0.0,
                 spectral_type: "B5V".to_string(),
+                spectral_enum: SpectralType::B,
                 luminosity: 10.0,
             },
         ];
@@ -1,7 +1,7 @@
 /// Spectral type classification and color mapping for stars
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum SpectralType {
     O,  // Blue
     B,  // Blue-white
@@ -10,9 +10,38 @@ pub enum SpectralType {
     G,  // Yellow (like Sol)
     K,  // Orange
     M,  // Red
+    L,  // Brown dwarf, deep red
+    T,  // Methane brown dwarf, magenta
+    Y,  // Coldest brown dwarf, dark violet
+    C,  // Carbon star, crimson
+    S,  // Zirconium-oxide giant, orange-red
+    W,  // Wolf-Rayet (WN, WC, WO), violet-blue
+    WhiteDwarf,  // D-class degenerate (DA, DB, DC, ...)
+    #[default]
     Unknown,
 }
 
+impl SpectralType {
+    /// Every variant, hottest main-sequence class first
+    pub const ALL: [SpectralType; 15] = [
+        SpectralType::O,
+        SpectralType::B,
+        SpectralType::A,
+        SpectralType::F,
+        SpectralType::G,
+        SpectralType::K,
+        SpectralType::M,
+        SpectralType::L,
+        SpectralType::T,
+        SpectralType::Y,
+        SpectralType::C,
+        SpectralType::S,
+        SpectralType::W,
+        SpectralType::WhiteDwarf,
+        SpectralType::Unknown,
+    ];
+}
+
 impl FromStr for SpectralType {
     type Err = String;
 
@@ -22,6 +51,19 @@ impl FromStr for SpectralType {
             return Ok(SpectralType::Unknown);
         }
 
+        // White dwarfs are written D<x> (DA, DB, DQ, ...) or WD; "dM" style dwarfs are not
+        let mut chars = s.chars();
+        let d_class = chars.next() == Some('D')
+            && chars.next().is_none_or(|c| "ABCOQZX".contains(c) || !c.is_ascii_alphabetic());
+        if d_class || s.starts_with("WD") {
+            return Ok(SpectralType::WhiteDwarf);
+        }
+
+        // Subdwarfs (sdB, sdM, ...) are colored by the class that follows the prefix
+        if let Some(rest) = s.strip_prefix("SD").filter(|rest| !rest.is_empty()) {
+            return rest.parse();
+        }
+
         match s.chars().next().unwrap() {
             'O' => Ok(SpectralType::O),
             'B' => Ok(SpectralType::B),
@@ -30,11 +72,30 @@ impl FromStr for SpectralType {
             'G' => Ok(SpectralType::G),
             'K' => Ok(SpectralType::K),
             'M' => Ok(SpectralType::M),
+            'L' => Ok(SpectralType::L),
+            'T' => Ok(SpectralType::T),
+            'Y' => Ok(SpectralType::Y),
+            'C' => Ok(SpectralType::C),
+            'S' => Ok(SpectralType::S),
+            'W' => Ok(SpectralType::W),
             _ => Ok(SpectralType::Unknown),
         }
     }
 }
 
+/// Parse a comma-separated list of spectral classes such as "G,K" or "m, k"
+/// Each entry must start with one of O, B, A, F, G, K, M, L, T, Y, C, S or W, or D for white dwarfs.
+pub fn parse_spectral_classes(list: &str) -> Result<Vec<SpectralType>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.parse::<SpectralType>()? {
+            SpectralType::Unknown => Err(format!("Unknown spectral class '{}'", entry)),
+            class => Ok(class),
+        })
+        .collect()
+}
+
 /// RGB color with alpha channel
 #[derive(Debug, Clone, Copy)]
 pub struct Color {
@@ -53,6 +114,16 @@ impl Color {
         Self { r, g, b, a: 255 }
     }
 
+    /// Parse "#rrggbb" or "rrggbb"
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let digits = hex.trim().trim_start_matches('#');
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Expected a color like #1a2b3c, got '{}'", hex));
+        }
+        let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap();
+        Ok(Self::rgb(channel(0), channel(2), channel(4)))
+    }
+
     pub fn with_alpha(&self, a: u8) -> Self {
         Self {
             r: self.r,
@@ -99,9 +170,86 @@ pub fn get_spectral_colors(spectral_type: SpectralType) -> (Color, Color) {
             Color::rgb(255, 204, 111), // Red-orange
             Color::rgba(255, 150, 80, 0),
         ),
+        SpectralType::L => (
+            Color::rgb(255, 120, 70), // Deep red
+            Color::rgba(200, 70, 40, 0),
+        ),
+        SpectralType::T => (
+            Color::rgb(200, 90, 140), // Magenta
+            Color::rgba(150, 50, 110, 0),
+        ),
+        SpectralType::Y => (
+            Color::rgb(130, 70, 130), // Dark violet
+            Color::rgba(90, 40, 100, 0),
+        ),
+        SpectralType::C => (
+            Color::rgb(230, 70, 50), // Crimson carbon star
+            Color::rgba(180, 40, 30, 0),
+        ),
+        SpectralType::S => (
+            Color::rgb(255, 150, 100), // Orange-red giant
+            Color::rgba(220, 100, 60, 0),
+        ),
+        SpectralType::W => (
+            Color::rgb(175, 155, 255), // Violet-blue, hotter than O
+            Color::rgba(130, 100, 255, 0),
+        ),
+        SpectralType::WhiteDwarf => (
+            Color::rgb(230, 240, 255), // Hot blue-white point
+            Color::rgba(190, 210, 255, 0),
+        ),
         SpectralType::Unknown => (
             Color::rgb(200, 200, 200), // Gray for unknown
             Color::rgba(150, 150, 150, 0),
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn core_rgb(spectral_type: SpectralType) -> (u8, u8, u8) {
+        let (core, _) = get_spectral_colors(spectral_type);
+        (core.r, core.g, core.b)
+    }
+
+    #[test]
+    fn test_dwarf_classes_parse_and_get_colors() {
+        assert_eq!("L5".parse::<SpectralType>().unwrap(), SpectralType::L);
+        assert_eq!("T8".parse::<SpectralType>().unwrap(), SpectralType::T);
+        assert_eq!("y0".parse::<SpectralType>().unwrap(), SpectralType::Y);
+        assert_eq!("DA".parse::<SpectralType>().unwrap(), SpectralType::WhiteDwarf);
+        assert_eq!("WD 0806-661".parse::<SpectralType>().unwrap(), SpectralType::WhiteDwarf);
+        assert_eq!("dM3".parse::<SpectralType>().unwrap(), SpectralType::Unknown);
+
+        let unknown = core_rgb(SpectralType::Unknown);
+        let dwarfs = [SpectralType::L, SpectralType::T, SpectralType::Y, SpectralType::WhiteDwarf];
+        for (i, class) in dwarfs.iter().enumerate() {
+            assert_ne!(core_rgb(*class), unknown, "{:?} uses the fallback color", class);
+            for other in &dwarfs[i + 1..] {
+                assert_ne!(core_rgb(*class), core_rgb(*other));
+            }
+        }
+
+        assert_eq!(parse_spectral_classes("L, DA").unwrap(), vec![SpectralType::L, SpectralType::WhiteDwarf]);
+    }
+
+    #[test]
+    fn test_every_class_has_its_own_color() {
+        assert_eq!("C5".parse::<SpectralType>().unwrap(), SpectralType::C);
+        assert_eq!("S4/2".parse::<SpectralType>().unwrap(), SpectralType::S);
+        assert_eq!("WC8".parse::<SpectralType>().unwrap(), SpectralType::W);
+        assert_eq!("sdB".parse::<SpectralType>().unwrap(), SpectralType::B);
+
+        let placeholder = core_rgb(SpectralType::Unknown);
+        let classes: Vec<SpectralType> =
+            SpectralType::ALL.into_iter().filter(|c| *c != SpectralType::Unknown).collect();
+        for (i, class) in classes.iter().enumerate() {
+            assert_ne!(core_rgb(*class), placeholder, "{:?} uses the fallback color", class);
+            for other in &classes[i + 1..] {
+                assert_ne!(core_rgb(*class), core_rgb(*other), "{:?} and {:?} share a color", class, other);
+            }
+        }
+    }
+}
@@ -0,0 +1,262 @@
+/// Top-down system renderer - draws a single system's orbital plane with zone overlays
+use std::path::Path;
+use anyhow::Result;
+use time::OffsetDateTime;
+use tiny_skia::{FillRule, Paint, PathBuilder, Pixmap, Stroke, StrokeDash, Transform};
+
+use crate::stellar_forge::bodies::{BodyKind, StellarBody};
+use crate::stellar_forge::containers::StarSystem;
+use crate::stellar_forge::core::Units;
+use crate::stellar_forge::physical::Physical;
+
+use super::projection::{Point2D, ProjectionEngine, SystemProjection};
+use super::spectral::{get_spectral_colors, Color, SpectralType};
+
+/// Ring between two radii, in image pixels
+#[derive(Debug, Clone)]
+pub struct Annulus {
+    pub center: Point2D,
+    pub inner_radius_px: f64,
+    pub outer_radius_px: f64,
+}
+
+pub struct SystemTopDownRenderer {
+    width: u32,
+    height: u32,
+    margin: u32,
+    background: Color,
+}
+
+impl Default for SystemTopDownRenderer {
+    fn default() -> Self {
+        Self {
+            width: 2000,
+            height: 2000,
+            margin: 100,
+            background: Color::rgb(0, 0, 0),
+        }
+    }
+}
+
+impl SystemTopDownRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Project the system at `epoch` with this renderer's dimensions
+    pub fn project(&self, system: &StarSystem, epoch: OffsetDateTime) -> SystemProjection {
+        ProjectionEngine::new(self.width, self.height, self.margin).project_system_topdown(system, epoch)
+    }
+
+    /// Habitable zone of the system on the same scale as the projected bodies
+    pub fn habitable_zone_annulus(&self, system: &StarSystem, projection: &SystemProjection) -> Option<Annulus> {
+        let (inner_m, outer_m) = system.habitable_zone()?;
+        Some(Annulus {
+            center: self.center(),
+            inner_radius_px: inner_m / Units::AU * projection.pixels_per_au,
+            outer_radius_px: outer_m / Units::AU * projection.pixels_per_au,
+        })
+    }
+
+    /// Frost line circle radius in pixels, on the same scale as the projected bodies
+    pub fn frost_line_radius_px(&self, system: &StarSystem, projection: &SystemProjection) -> Option<f64> {
+        system.frost_line_au().map(|au| au * projection.pixels_per_au)
+    }
+
+    /// One full orbit of `body` sampled from its motion model, in image pixels
+    /// Returns None for bodies without a periodic motion model.
+    pub fn orbit_track(
+        &self,
+        system: &StarSystem,
+        body: &StellarBody,
+        epoch: OffsetDateTime,
+        projection: &SystemProjection,
+    ) -> Option<Vec<Point2D>> {
+        const SAMPLES: usize = 180;
+
+        let motion = body.motion.as_ref()?;
+        let period_s = motion.orbital_period_s().filter(|p| p.is_finite() && *p > 0.0)?;
+        let barycenter = system.barycenter_at(epoch);
+        let center = self.center();
+
+        let track = (0..SAMPLES)
+            .map(|i| {
+                let at = epoch + time::Duration::seconds_f64(period_s * i as f64 / SAMPLES as f64);
+                let offset = (motion.propagate(body.state, body.epoch, at).position_m - barycenter) / Units::AU;
+                Point2D {
+                    x: center.x + offset.x * projection.pixels_per_au,
+                    y: center.y + offset.y * projection.pixels_per_au,
+                }
+            })
+            .collect();
+        Some(track)
+    }
+
+    pub fn render_to_file<P: AsRef<Path>>(
+        &self,
+        system: &StarSystem,
+        epoch: OffsetDateTime,
+        output_path: P,
+    ) -> Result<()> {
+        let mut pixmap = Pixmap::new(self.width, self.height)
+            .ok_or_else(|| anyhow::anyhow!("Invalid image size {}x{}", self.width, self.height))?;
+        pixmap.fill(self.background.to_tiny_skia());
+
+        let projection = self.project(system, epoch);
+
+        // Zones go underneath the bodies
+        if let Some(hz) = self.habitable_zone_annulus(system, &projection) {
+            Self::fill_annulus(&mut pixmap, &hz, Color::rgba(40, 200, 80, 64));
+        }
+        if let Some(radius) = self.frost_line_radius_px(system, &projection) {
+            Self::stroke_dashed_circle(&mut pixmap, &self.center(), radius as f32, Color::rgba(150, 210, 255, 200));
+        }
+
+        // Orbits of every moving body, including both components of a binary
+        for body in system.stars.iter().chain(&system.planets).chain(&system.stations) {
+            if let Some(track) = self.orbit_track(system, body, epoch, &projection) {
+                Self::stroke_closed_path(&mut pixmap, &track, Color::rgba(255, 255, 255, 70));
+            }
+        }
+
+        for body in &projection.bodies {
+            let (color, radius) = match body.kind {
+                BodyKind::Star => {
+                    let spectral = system
+                        .stars
+                        .iter()
+                        .find(|s| s.id == body.id)
+                        .and_then(|s| match &s.physical {
+                            Some(Physical::Star(star)) => star.spectral_type.parse().ok(),
+                            _ => None,
+                        })
+                        .unwrap_or(SpectralType::Unknown);
+                    (get_spectral_colors(spectral).0, 12.0)
+                }
+                BodyKind::Station => (Color::rgb(200, 200, 220), 3.0),
+                _ => (Color::rgb(120, 170, 255), 6.0),
+            };
+            Self::fill_circle(&mut pixmap, &body.point, radius, color);
+        }
+
+        pixmap.save_png(output_path)?;
+        Ok(())
+    }
+
+    fn center(&self) -> Point2D {
+        Point2D {
+            x: self.width as f64 / 2.0,
+            y: self.height as f64 / 2.0,
+        }
+    }
+
+    fn paint(color: Color) -> Paint<'static> {
+        let mut paint = Paint::default();
+        paint.set_color(color.to_tiny_skia());
+        paint.anti_alias = true;
+        paint
+    }
+
+    fn fill_circle(pixmap: &mut Pixmap, center: &Point2D, radius: f32, color: Color) {
+        if let Some(path) = PathBuilder::from_circle(center.x as f32, center.y as f32, radius) {
+            pixmap.fill_path(&path, &Self::paint(color), FillRule::Winding, Transform::identity(), None);
+        }
+    }
+
+    fn stroke_dashed_circle(pixmap: &mut Pixmap, center: &Point2D, radius: f32, color: Color) {
+        if let Some(path) = PathBuilder::from_circle(center.x as f32, center.y as f32, radius) {
+            let stroke = Stroke {
+                width: 2.0,
+                dash: StrokeDash::new(vec![12.0, 8.0], 0.0),
+                ..Stroke::default()
+            };
+            pixmap.stroke_path(&path, &Self::paint(color), &stroke, Transform::identity(), None);
+        }
+    }
+
+    fn stroke_closed_path(pixmap: &mut Pixmap, points: &[Point2D], color: Color) {
+        let Some((first, rest)) = points.split_first() else {
+            return;
+        };
+        let mut builder = PathBuilder::new();
+        builder.move_to(first.x as f32, first.y as f32);
+        for point in rest {
+            builder.line_to(point.x as f32, point.y as f32);
+        }
+        builder.close();
+        if let Some(path) = builder.finish() {
+            let stroke = Stroke { width: 1.0, ..Stroke::default() };
+            pixmap.stroke_path(&path, &Self::paint(color), &stroke, Transform::identity(), None);
+        }
+    }
+
+    fn fill_annulus(pixmap: &mut Pixmap, annulus: &Annulus, color: Color) {
+        let mut builder = PathBuilder::new();
+        builder.push_circle(annulus.center.x as f32, annulus.center.y as f32, annulus.outer_radius_px as f32);
+        builder.push_circle(annulus.center.x as f32, annulus.center.y as f32, annulus.inner_radius_px as f32);
+        if let Some(path) = builder.finish() {
+            // Even-odd leaves the inner disc empty
+            pixmap.fill_path(&path, &Self::paint(color), FillRule::EvenOdd, Transform::identity(), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stellar_forge::builders::{PlanetBuilder, SystemBuilder};
+
+    /// G2V system with the star pinned to exactly one solar luminosity
+    fn sun_like_system() -> StarSystem {
+        let mut system = SystemBuilder::new("Test")
+            .with_star("G2V")
+            .with_planet(PlanetBuilder::terrestrial("Outer", 3.0))
+            .build();
+        if let Some(Physical::Star(star)) = &mut system.stars[0].physical {
+            star.luminosity_w = Units::SOLAR_LUMINOSITY;
+        }
+        system
+    }
+
+    #[test]
+    fn test_habitable_zone_annulus() {
+        let system = sun_like_system();
+        let renderer = SystemTopDownRenderer::new(1000, 1000);
+        let projection = renderer.project(&system, system.planets[0].epoch);
+        let hz = renderer.habitable_zone_annulus(&system, &projection).unwrap();
+
+        let inner_au = hz.inner_radius_px / projection.pixels_per_au;
+        let outer_au = hz.outer_radius_px / projection.pixels_per_au;
+        assert!((inner_au - 0.95).abs() < 0.01, "inner edge at {} AU", inner_au);
+        assert!((outer_au - 1.37).abs() < 0.01, "outer edge at {} AU", outer_au);
+        assert_eq!((hz.center.x, hz.center.y), (500.0, 500.0));
+    }
+
+    #[test]
+    fn test_binary_components_orbit_center() {
+        let system = SystemBuilder::new("Pair").with_binary_stars("G2V", "M2V", 10.0).build();
+        let renderer = SystemTopDownRenderer::new(1000, 1000);
+        let epoch = system.stars[0].epoch;
+        let projection = renderer.project(&system, epoch);
+
+        let stars: Vec<_> = projection.bodies.iter().filter(|b| b.kind == BodyKind::Star).collect();
+        assert_eq!(stars.len(), 2);
+
+        // Components sit on opposite sides of the image center
+        let (a, b) = (&stars[0].point, &stars[1].point);
+        assert!((a.x - 500.0) * (b.x - 500.0) + (a.y - 500.0) * (b.y - 500.0) < 0.0);
+
+        for (star, projected) in system.stars.iter().zip(&stars) {
+            let track = renderer.orbit_track(&system, star, epoch, &projection).unwrap();
+            let radius = (projected.point.x - 500.0).hypot(projected.point.y - 500.0);
+            for point in &track {
+                let r = (point.x - 500.0).hypot(point.y - 500.0);
+                assert!((r - radius).abs() < 1e-6 * radius.max(1.0), "{} orbit left its circle", star.name);
+            }
+        }
+    }
+}